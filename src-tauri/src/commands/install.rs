@@ -1,45 +1,84 @@
-use crate::errors::AppResult;
-use crate::models::install::InstallProgress;
+use std::path::Path;
+
+use crate::errors::{AppError, AppResult};
+use crate::models::dependency_plan::{DependencyPlan, ModInstallResult};
+use crate::models::install::{InstallCheckpoint, InstallProgress};
 use crate::models::instance::MinecraftInstance;
-use crate::models::mod_info::{ModInfo, ModSource};
+use crate::models::mod_info::{ModInfo, ModSource, RescanReport, UpdateCheckReport, UpdateResult};
+use crate::models::mod_platform::ReleaseChannel;
 use crate::services::database::DatabaseService;
+use crate::services::dependency_resolver;
 use crate::services::download::DownloadService;
 use crate::services::install::InstallService;
+use crate::services::jobs::{JobKind, JobRegistry};
 use crate::services::loader::LoaderService;
 use crate::services::minecraft::MinecraftService;
 use crate::services::mod_platform::UnifiedModClient;
+use crate::services::mod_scan;
+use crate::services::mod_update;
+use crate::services::store::StoreGcReport;
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn install_mod(
     install_service: tauri::State<'_, InstallService>,
     mod_client: tauri::State<'_, UnifiedModClient>,
     download_service: tauri::State<'_, DownloadService>,
+    jobs: tauri::State<'_, JobRegistry>,
     db: tauri::State<'_, DatabaseService>,
     instance_id: String,
     source: ModSource,
     project_id: String,
     version_id: String,
-) -> AppResult<ModInfo> {
+    install_dependencies: Option<bool>,
+    job_id: Option<String>,
+) -> AppResult<ModInstallResult> {
+    let job = jobs.start(job_id, JobKind::ModInstall)?;
     install_service
         .install_mod(
             &db,
             &mod_client,
             &download_service,
+            &job,
             &instance_id,
             &source,
             &project_id,
             &version_id,
+            install_dependencies.unwrap_or(false),
         )
         .await
 }
 
+/// What installing a mod version would pull in, skip or clash with.
 #[tauri::command]
+pub async fn plan_mod_install(
+    mod_client: tauri::State<'_, UnifiedModClient>,
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+    source: ModSource,
+    project_id: String,
+    version_id: String,
+) -> AppResult<DependencyPlan> {
+    dependency_resolver::plan_install(
+        &db,
+        &mod_client,
+        &instance_id,
+        &source,
+        &project_id,
+        &version_id,
+    )
+    .await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn install_modpack(
     install_service: tauri::State<'_, InstallService>,
     mod_client: tauri::State<'_, UnifiedModClient>,
     download_service: tauri::State<'_, DownloadService>,
     mc_service: tauri::State<'_, MinecraftService>,
     loader_service: tauri::State<'_, LoaderService>,
+    jobs: tauri::State<'_, JobRegistry>,
     db: tauri::State<'_, DatabaseService>,
     source: ModSource,
     project_id: String,
@@ -47,7 +86,9 @@ pub async fn install_modpack(
     modpack_name: Option<String>,
     modpack_icon_url: Option<String>,
     modpack_description: Option<String>,
+    job_id: Option<String>,
 ) -> AppResult<MinecraftInstance> {
+    let job = jobs.start(job_id, JobKind::ModpackInstall)?;
     install_service
         .install_modpack(
             &db,
@@ -55,6 +96,7 @@ pub async fn install_modpack(
             &download_service,
             &mc_service,
             &loader_service,
+            &job,
             &source,
             &project_id,
             &version_id,
@@ -65,11 +107,104 @@ pub async fn install_modpack(
         .await
 }
 
+/// Install a modpack from a local `.mrpack` or CurseForge zip.
 #[tauri::command]
-pub fn get_install_progress(
+#[allow(clippy::too_many_arguments)]
+pub async fn import_modpack_file(
+    install_service: tauri::State<'_, InstallService>,
+    mod_client: tauri::State<'_, UnifiedModClient>,
+    download_service: tauri::State<'_, DownloadService>,
+    mc_service: tauri::State<'_, MinecraftService>,
+    loader_service: tauri::State<'_, LoaderService>,
+    jobs: tauri::State<'_, JobRegistry>,
+    db: tauri::State<'_, DatabaseService>,
+    path: String,
+    job_id: Option<String>,
+) -> AppResult<MinecraftInstance> {
+    let job = jobs.start(job_id, JobKind::ModpackInstall)?;
+    install_service
+        .import_modpack_file(
+            &db,
+            &mod_client,
+            &download_service,
+            &mc_service,
+            &loader_service,
+            &job,
+            Path::new(&path),
+        )
+        .await
+}
+
+/// Continue a modpack install that stopped before completing.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn resume_install(
+    install_service: tauri::State<'_, InstallService>,
+    mod_client: tauri::State<'_, UnifiedModClient>,
+    download_service: tauri::State<'_, DownloadService>,
+    mc_service: tauri::State<'_, MinecraftService>,
+    loader_service: tauri::State<'_, LoaderService>,
+    jobs: tauri::State<'_, JobRegistry>,
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+    skip_failed_optional: Option<bool>,
+    job_id: Option<String>,
+) -> AppResult<MinecraftInstance> {
+    let job = jobs.start(job_id, JobKind::ModpackInstall)?;
+    install_service
+        .resume_install(
+            &db,
+            &mod_client,
+            &download_service,
+            &mc_service,
+            &loader_service,
+            &job,
+            &instance_id,
+            skip_failed_optional.unwrap_or(false),
+        )
+        .await
+}
+
+/// Modpack installs that can be resumed.
+#[tauri::command]
+pub fn list_unfinished_installs(
+    db: tauri::State<'_, DatabaseService>,
+) -> AppResult<Vec<InstallCheckpoint>> {
+    db.list_install_checkpoints()
+}
+
+#[tauri::command]
+pub async fn discard_install(
     install_service: tauri::State<'_, InstallService>,
+    mc_service: tauri::State<'_, MinecraftService>,
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+) -> AppResult<()> {
+    install_service
+        .discard_install(&db, mc_service.base_dir(), &instance_id)
+        .await
+}
+
+/// Stop an install job; it reports `InstallStage::Cancelled` once stopped.
+#[tauri::command]
+pub fn cancel_install(jobs: tauri::State<'_, JobRegistry>, job_id: String) -> AppResult<()> {
+    jobs.cancel(&job_id, JobKind::is_install)
+}
+
+/// Progress of an install job, or of the latest install when no id is given.
+#[tauri::command]
+pub fn get_install_progress(
+    jobs: tauri::State<'_, JobRegistry>,
+    job_id: Option<String>,
 ) -> AppResult<InstallProgress> {
-    install_service.get_progress()
+    let job = match job_id {
+        Some(id) => Some(
+            jobs.get(&id)?
+                .ok_or_else(|| AppError::Custom(format!("Job not found: {id}")))?,
+        ),
+        None => jobs.latest(JobKind::is_install)?,
+    };
+    Ok(job.map_or_else(InstallProgress::idle, |job| job.progress))
 }
 
 #[tauri::command]
@@ -85,6 +220,79 @@ pub fn remove_mod(
     install_service: tauri::State<'_, InstallService>,
     db: tauri::State<'_, DatabaseService>,
     mod_id: String,
-) -> AppResult<()> {
+) -> AppResult<Vec<ModInfo>> {
     install_service.remove_mod(&db, &mod_id)
 }
+
+#[tauri::command]
+pub fn toggle_mod(
+    install_service: tauri::State<'_, InstallService>,
+    db: tauri::State<'_, DatabaseService>,
+    mod_id: String,
+    enabled: bool,
+) -> AppResult<ModInfo> {
+    install_service.toggle_mod(&db, &mod_id, enabled)
+}
+
+#[tauri::command]
+pub async fn rescan_instance_mods(
+    mod_client: tauri::State<'_, UnifiedModClient>,
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+) -> AppResult<RescanReport> {
+    mod_scan::rescan_instance_mods(&db, &mod_client, &instance_id).await
+}
+
+#[tauri::command]
+pub async fn check_mod_updates(
+    mod_client: tauri::State<'_, UnifiedModClient>,
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+    min_channel: Option<ReleaseChannel>,
+) -> AppResult<UpdateCheckReport> {
+    mod_update::check_mod_updates(&db, &mod_client, &instance_id, min_channel).await
+}
+
+#[tauri::command]
+pub async fn update_mods(
+    mod_client: tauri::State<'_, UnifiedModClient>,
+    download_service: tauri::State<'_, DownloadService>,
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+    mod_ids: Vec<String>,
+    min_channel: Option<ReleaseChannel>,
+) -> AppResult<UpdateResult> {
+    mod_update::update_mods(
+        &db,
+        &mod_client,
+        &download_service,
+        &instance_id,
+        &mod_ids,
+        min_channel,
+    )
+    .await
+}
+
+/// Delete shared store files no installed mod uses any more. Refused while
+/// an install runs, since its files are not recorded as installed yet.
+#[tauri::command]
+pub async fn gc_store(
+    download_service: tauri::State<'_, DownloadService>,
+    db: tauri::State<'_, DatabaseService>,
+    jobs: tauri::State<'_, JobRegistry>,
+) -> AppResult<StoreGcReport> {
+    let Some(store) = download_service.store() else {
+        return Ok(StoreGcReport::default());
+    };
+    if jobs
+        .list()?
+        .iter()
+        .any(|job| job.kind.is_install() && !job.finished)
+    {
+        return Err(AppError::Custom(
+            "Cannot clean the file store while an install is running".to_string(),
+        ));
+    }
+    let referenced = db.list_mod_file_hashes()?;
+    store.gc(&referenced).await
+}
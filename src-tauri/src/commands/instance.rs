@@ -1,9 +1,17 @@
 use std::path::Path;
 
 use crate::errors::{AppError, AppResult};
+use crate::models::export::{CurseForgeExportOptions, ExportReport, MrpackExportOptions};
 use crate::models::instance::{MinecraftInstance, ModLoader};
+use crate::models::instance_check::InstanceCheckReport;
+use crate::models::launcher_import::LauncherImportReport;
+use crate::models::mod_platform::ReleaseChannel;
 use crate::services::database::DatabaseService;
+use crate::services::export;
+use crate::services::instance_check;
+use crate::services::launcher_import;
 use crate::services::minecraft::MinecraftService;
+use crate::services::mod_platform::UnifiedModClient;
 
 #[tauri::command]
 pub fn list_instances(db: tauri::State<'_, DatabaseService>) -> AppResult<Vec<MinecraftInstance>> {
@@ -63,6 +71,64 @@ pub async fn delete_instance(db: tauri::State<'_, DatabaseService>, id: String)
     db.delete_instance(&id)
 }
 
+/// Set the least stable release channel accepted for an instance's mods.
+#[tauri::command]
+pub fn set_instance_release_channel(
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+    channel: ReleaseChannel,
+) -> AppResult<()> {
+    db.set_instance_release_channel(&instance_id, channel)
+}
+
+/// Offline check of an instance's mods for missing dependencies, conflicts,
+/// duplicates and version/loader mismatches.
+#[tauri::command]
+pub fn check_instance(
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+) -> AppResult<InstanceCheckReport> {
+    instance_check::check_instance(&db, &instance_id)
+}
+
+/// Export an instance as a Modrinth `.mrpack`.
+#[tauri::command]
+pub async fn export_instance_mrpack(
+    db: tauri::State<'_, DatabaseService>,
+    mod_client: tauri::State<'_, UnifiedModClient>,
+    instance_id: String,
+    options: MrpackExportOptions,
+) -> AppResult<ExportReport> {
+    export::export_instance_mrpack(&db, &mod_client, &instance_id, &options).await
+}
+
+/// Export an instance as a CurseForge modpack zip.
+#[tauri::command]
+pub async fn export_instance_curseforge(
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+    options: CurseForgeExportOptions,
+) -> AppResult<ExportReport> {
+    export::export_instance_curseforge(&db, &instance_id, &options).await
+}
+
+/// Import instances from Prism/MultiMC, ATLauncher or the official launcher.
+#[tauri::command]
+pub async fn import_launcher_instances(
+    db: tauri::State<'_, DatabaseService>,
+    mod_client: tauri::State<'_, UnifiedModClient>,
+    mc_service: tauri::State<'_, MinecraftService>,
+    path: String,
+) -> AppResult<LauncherImportReport> {
+    launcher_import::import_launcher_instances(
+        &db,
+        &mod_client,
+        mc_service.base_dir(),
+        Path::new(&path),
+    )
+    .await
+}
+
 fn build_instance(
     name: String,
     minecraft_version: String,
@@ -91,6 +157,7 @@ fn build_instance(
         last_played_at: None,
         total_play_time: 0,
         is_active: true,
+        min_release_channel: ReleaseChannel::default(),
         created_at: now,
         updated_at: now,
     })
@@ -0,0 +1,8 @@
+use crate::errors::AppResult;
+use crate::services::jobs::{JobInfo, JobRegistry};
+
+/// Running and recently finished install and download jobs.
+#[tauri::command]
+pub fn list_jobs(jobs: tauri::State<'_, JobRegistry>) -> AppResult<Vec<JobInfo>> {
+    jobs.list()
+}
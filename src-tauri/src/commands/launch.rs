@@ -3,12 +3,15 @@ use crate::models::instance::ModLoader;
 use crate::models::launch::{CrashLog, GameStatus, LaunchInfo};
 use crate::services::database::DatabaseService;
 use crate::services::download::DownloadService;
+use crate::services::install;
+use crate::services::instance_check;
 use crate::services::java::JavaService;
 use crate::services::launch::LaunchService;
 use crate::services::loader::LoaderService;
 use crate::services::minecraft::MinecraftService;
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn launch_instance(
     launch_svc: tauri::State<'_, LaunchService>,
     mc_svc: tauri::State<'_, MinecraftService>,
@@ -19,12 +22,27 @@ pub async fn launch_instance(
     app_handle: tauri::AppHandle,
     instance_id: String,
     java_path: Option<String>,
+    force: Option<bool>,
 ) -> AppResult<LaunchInfo> {
     // Fetch instance from DB
     let instance = db
         .get_instance(&instance_id)?
         .ok_or_else(|| AppError::Custom(format!("Instance not found: {instance_id}")))?;
 
+    // Disabled mods must not be visible to the loader
+    install::apply_mod_enabled_states(&db, &instance)?;
+
+    // Refuse to start a game that cannot load its mods, unless the user insists
+    let report = instance_check::check_instance(&db, &instance_id)?;
+    if report.is_blocking() && !force.unwrap_or(false) {
+        return Err(AppError::ModProblems(Box::new(report)));
+    }
+    if !report.is_clean() {
+        for problem in report.summary() {
+            log::warn!("[LAUNCH] {problem}");
+        }
+    }
+
     // Fetch account (need auth token for Minecraft)
     let account = db
         .get_active_account()?
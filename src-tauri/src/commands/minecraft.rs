@@ -1,5 +1,6 @@
 use crate::errors::{AppError, AppResult};
 use crate::services::download::{DownloadProgress, DownloadService};
+use crate::services::jobs::{JobKind, JobRegistry};
 use crate::services::minecraft::{MinecraftService, VersionEntry};
 
 #[tauri::command]
@@ -9,13 +10,18 @@ pub async fn list_mc_versions(
     mc.fetch_version_manifest().await
 }
 
+/// Start downloading a Minecraft version in the background, returning its job id.
 #[tauri::command]
 pub async fn download_version(
     mc: tauri::State<'_, MinecraftService>,
     dl: tauri::State<'_, DownloadService>,
+    jobs: tauri::State<'_, JobRegistry>,
     version_id: String,
-) -> AppResult<()> {
-    if dl.is_downloading()? {
+) -> AppResult<String> {
+    let running = jobs
+        .latest(|kind| kind == JobKind::MinecraftDownload)?
+        .is_some_and(|job| !job.finished);
+    if running {
         return Err(AppError::Custom(
             "A download is already in progress".to_string(),
         ));
@@ -25,18 +31,43 @@ pub async fn download_version(
     let tasks = mc.resolve_downloads(&detail).await?;
 
     // Run downloads in background so the command returns immediately
-    let dl_clone = DownloadService::clone(&*dl);
-    let vid = version_id.clone();
+    let job = jobs.start(None, JobKind::MinecraftDownload)?;
+    let job_id = job.id().to_string();
+    let dl_job = job.download_service(&dl);
     tokio::spawn(async move {
-        if let Err(e) = dl_clone.download_all(tasks).await {
-            log::error!("Download failed for version {vid}: {e}");
+        match dl_job.download_all_with_failures(tasks).await {
+            Ok(failures) if failures.is_empty() => job.complete(),
+            Ok(failures) => job.fail(&AppError::Custom(format!(
+                "{} files failed",
+                failures.len()
+            ))),
+            Err(e) => {
+                log::error!("Download failed for version {version_id}: {e}");
+                job.fail(&e);
+            }
         }
     });
 
-    Ok(())
+    Ok(job_id)
 }
 
 #[tauri::command]
-pub fn get_download_progress(dl: tauri::State<'_, DownloadService>) -> AppResult<DownloadProgress> {
-    dl.get_progress()
+pub fn cancel_download(jobs: tauri::State<'_, JobRegistry>, job_id: String) -> AppResult<()> {
+    jobs.cancel(&job_id, |kind| kind == JobKind::MinecraftDownload)
+}
+
+/// Download progress of a job, or of the latest Minecraft download.
+#[tauri::command]
+pub fn get_download_progress(
+    jobs: tauri::State<'_, JobRegistry>,
+    job_id: Option<String>,
+) -> AppResult<DownloadProgress> {
+    let job = match job_id {
+        Some(id) => Some(
+            jobs.get(&id)?
+                .ok_or_else(|| AppError::Custom(format!("Job not found: {id}")))?,
+        ),
+        None => jobs.latest(|kind| kind == JobKind::MinecraftDownload)?,
+    };
+    Ok(job.map_or_else(DownloadProgress::idle, |job| job.downloads))
 }
@@ -3,6 +3,7 @@ pub mod auth;
 pub mod install;
 pub mod instance;
 pub mod java;
+pub mod jobs;
 pub mod launch;
 pub mod loader;
 pub mod minecraft;
@@ -1,9 +1,10 @@
-use crate::errors::AppResult;
+use crate::errors::{AppError, AppResult};
 use crate::models::mod_info::ModSource;
 use crate::models::mod_platform::{
-    ContentType, ModDetails, ModVersionInfo, SearchFilters, SearchResponse,
+    ContentType, ModDetails, ModVersionInfo, ReleaseChannel, SearchFilters, SearchResponse,
 };
-use crate::services::mod_platform::UnifiedModClient;
+use crate::services::database::DatabaseService;
+use crate::services::mod_platform::{find_incompatibility, UnifiedModClient};
 
 #[tauri::command]
 pub async fn search_mods(
@@ -32,6 +33,8 @@ pub async fn get_mod_details(
     client.get_mod(&source, &project_id).await
 }
 
+/// Versions of a project, newest first. With `min_channel`, less stable
+/// versions are left out.
 #[tauri::command]
 pub async fn get_mod_versions(
     client: tauri::State<'_, UnifiedModClient>,
@@ -39,15 +42,71 @@ pub async fn get_mod_versions(
     project_id: String,
     game_version: Option<String>,
     loader: Option<String>,
+    min_channel: Option<ReleaseChannel>,
 ) -> AppResult<Vec<ModVersionInfo>> {
-    client
+    let versions = client
         .get_versions(
             &source,
             &project_id,
             game_version.as_deref(),
             loader.as_deref(),
         )
-        .await
+        .await?;
+
+    Ok(match min_channel {
+        Some(min) => versions
+            .into_iter()
+            .filter(|v| v.channel.satisfies(min))
+            .collect(),
+        None => versions,
+    })
+}
+
+/// Many Modrinth projects in one round trip, e.g. to describe a synced pack.
+#[tauri::command]
+pub async fn get_modrinth_projects(
+    client: tauri::State<'_, UnifiedModClient>,
+    project_ids: Vec<String>,
+) -> AppResult<Vec<ModDetails>> {
+    client.get_modrinth_projects(&project_ids).await
+}
+
+/// Many Modrinth versions in one round trip, e.g. to download a synced pack.
+#[tauri::command]
+pub async fn get_modrinth_versions(
+    client: tauri::State<'_, UnifiedModClient>,
+    version_ids: Vec<String>,
+) -> AppResult<Vec<ModVersionInfo>> {
+    client.get_modrinth_versions(&version_ids).await
+}
+
+/// Versions of a project that can be installed into an instance, newest first.
+#[tauri::command]
+pub async fn get_compatible_versions(
+    client: tauri::State<'_, UnifiedModClient>,
+    db: tauri::State<'_, DatabaseService>,
+    instance_id: String,
+    source: ModSource,
+    project_id: String,
+) -> AppResult<Vec<ModVersionInfo>> {
+    let instance = db
+        .get_instance(&instance_id)?
+        .ok_or_else(|| AppError::Custom(format!("Instance not found: {instance_id}")))?;
+
+    // Loader filtering happens locally: Quilt also accepts Fabric versions
+    let versions = client
+        .get_versions(
+            &source,
+            &project_id,
+            Some(&instance.minecraft_version),
+            None,
+        )
+        .await?;
+
+    Ok(versions
+        .into_iter()
+        .filter(|v| find_incompatibility(v, &instance).is_none())
+        .collect())
 }
 
 #[tauri::command]
@@ -58,6 +117,7 @@ pub async fn resolve_mod_dependencies(
     version_id: String,
     game_version: Option<String>,
     loader: Option<String>,
+    min_channel: Option<ReleaseChannel>,
 ) -> AppResult<Vec<ModVersionInfo>> {
     // Fetch versions to find the specific one
     let versions = client
@@ -77,6 +137,11 @@ pub async fn resolve_mod_dependencies(
         })?;
 
     client
-        .resolve_dependencies(&version, game_version.as_deref(), loader.as_deref())
+        .resolve_dependencies(
+            &version,
+            game_version.as_deref(),
+            loader.as_deref(),
+            min_channel.unwrap_or_default(),
+        )
         .await
 }
@@ -88,8 +88,10 @@ pub async fn share_modpack(
             ref l => Some(l.to_string()),
         },
         loader_version: instance.loader_version,
+        // Disabled mods stay local: peers should get the pack as it is played
         mods: mods
             .into_iter()
+            .filter(|m| m.enabled)
             .map(|m| crate::models::sync::SyncModEntry {
                 mod_name: m.name,
                 mod_version: m.version,
@@ -98,6 +100,7 @@ pub async fn share_modpack(
                 source: m.source.to_string(),
                 source_project_id: m.source_project_id,
                 source_version_id: m.source_version_id,
+                enabled: true,
             })
             .collect(),
         manifest_version: 1,
@@ -42,6 +42,7 @@ pub fn preview_sync(
                 source: m.source.to_string(),
                 source_project_id: m.source_project_id,
                 source_version_id: m.source_version_id,
+                enabled: m.enabled,
             })
             .collect(),
         manifest_version: 1,
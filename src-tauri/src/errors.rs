@@ -1,5 +1,9 @@
+use serde::ser::SerializeStruct;
 use thiserror::Error;
 
+use crate::models::instance_check::InstanceCheckReport;
+use crate::models::mod_platform::VersionIncompatibility;
+
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("Database error: {0}")]
@@ -17,17 +21,45 @@ pub enum AppError {
     #[error("P2P error: {0}")]
     P2p(String),
 
+    #[error("{0} is rate limiting requests, try again in a moment")]
+    RateLimited(String),
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("{0}")]
+    IncompatibleVersion(Box<VersionIncompatibility>),
+
+    #[error("Instance has mod problems: {}", .0.summary().join("; "))]
+    ModProblems(Box<InstanceCheckReport>),
+
     #[error("{0}")]
     Custom(String),
 }
 
-// Tauri commands need serializable errors
+// Tauri commands need serializable errors. Most are plain messages; the
+// incompatibility and the pre-launch check report also carry their details
+// so the UI can explain them.
 impl serde::Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        match self {
+            Self::IncompatibleVersion(details) => {
+                let mut state = serializer.serialize_struct("AppError", 2)?;
+                state.serialize_field("message", &self.to_string())?;
+                state.serialize_field("incompatible_version", details)?;
+                state.end()
+            }
+            Self::ModProblems(report) => {
+                let mut state = serializer.serialize_struct("AppError", 2)?;
+                state.serialize_field("message", &self.to_string())?;
+                state.serialize_field("mod_problems", report)?;
+                state.end()
+            }
+            _ => serializer.serialize_str(&self.to_string()),
+        }
     }
 }
 
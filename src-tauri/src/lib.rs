@@ -2,20 +2,26 @@ mod commands;
 mod errors;
 mod models;
 mod services;
+#[cfg(test)]
+mod test_support;
 
 use commands::{
-    account, auth, install, instance, java, launch, loader, minecraft, mods, p2p, sync,
+    account, auth, install, instance, java, jobs, launch, loader, minecraft, mods, p2p, sync,
     sync_protocol,
 };
+use models::endpoints::Endpoints;
 use services::auth::AuthService;
 use services::database::DatabaseService;
 use services::download::DownloadService;
+use services::http_cache::{HttpCache, MAX_ENTRY_AGE};
 use services::install::InstallService;
 use services::java::JavaService;
+use services::jobs::JobRegistry;
 use services::launch::LaunchService;
 use services::loader::LoaderService;
 use services::minecraft::MinecraftService;
 use services::mod_platform::UnifiedModClient;
+use services::store::FileStore;
 use services::sync_protocol::SyncProtocolService;
 use tauri::Manager;
 
@@ -43,23 +49,35 @@ pub fn run() {
             let db = DatabaseService::new(&db_path)?;
             app.manage(db);
 
+            // Remote service URLs (MINESYNC_*_URL overrides for mirrors and test stubs)
+            let endpoints = Endpoints::from_env();
+
+            // Metadata responses, kept for offline browsing and launch prep
+            let http_cache = HttpCache::new(app_dir.join("cache").join("http"));
+            let stale_cache = http_cache.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = stale_cache.prune(MAX_ENTRY_AGE).await {
+                    log::warn!("[INIT] HTTP cache cleanup failed: {e}");
+                }
+            });
+
             // Auth service
-            app.manage(AuthService::new());
+            app.manage(AuthService::new(&endpoints));
 
             // Minecraft version manager
-            app.manage(MinecraftService::new(app_dir.clone()));
+            app.manage(MinecraftService::new(app_dir.clone(), &endpoints, http_cache.clone()));
 
-            // Download manager
-            app.manage(DownloadService::new());
+            // Download manager, sharing mod files across instances through the store
+            app.manage(DownloadService::new().with_store(FileStore::new(app_dir.join("store"))));
 
             // Mod loader installer service
-            app.manage(LoaderService::new(app_dir.clone()));
+            app.manage(LoaderService::new(app_dir.clone(), &endpoints));
 
             // Game launcher
             app.manage(LaunchService::new(app_dir.clone()));
 
             // Java runtime manager (portable Java 21)
-            app.manage(JavaService::new(app_dir.clone()));
+            app.manage(JavaService::new(app_dir.clone(), &endpoints));
 
             // Mod platform client (CurseForge + Modrinth)
             // CurseForge API key is optional — pass None to use Modrinth only
@@ -67,7 +85,7 @@ pub fn run() {
             if let Some(ref key) = cf_key {
                 log::info!("[INIT] CurseForge API key loaded (len={})", key.len());
             }
-            app.manage(UnifiedModClient::new(cf_key));
+            app.manage(UnifiedModClient::new(cf_key, &endpoints, http_cache));
 
             // P2P service (starts as None, activated via command)
             let p2p_state: p2p::P2pState = std::sync::Arc::new(tokio::sync::Mutex::new(None));
@@ -79,6 +97,9 @@ pub fn run() {
             // Install service (mod + modpack installation)
             app.manage(InstallService::new());
 
+            // Install and download jobs with their progress
+            app.manage(JobRegistry::new());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -86,6 +107,11 @@ pub fn run() {
             instance::get_instance,
             instance::create_instance,
             instance::delete_instance,
+            instance::check_instance,
+            instance::set_instance_release_channel,
+            instance::export_instance_mrpack,
+            instance::export_instance_curseforge,
+            instance::import_launcher_instances,
             sync::create_sync_session,
             sync::join_sync_session,
             account::get_active_account,
@@ -98,6 +124,7 @@ pub fn run() {
             minecraft::list_mc_versions,
             minecraft::download_version,
             minecraft::get_download_progress,
+            minecraft::cancel_download,
             java::get_java_status,
             java::get_java_install_progress,
             java::install_java_runtime,
@@ -111,6 +138,9 @@ pub fn run() {
             mods::search_modpacks,
             mods::get_mod_details,
             mods::get_mod_versions,
+            mods::get_compatible_versions,
+            mods::get_modrinth_projects,
+            mods::get_modrinth_versions,
             mods::resolve_mod_dependencies,
             sync_protocol::preview_sync,
             sync_protocol::get_pending_sync,
@@ -127,10 +157,22 @@ pub fn run() {
             launch::get_crash_log,
             launch::clear_crash_log,
             install::install_mod,
+            install::plan_mod_install,
             install::install_modpack,
+            install::import_modpack_file,
+            install::resume_install,
+            install::list_unfinished_installs,
+            install::discard_install,
             install::get_install_progress,
+            install::cancel_install,
+            jobs::list_jobs,
             install::list_instance_mods,
             install::remove_mod,
+            install::toggle_mod,
+            install::rescan_instance_mods,
+            install::check_mod_updates,
+            install::update_mods,
+            install::gc_store,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use super::mod_info::{ModInfo, ModSource};
+use super::mod_platform::ModVersionInfo;
+
+/// Everything installing one mod version into an instance involves: the
+/// versions to download, dependencies already present, and conflicts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependencyPlan {
+    pub instance_id: String,
+    /// The requested version first, then every required dependency missing
+    /// from the instance.
+    pub install: Vec<PlannedMod>,
+    pub skip: Vec<SkippedDependency>,
+    pub conflicts: Vec<DependencyConflict>,
+    pub unresolved: Vec<UnresolvedDependency>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedMod {
+    pub version: ModVersionInfo,
+    /// Project slug, when it was looked up to match installed mods.
+    pub slug: Option<String>,
+    /// Project id of the planned mod that needs this one; `None` for the
+    /// requested mod itself.
+    pub required_by: Option<String>,
+}
+
+/// A required dependency already satisfied by an installed mod.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkippedDependency {
+    pub project_id: String,
+    pub source: ModSource,
+    pub required_by: String,
+    /// `instance_mods` row providing it.
+    pub installed_mod_id: String,
+    pub installed_name: String,
+    /// A disabled mod satisfies the plan but must be re-enabled to load.
+    pub installed_enabled: bool,
+}
+
+/// An `Incompatible` declaration between a planned mod and an installed or
+/// another planned one, in either direction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DependencyConflict {
+    pub project_id: String,
+    pub mod_name: String,
+    pub other_project_id: String,
+    pub other_name: String,
+    /// `instance_mods` row of the other mod; `None` when it is also planned.
+    pub installed_mod_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnresolvedDependency {
+    pub project_id: String,
+    pub source: ModSource,
+    pub required_by: String,
+    pub reason: String,
+}
+
+/// Outcome of installing a mod version together with its dependencies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModInstallResult {
+    pub installed: ModInfo,
+    /// Dependencies installed alongside it.
+    pub dependencies: Vec<ModInfo>,
+    /// Required dependencies still missing, because no compatible version
+    /// was found or their download failed. The mod may not load without them.
+    pub unresolved: Vec<UnresolvedDependency>,
+}
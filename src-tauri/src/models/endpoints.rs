@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+/// Base URLs of every remote service the app talks to.
+///
+/// Defaults point at the official services. Each entry can be overridden
+/// with a `MINESYNC_*_URL` environment variable (or `.env` entry) to use a
+/// mirror, a self-hosted proxy, or a local stub server in tests.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Endpoints {
+    pub modrinth_api: String,
+    pub curseforge_api: String,
+    /// Fallback download host when CurseForge returns no `downloadUrl`.
+    pub curseforge_cdn: String,
+    pub mojang_version_manifest: String,
+    pub mojang_assets: String,
+    pub fabric_meta: String,
+    /// Fallback repository for Fabric libraries without a `url`.
+    pub fabric_maven: String,
+    pub quilt_meta: String,
+    /// Fallback repository for Quilt libraries without a `url`.
+    pub quilt_maven: String,
+    pub forge_maven: String,
+    pub forge_promotions: String,
+    pub neoforge_maven: String,
+    pub neoforge_versions: String,
+    pub ms_device_code: String,
+    pub ms_token: String,
+    pub xbox_user_auth: String,
+    pub xsts_authorize: String,
+    pub minecraft_login: String,
+    pub minecraft_profile: String,
+    pub adoptium_api: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            modrinth_api: "https://api.modrinth.com/v2".to_string(),
+            curseforge_api: "https://api.curseforge.com".to_string(),
+            curseforge_cdn: "https://edge.forgecdn.net/files".to_string(),
+            mojang_version_manifest:
+                "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json".to_string(),
+            mojang_assets: "https://resources.download.minecraft.net".to_string(),
+            fabric_meta: "https://meta.fabricmc.net/v2".to_string(),
+            fabric_maven: "https://maven.fabricmc.net/".to_string(),
+            quilt_meta: "https://meta.quiltmc.org/v3".to_string(),
+            quilt_maven: "https://maven.quiltmc.org/repository/release/".to_string(),
+            forge_maven: "https://maven.minecraftforge.net".to_string(),
+            forge_promotions:
+                "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json"
+                    .to_string(),
+            neoforge_maven: "https://maven.neoforged.net".to_string(),
+            neoforge_versions:
+                "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge"
+                    .to_string(),
+            ms_device_code: "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode"
+                .to_string(),
+            ms_token: "https://login.microsoftonline.com/consumers/oauth2/v2.0/token".to_string(),
+            xbox_user_auth: "https://user.auth.xboxlive.com/user/authenticate".to_string(),
+            xsts_authorize: "https://xsts.auth.xboxlive.com/xsts/authorize".to_string(),
+            minecraft_login: "https://api.minecraftservices.com/authentication/login_with_xbox"
+                .to_string(),
+            minecraft_profile: "https://api.minecraftservices.com/minecraft/profile".to_string(),
+            adoptium_api: "https://api.adoptium.net/v3".to_string(),
+        }
+    }
+}
+
+impl Endpoints {
+    /// Official endpoints with `MINESYNC_*_URL` overrides applied.
+    pub fn from_env() -> Self {
+        Self::default().with_overrides(|key| std::env::var(key).ok())
+    }
+
+    /// Apply overrides looked up by variable name (e.g. `MINESYNC_MODRINTH_API_URL`).
+    /// Trailing slashes are trimmed so URLs can be joined with `/path`.
+    pub fn with_overrides(mut self, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let fields: [(&str, &mut String); 20] = [
+            ("MODRINTH_API", &mut self.modrinth_api),
+            ("CURSEFORGE_API", &mut self.curseforge_api),
+            ("CURSEFORGE_CDN", &mut self.curseforge_cdn),
+            ("MOJANG_VERSION_MANIFEST", &mut self.mojang_version_manifest),
+            ("MOJANG_ASSETS", &mut self.mojang_assets),
+            ("FABRIC_META", &mut self.fabric_meta),
+            ("FABRIC_MAVEN", &mut self.fabric_maven),
+            ("QUILT_META", &mut self.quilt_meta),
+            ("QUILT_MAVEN", &mut self.quilt_maven),
+            ("FORGE_MAVEN", &mut self.forge_maven),
+            ("FORGE_PROMOTIONS", &mut self.forge_promotions),
+            ("NEOFORGE_MAVEN", &mut self.neoforge_maven),
+            ("NEOFORGE_VERSIONS", &mut self.neoforge_versions),
+            ("MS_DEVICE_CODE", &mut self.ms_device_code),
+            ("MS_TOKEN", &mut self.ms_token),
+            ("XBOX_USER_AUTH", &mut self.xbox_user_auth),
+            ("XSTS_AUTHORIZE", &mut self.xsts_authorize),
+            ("MINECRAFT_LOGIN", &mut self.minecraft_login),
+            ("MINECRAFT_PROFILE", &mut self.minecraft_profile),
+            ("ADOPTIUM_API", &mut self.adoptium_api),
+        ];
+
+        for (name, field) in fields {
+            if let Some(url) = lookup(&format!("MINESYNC_{name}_URL")) {
+                let url = url.trim().trim_end_matches('/');
+                if !url.is_empty() {
+                    *field = url.to_string();
+                }
+            }
+        }
+        self
+    }
+
+    /// Point every endpoint at one server, each under its own path prefix
+    /// (`{base}/modrinth`, `{base}/fabric-meta`, ...). Meant for a local stub
+    /// server driving end-to-end tests.
+    #[cfg(test)]
+    pub fn all_at(base: &str) -> Self {
+        let base = base.trim_end_matches('/');
+        Self {
+            modrinth_api: format!("{base}/modrinth"),
+            curseforge_api: format!("{base}/curseforge"),
+            curseforge_cdn: format!("{base}/curseforge-cdn"),
+            mojang_version_manifest: format!("{base}/mojang/version_manifest_v2.json"),
+            mojang_assets: format!("{base}/mojang-assets"),
+            fabric_meta: format!("{base}/fabric-meta"),
+            fabric_maven: format!("{base}/fabric-maven/"),
+            quilt_meta: format!("{base}/quilt-meta"),
+            quilt_maven: format!("{base}/quilt-maven/"),
+            forge_maven: format!("{base}/forge-maven"),
+            forge_promotions: format!("{base}/forge/promotions_slim.json"),
+            neoforge_maven: format!("{base}/neoforge-maven"),
+            neoforge_versions: format!("{base}/neoforge/versions"),
+            ms_device_code: format!("{base}/ms/devicecode"),
+            ms_token: format!("{base}/ms/token"),
+            xbox_user_auth: format!("{base}/xbox/authenticate"),
+            xsts_authorize: format!("{base}/xsts/authorize"),
+            minecraft_login: format!("{base}/minecraft/login_with_xbox"),
+            minecraft_profile: format!("{base}/minecraft/profile"),
+            adoptium_api: format!("{base}/adoptium"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_replace_only_named_endpoints() {
+        let endpoints = Endpoints::default().with_overrides(|key| match key {
+            "MINESYNC_MODRINTH_API_URL" => Some("http://127.0.0.1:8080/v2/".to_string()),
+            "MINESYNC_FORGE_MAVEN_URL" => Some("  ".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(endpoints.modrinth_api, "http://127.0.0.1:8080/v2");
+        assert_eq!(endpoints.forge_maven, Endpoints::default().forge_maven);
+        assert_eq!(
+            endpoints.curseforge_api,
+            Endpoints::default().curseforge_api
+        );
+    }
+}
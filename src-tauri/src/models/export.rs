@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// What goes into an exported `.mrpack`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MrpackExportOptions {
+    /// Where the archive is written.
+    pub output_path: String,
+    /// Pack name; defaults to the instance name.
+    pub name: Option<String>,
+    /// Pack version, e.g. "1.0.0".
+    pub version_id: String,
+    pub summary: Option<String>,
+    /// Instance folders bundled under `overrides/`, e.g. "config", "resourcepacks".
+    #[serde(default)]
+    pub include_folders: Vec<String>,
+}
+
+/// What goes into an exported CurseForge modpack zip.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurseForgeExportOptions {
+    /// Where the archive is written.
+    pub output_path: String,
+    /// Pack name; defaults to the instance name.
+    pub name: Option<String>,
+    /// Pack version, e.g. "1.0.0".
+    pub version: String,
+    pub author: String,
+    /// Instance folders bundled under `overrides/`, e.g. "config", "resourcepacks".
+    #[serde(default)]
+    pub include_folders: Vec<String>,
+}
+
+/// Result of an export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportReport {
+    pub output_path: String,
+    /// Mods referenced by download URL.
+    pub linked_files: usize,
+    /// Mods copied into the archive because no platform hosts them.
+    pub bundled_mods: Vec<String>,
+    /// Problems worth showing the user, e.g. mods the target platform can't
+    /// reference.
+    pub warnings: Vec<String>,
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 // --- Install progress tracking ---
@@ -18,6 +19,7 @@ pub enum InstallStage {
     CopyingOverrides,
     RegisteringMods,
     Completed,
+    Cancelled,
     Failed { message: String },
 }
 
@@ -52,11 +54,92 @@ impl InstallProgress {
     }
 }
 
+// --- Resumable installs ---
+
+/// Last step a modpack install completed, in install order.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointStage {
+    PackReady,
+    MinecraftReady,
+    LoaderReady,
+    ModsReady,
+    OverridesCopied,
+}
+
+impl std::fmt::Display for CheckpointStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PackReady => write!(f, "pack_ready"),
+            Self::MinecraftReady => write!(f, "minecraft_ready"),
+            Self::LoaderReady => write!(f, "loader_ready"),
+            Self::ModsReady => write!(f, "mods_ready"),
+            Self::OverridesCopied => write!(f, "overrides_copied"),
+        }
+    }
+}
+
+impl std::str::FromStr for CheckpointStage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pack_ready" => Ok(Self::PackReady),
+            "minecraft_ready" => Ok(Self::MinecraftReady),
+            "loader_ready" => Ok(Self::LoaderReady),
+            "mods_ready" => Ok(Self::ModsReady),
+            "overrides_copied" => Ok(Self::OverridesCopied),
+            other => Err(format!("Unknown install stage: {other}")),
+        }
+    }
+}
+
+/// A modpack install that stopped before completing. Its files are kept so
+/// it can be resumed from `stage`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallCheckpoint {
+    pub instance_id: String,
+    pub instance_path: String,
+    /// The pack archive; downloaded packs stay in the download cache until
+    /// the install completes.
+    pub archive_path: String,
+    pub modpack_name: Option<String>,
+    pub modpack_icon_url: Option<String>,
+    pub modpack_description: Option<String>,
+    pub stage: CheckpointStage,
+    /// Pack files that could not be downloaded on the last attempt.
+    pub failed_mods: Vec<FailedModDownload>,
+    /// Why the last attempt stopped.
+    pub error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FailedModDownload {
+    pub name: String,
+    /// Destination inside the instance, e.g. "mods/sodium.jar"; unknown when
+    /// the platform could not resolve the file at all.
+    pub path: Option<String>,
+    pub url: Option<String>,
+    /// Optional files may be skipped to finish the install.
+    pub required: bool,
+    pub reason: String,
+}
+
 // --- CurseForge manifest.json (inside modpack ZIP) ---
 
-#[derive(Debug, Clone, Deserialize)]
+/// `manifestType` of a CurseForge modpack manifest.
+pub const CF_MANIFEST_TYPE: &str = "minecraftModpack";
+/// `manifestVersion` written on export.
+pub const CF_MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CfManifest {
     pub minecraft: CfMinecraftInfo,
+    #[serde(rename = "manifestType", default)]
+    pub manifest_type: String,
+    #[serde(rename = "manifestVersion", default)]
+    pub manifest_version: u32,
     pub name: String,
     pub version: String,
     pub author: String,
@@ -64,20 +147,20 @@ pub struct CfManifest {
     pub overrides: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CfMinecraftInfo {
     pub version: String,
     #[serde(rename = "modLoaders")]
     pub mod_loaders: Vec<CfModLoaderInfo>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CfModLoaderInfo {
     pub id: String,
     pub primary: bool,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CfManifestFile {
     pub project_i_d: u32,
@@ -87,26 +170,53 @@ pub struct CfManifestFile {
 
 // --- Modrinth modrinth.index.json (inside .mrpack ZIP) ---
 
-#[derive(Debug, Clone, Deserialize)]
+/// Format version of `modrinth.index.json` written on export.
+pub const MR_INDEX_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MrIndex {
+    #[serde(rename = "formatVersion", default)]
+    pub format_version: u32,
+    #[serde(default)]
+    pub game: String,
     pub name: String,
     #[serde(rename = "versionId")]
     pub version_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
     pub dependencies: HashMap<String, String>,
     pub files: Vec<MrIndexFile>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MrIndexFile {
     pub path: String,
     pub hashes: MrFileHashes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<MrFileEnv>,
     pub downloads: Vec<String>,
     #[serde(rename = "fileSize")]
     pub file_size: u64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MrFileHashes {
     pub sha1: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sha512: Option<String>,
 }
+
+/// Which sides a file is installed on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MrFileEnv {
+    pub client: MrEnvSupport,
+    pub server: MrEnvSupport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MrEnvSupport {
+    Required,
+    Optional,
+    Unsupported,
+}
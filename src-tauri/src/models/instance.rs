@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::mod_platform::ReleaseChannel;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MinecraftInstance {
     pub id: String,
@@ -15,6 +17,10 @@ pub struct MinecraftInstance {
     pub last_played_at: Option<DateTime<Utc>>,
     pub total_play_time: i64,
     pub is_active: bool,
+    /// Least stable channel accepted when picking mod versions for this
+    /// instance (dependencies, updates).
+    #[serde(default)]
+    pub min_release_channel: ReleaseChannel,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use super::instance::ModLoader;
+use super::mod_info::MetadataFormat;
+
+/// Problems found by checking an instance's enabled mods against each other,
+/// the instance's Minecraft version and its loader.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstanceCheckReport {
+    pub instance_id: String,
+    pub missing_dependencies: Vec<MissingDependency>,
+    pub incompatibilities: Vec<ModIncompatibility>,
+    pub duplicates: Vec<DuplicateMod>,
+    pub minecraft_version_violations: Vec<VersionRangeViolation>,
+    pub loader_version_violations: Vec<VersionRangeViolation>,
+    pub loader_mismatches: Vec<LoaderMismatch>,
+}
+
+impl InstanceCheckReport {
+    /// Whether launching would almost certainly fail.
+    ///
+    /// Absent required mods, declared incompatibilities, duplicate mod ids and
+    /// mods for another loader stop the game from loading. Version range
+    /// violations and unverified dependencies often still work, so they only
+    /// warn.
+    pub fn is_blocking(&self) -> bool {
+        self.missing_dependencies
+            .iter()
+            .any(|d| d.found_version.is_none() && !d.unverified)
+            || !self.incompatibilities.is_empty()
+            || !self.duplicates.is_empty()
+            || !self.loader_mismatches.is_empty()
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.missing_dependencies.is_empty()
+            && self.incompatibilities.is_empty()
+            && self.duplicates.is_empty()
+            && self.minecraft_version_violations.is_empty()
+            && self.loader_version_violations.is_empty()
+            && self.loader_mismatches.is_empty()
+    }
+
+    /// One line per problem, for logs and error messages.
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for d in &self.missing_dependencies {
+            let wanted = d.version_range.as_deref().unwrap_or("*");
+            lines.push(match &d.found_version {
+                Some(found) => format!(
+                    "{} needs {} {wanted}, found {found}",
+                    d.mod_name, d.dependency_id
+                ),
+                None if d.unverified => format!(
+                    "{} may need {} {wanted} (could not be verified)",
+                    d.mod_name, d.dependency_id
+                ),
+                None => format!("{} needs {} {wanted}", d.mod_name, d.dependency_id),
+            });
+        }
+        for i in &self.incompatibilities {
+            lines.push(format!(
+                "{} is incompatible with {}",
+                i.mod_name, i.other_mod_name
+            ));
+        }
+        for d in &self.duplicates {
+            lines.push(format!(
+                "{} is installed more than once ({})",
+                d.mod_id,
+                d.file_names.join(", ")
+            ));
+        }
+        for v in &self.minecraft_version_violations {
+            lines.push(format!(
+                "{} requires Minecraft {}, instance has {}",
+                v.mod_name, v.declared_range, v.actual_version
+            ));
+        }
+        for v in &self.loader_version_violations {
+            lines.push(format!(
+                "{} requires loader {}, instance has {}",
+                v.mod_name, v.declared_range, v.actual_version
+            ));
+        }
+        for m in &self.loader_mismatches {
+            lines.push(format!(
+                "{} is a {} mod but the instance uses {}",
+                m.mod_name, m.format, m.instance_loader
+            ));
+        }
+        lines
+    }
+}
+
+/// A required dependency that is absent, or present in a version outside
+/// the declared range (`found_version` set).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MissingDependency {
+    /// `instance_mods` row of the mod declaring the dependency.
+    pub mod_row_id: String,
+    pub mod_name: String,
+    /// Embedded mod id, or platform project id for mods without a descriptor.
+    pub dependency_id: String,
+    pub version_range: Option<String>,
+    pub found_version: Option<String>,
+    /// Absent by project id, but another jar in the instance could not be
+    /// compared and might provide it.
+    #[serde(default)]
+    pub unverified: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModIncompatibility {
+    pub mod_row_id: String,
+    pub mod_name: String,
+    pub other_mod_row_id: String,
+    pub other_mod_name: String,
+}
+
+/// Several enabled files providing the same mod.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DuplicateMod {
+    pub mod_id: String,
+    pub mod_row_ids: Vec<String>,
+    pub file_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionRangeViolation {
+    pub mod_row_id: String,
+    pub mod_name: String,
+    pub declared_range: String,
+    pub actual_version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LoaderMismatch {
+    pub mod_row_id: String,
+    pub mod_name: String,
+    pub format: MetadataFormat,
+    pub instance_loader: ModLoader,
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use super::instance::MinecraftInstance;
+
+/// Launcher an imported instance came from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LauncherKind {
+    /// Prism Launcher and MultiMC, which share the instance format.
+    Prism,
+    AtLauncher,
+    /// The official launcher's `launcher_profiles.json`.
+    Official,
+}
+
+impl std::fmt::Display for LauncherKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Prism => write!(f, "Prism Launcher / MultiMC"),
+            Self::AtLauncher => write!(f, "ATLauncher"),
+            Self::Official => write!(f, "the Minecraft Launcher"),
+        }
+    }
+}
+
+/// Outcome of importing another launcher's instances.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherImportReport {
+    pub imported: Vec<MinecraftInstance>,
+    pub skipped: Vec<SkippedImport>,
+    /// Imported instances whose files were left behind, and why.
+    pub notes: Vec<ImportNote>,
+}
+
+/// An instance that was found but could not be imported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedImport {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Something the user should know about an imported instance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportNote {
+    pub name: String,
+    pub message: String,
+}
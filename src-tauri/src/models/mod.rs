@@ -1,9 +1,14 @@
 pub mod account;
 pub mod auth;
+pub mod dependency_plan;
+pub mod endpoints;
+pub mod export;
 pub mod install;
 pub mod instance;
+pub mod instance_check;
 pub mod java;
 pub mod launch;
+pub mod launcher_import;
 pub mod loader;
 pub mod mod_info;
 pub mod mod_platform;
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::mod_platform::{DependencyType, ModVersionInfo};
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ModInfo {
     pub id: String,
@@ -14,9 +16,34 @@ pub struct ModInfo {
     pub source_project_id: Option<String>,
     pub source_version_id: Option<String>,
     pub is_active: bool,
+    /// `false` when the jar is parked as `<file_name>.disabled` on disk.
+    /// Independent from `is_active`, which marks a removed (soft-deleted) mod.
+    pub enabled: bool,
+    /// Set by a rescan when the row's file is no longer in `mods/`.
+    pub file_missing: bool,
+    /// Installed only because another mod required it.
+    #[serde(default)]
+    pub auto_installed: bool,
+    /// What the jar says about itself, when it ships a loader descriptor.
+    pub metadata: Option<JarMetadata>,
     pub installed_at: DateTime<Utc>,
 }
 
+/// Suffix appended to a mod's file name while it is disabled, following the
+/// convention used by other launchers (Prism, MultiMC, ATLauncher).
+pub const DISABLED_SUFFIX: &str = ".disabled";
+
+impl ModInfo {
+    /// File name of the mod as it currently sits in `mods/`.
+    pub fn disk_file_name(&self) -> String {
+        if self.enabled {
+            self.file_name.clone()
+        } else {
+            format!("{}{DISABLED_SUFFIX}", self.file_name)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ModSource {
@@ -47,3 +74,145 @@ impl std::str::FromStr for ModSource {
         }
     }
 }
+
+// --- Embedded jar metadata ---
+
+/// Metadata a mod declares in its own descriptor (`fabric.mod.json`,
+/// `quilt.mod.json`, `META-INF/mods.toml` or `META-INF/neoforge.mods.toml`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JarMetadata {
+    pub format: MetadataFormat,
+    pub mod_id: String,
+    pub name: String,
+    pub version: String,
+    pub authors: Vec<String>,
+    /// Declared Minecraft version range, verbatim from the descriptor.
+    pub minecraft_range: Option<String>,
+    /// Declared loader version range, verbatim from the descriptor.
+    pub loader_range: Option<String>,
+    pub dependencies: Vec<DeclaredDependency>,
+    /// Other mod ids this jar satisfies: declared aliases and bundled jars.
+    pub provides: Vec<String>,
+    pub environment: ModEnvironment,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeclaredDependency {
+    pub mod_id: String,
+    pub version_range: Option<String>,
+    pub dependency_type: DependencyType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataFormat {
+    Fabric,
+    Quilt,
+    Forge,
+    NeoForge,
+}
+
+impl std::fmt::Display for MetadataFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fabric => write!(f, "fabric"),
+            Self::Quilt => write!(f, "quilt"),
+            Self::Forge => write!(f, "forge"),
+            Self::NeoForge => write!(f, "neoforge"),
+        }
+    }
+}
+
+impl std::str::FromStr for MetadataFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fabric" => Ok(Self::Fabric),
+            "quilt" => Ok(Self::Quilt),
+            "forge" => Ok(Self::Forge),
+            "neoforge" => Ok(Self::NeoForge),
+            other => Err(format!("Unknown metadata format: {other}")),
+        }
+    }
+}
+
+/// Physical side a mod runs on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModEnvironment {
+    Both,
+    Client,
+    Server,
+}
+
+impl std::fmt::Display for ModEnvironment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Both => write!(f, "both"),
+            Self::Client => write!(f, "client"),
+            Self::Server => write!(f, "server"),
+        }
+    }
+}
+
+impl std::str::FromStr for ModEnvironment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "both" => Ok(Self::Both),
+            "client" => Ok(Self::Client),
+            "server" => Ok(Self::Server),
+            other => Err(format!("Unknown mod environment: {other}")),
+        }
+    }
+}
+
+/// Outcome of reconciling an instance's `mods/` folder with `instance_mods`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RescanReport {
+    /// Jars found on disk that had no row yet.
+    pub added: Vec<ModInfo>,
+    /// Existing rows whose platform ids were filled in or corrected.
+    pub identified: Vec<ModInfo>,
+    /// Rows whose file is gone from `mods/`.
+    pub missing: Vec<ModInfo>,
+    /// Jars present both enabled and as `.disabled`; left unreconciled.
+    pub duplicates: Vec<String>,
+    pub scanned_files: usize,
+}
+
+/// A newer platform version available for an installed mod.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModUpdate {
+    /// `instance_mods` row id.
+    pub mod_id: String,
+    pub mod_name: String,
+    pub current_version: String,
+    pub current_version_id: Option<String>,
+    pub latest: ModVersionInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCheckReport {
+    pub updates: Vec<ModUpdate>,
+    /// Platform mods already on their newest acceptable version.
+    pub up_to_date: usize,
+    /// Platform mods whose version lookup failed. Local files are not checked
+    /// and not listed.
+    pub skipped: Vec<ModInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateFailure {
+    pub mod_id: String,
+    pub mod_name: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateResult {
+    pub updated: Vec<ModInfo>,
+    pub failed: Vec<UpdateFailure>,
+}
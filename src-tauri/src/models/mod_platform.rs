@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use super::mod_info::ModSource;
+use super::instance::ModLoader;
+use super::mod_info::{ModEnvironment, ModSource};
 
 // --- Search ---
 
@@ -10,14 +11,29 @@ pub enum ContentType {
     #[default]
     Mod,
     Modpack,
+    ResourcePack,
+    Shader,
+    DataPack,
+    Plugin,
 }
 
+/// Search criteria. Within each list any value matches (OR); different
+/// lists must all match (AND).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchFilters {
     pub query: String,
-    pub game_version: Option<String>,
-    pub loader: Option<String>,
-    pub category: Option<String>,
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    /// Modrinth category slugs. CurseForge has no slug filter, so it is left
+    /// out of searches that set any.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Side the project must support (`Both`: client and server). Modrinth
+    /// only: CurseForge is left out of searches that set it.
+    #[serde(default)]
+    pub environment: Option<ModEnvironment>,
     pub sort: SearchSort,
     pub offset: u32,
     pub limit: u32,
@@ -94,6 +110,76 @@ pub struct ModVersionInfo {
     pub dependencies: Vec<ModDependency>,
     pub date_published: String,
     pub source: ModSource,
+    pub channel: ReleaseChannel,
+}
+
+/// Stability of a published version, as labelled by the platform.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseChannel {
+    Alpha,
+    Beta,
+    #[default]
+    Release,
+}
+
+impl ReleaseChannel {
+    /// Whether a version on this channel is acceptable when `minimum` is the
+    /// least stable channel wanted (e.g. `Beta` accepts betas and releases).
+    pub fn satisfies(self, minimum: ReleaseChannel) -> bool {
+        self >= minimum
+    }
+}
+
+impl std::fmt::Display for ReleaseChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Alpha => write!(f, "alpha"),
+            Self::Beta => write!(f, "beta"),
+            Self::Release => write!(f, "release"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReleaseChannel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "alpha" => Ok(Self::Alpha),
+            "beta" => Ok(Self::Beta),
+            "release" => Ok(Self::Release),
+            other => Err(format!("Unknown release channel: {other}")),
+        }
+    }
+}
+
+/// Why a mod version cannot be installed into an instance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionIncompatibility {
+    pub version_id: String,
+    pub version_name: String,
+    pub minecraft_version: String,
+    pub loader: ModLoader,
+    /// Game versions and loaders the version was published for.
+    pub game_versions: Vec<String>,
+    pub loaders: Vec<String>,
+    pub game_version_supported: bool,
+    pub loader_supported: bool,
+}
+
+impl std::fmt::Display for VersionIncompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} does not support {} {}: it targets {} for Minecraft {}",
+            self.version_name,
+            self.loader,
+            self.minecraft_version,
+            self.loaders.join(", "),
+            self.game_versions.join(", ")
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -109,9 +195,14 @@ pub struct ModVersionFile {
 pub struct ModDependency {
     pub project_id: String,
     pub dependency_type: DependencyType,
+    /// Exact version the dependent was built against, when the platform pins one.
+    #[serde(default)]
+    pub version_id: Option<String>,
+    #[serde(default)]
+    pub file_name: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum DependencyType {
     Required,
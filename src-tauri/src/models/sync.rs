@@ -120,4 +120,12 @@ pub struct SyncModEntry {
     pub source: String,
     pub source_project_id: Option<String>,
     pub source_version_id: Option<String>,
+    /// Disabled entries describe mods the peer keeps on disk but does not load.
+    /// Older peers don't send this field, so it defaults to enabled.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
 }
@@ -5,16 +5,7 @@ use serde::Deserialize;
 
 use crate::errors::{AppError, AppResult};
 use crate::models::auth::DeviceCodeInfo;
-
-// --- Auth endpoint URLs ---
-
-const MS_DEVICE_CODE_URL: &str =
-    "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode";
-const MS_TOKEN_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/token";
-const XBL_AUTH_URL: &str = "https://user.auth.xboxlive.com/user/authenticate";
-const XSTS_AUTH_URL: &str = "https://xsts.auth.xboxlive.com/xsts/authorize";
-const MC_AUTH_URL: &str = "https://api.minecraftservices.com/authentication/login_with_xbox";
-const MC_PROFILE_URL: &str = "https://api.minecraftservices.com/minecraft/profile";
+use crate::models::endpoints::Endpoints;
 
 const XBOX_SCOPE: &str = "XboxLive.signin offline_access";
 
@@ -114,17 +105,19 @@ pub enum PollResult {
 pub struct AuthService {
     client: reqwest::Client,
     client_id: String,
+    endpoints: Endpoints,
     pending_auth: Mutex<Option<PendingAuth>>,
 }
 
 impl AuthService {
-    pub fn new() -> Self {
+    pub fn new(endpoints: &Endpoints) -> Self {
         let client_id =
             std::env::var("AZURE_CLIENT_ID").unwrap_or_else(|_| FALLBACK_CLIENT_ID.to_string());
 
         Self {
             client: reqwest::Client::new(),
             client_id,
+            endpoints: endpoints.clone(),
             pending_auth: Mutex::new(None),
         }
     }
@@ -135,7 +128,7 @@ impl AuthService {
 
         let response = self
             .client
-            .post(MS_DEVICE_CODE_URL)
+            .post(&self.endpoints.ms_device_code)
             .form(&[("client_id", client_id.as_str()), ("scope", XBOX_SCOPE)])
             .send()
             .await?;
@@ -185,7 +178,7 @@ impl AuthService {
 
         let response = self
             .client
-            .post(MS_TOKEN_URL)
+            .post(&self.endpoints.ms_token)
             .form(&[
                 ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
                 ("client_id", &client_id),
@@ -227,7 +220,7 @@ impl AuthService {
 
         let response = self
             .client
-            .post(MS_TOKEN_URL)
+            .post(&self.endpoints.ms_token)
             .form(&[
                 ("grant_type", "refresh_token"),
                 ("client_id", &client_id),
@@ -307,7 +300,12 @@ impl AuthService {
             "TokenType": "JWT"
         });
 
-        let response = self.client.post(XBL_AUTH_URL).json(&body).send().await?;
+        let response = self
+            .client
+            .post(&self.endpoints.xbox_user_auth)
+            .json(&body)
+            .send()
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -331,7 +329,12 @@ impl AuthService {
             "TokenType": "JWT"
         });
 
-        let response = self.client.post(XSTS_AUTH_URL).json(&body).send().await?;
+        let response = self
+            .client
+            .post(&self.endpoints.xsts_authorize)
+            .json(&body)
+            .send()
+            .await?;
 
         if !response.status().is_success() {
             let error_msg = self.parse_xsts_error(&response.text().await.unwrap_or_default());
@@ -356,7 +359,7 @@ impl AuthService {
 
         let response = self
             .client
-            .post(MC_AUTH_URL)
+            .post(&self.endpoints.minecraft_login)
             .json(&body)
             .send()
             .await?;
@@ -376,7 +379,7 @@ impl AuthService {
     async fn get_minecraft_profile(&self, mc_access_token: &str) -> AppResult<McProfileResponse> {
         let response = self
             .client
-            .get(MC_PROFILE_URL)
+            .get(&self.endpoints.minecraft_profile)
             .bearer_auth(mc_access_token)
             .send()
             .await?;
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::{Mutex, MutexGuard};
 
@@ -6,8 +7,10 @@ use rusqlite::{params, Connection};
 
 use crate::errors::{AppError, AppResult};
 use crate::models::account::Account;
+use crate::models::install::{CheckpointStage, InstallCheckpoint};
 use crate::models::instance::{MinecraftInstance, ModLoader};
-use crate::models::mod_info::{ModInfo, ModSource};
+use crate::models::mod_info::{JarMetadata, MetadataFormat, ModEnvironment, ModInfo, ModSource};
+use crate::models::mod_platform::{ModDependency, ReleaseChannel};
 use crate::models::sync::{SyncHistory, SyncSession, SyncStatus};
 
 pub struct DatabaseService {
@@ -61,6 +64,10 @@ fn row_to_instance(row: &rusqlite::Row<'_>) -> rusqlite::Result<MinecraftInstanc
         last_played_at: parse_optional_dt(row.get("last_played_at")?)?,
         total_play_time: row.get("total_play_time")?,
         is_active: row.get::<_, i32>("is_active")? != 0,
+        min_release_channel: row
+            .get::<_, String>("min_release_channel")?
+            .parse::<ReleaseChannel>()
+            .map_err(parse_enum_err)?,
         created_at: parse_dt(&row.get::<_, String>("created_at")?)?,
         updated_at: parse_dt(&row.get::<_, String>("updated_at")?)?,
     })
@@ -82,10 +89,99 @@ fn row_to_mod(row: &rusqlite::Row<'_>) -> rusqlite::Result<ModInfo> {
         source_project_id: row.get("source_project_id")?,
         source_version_id: row.get("source_version_id")?,
         is_active: row.get::<_, i32>("is_active")? != 0,
+        enabled: row.get::<_, i32>("is_enabled")? != 0,
+        file_missing: row.get::<_, i32>("is_missing")? != 0,
+        auto_installed: row.get::<_, i32>("auto_installed")? != 0,
+        metadata: row_to_jar_metadata(row)?,
         installed_at: parse_dt(&row.get::<_, String>("installed_at")?)?,
     })
 }
 
+/// Embedded jar metadata is only present once `embedded_mod_id` was filled.
+fn row_to_jar_metadata(row: &rusqlite::Row<'_>) -> rusqlite::Result<Option<JarMetadata>> {
+    let Some(mod_id) = row.get::<_, Option<String>>("embedded_mod_id")? else {
+        return Ok(None);
+    };
+
+    let format = row
+        .get::<_, String>("metadata_format")?
+        .parse::<MetadataFormat>()
+        .map_err(parse_enum_err)?;
+    let environment = row
+        .get::<_, String>("environment")?
+        .parse::<ModEnvironment>()
+        .map_err(parse_enum_err)?;
+    let authors = serde_json::from_str(&row.get::<_, String>("authors")?)
+        .map_err(|e| parse_enum_err(e.to_string()))?;
+    let dependencies = serde_json::from_str(&row.get::<_, String>("declared_dependencies")?)
+        .map_err(|e| parse_enum_err(e.to_string()))?;
+    let provides = serde_json::from_str(&row.get::<_, String>("provides")?)
+        .map_err(|e| parse_enum_err(e.to_string()))?;
+
+    Ok(Some(JarMetadata {
+        format,
+        mod_id,
+        // Rows written before these columns existed only have the display values
+        name: match row.get("embedded_name")? {
+            Some(name) => name,
+            None => row.get("mod_name")?,
+        },
+        version: match row.get("embedded_version")? {
+            Some(version) => version,
+            None => row.get("mod_version")?,
+        },
+        authors,
+        minecraft_range: row.get("minecraft_range")?,
+        loader_range: row.get("loader_range")?,
+        dependencies,
+        provides,
+        environment,
+    }))
+}
+
+/// Column values for a mod's embedded metadata.
+struct MetadataColumns {
+    format: Option<String>,
+    mod_id: Option<String>,
+    name: Option<String>,
+    version: Option<String>,
+    authors: String,
+    minecraft_range: Option<String>,
+    loader_range: Option<String>,
+    environment: String,
+    dependencies: String,
+    provides: String,
+}
+
+fn jar_metadata_columns(metadata: Option<&JarMetadata>) -> AppResult<MetadataColumns> {
+    let Some(meta) = metadata else {
+        return Ok(MetadataColumns {
+            format: None,
+            mod_id: None,
+            name: None,
+            version: None,
+            authors: "[]".to_string(),
+            minecraft_range: None,
+            loader_range: None,
+            environment: ModEnvironment::Both.to_string(),
+            dependencies: "[]".to_string(),
+            provides: "[]".to_string(),
+        });
+    };
+    Ok(MetadataColumns {
+        format: Some(meta.format.to_string()),
+        mod_id: Some(meta.mod_id.clone()),
+        name: Some(meta.name.clone()),
+        version: Some(meta.version.clone()),
+        authors: serde_json::to_string(&meta.authors)?,
+        minecraft_range: meta.minecraft_range.clone(),
+        loader_range: meta.loader_range.clone(),
+        environment: meta.environment.to_string(),
+        dependencies: serde_json::to_string(&meta.dependencies)?,
+        provides: serde_json::to_string(&meta.provides)?,
+    })
+}
+
 fn row_to_sync_session(row: &rusqlite::Row<'_>) -> rusqlite::Result<SyncSession> {
     let status_str: String = row.get("status")?;
     let status = status_str.parse::<SyncStatus>().map_err(parse_enum_err)?;
@@ -102,6 +198,27 @@ fn row_to_sync_session(row: &rusqlite::Row<'_>) -> rusqlite::Result<SyncSession>
     })
 }
 
+fn row_to_install_checkpoint(row: &rusqlite::Row<'_>) -> rusqlite::Result<InstallCheckpoint> {
+    let failed_mods = serde_json::from_str(&row.get::<_, String>("failed_mods")?)
+        .map_err(|e| parse_enum_err(e.to_string()))?;
+
+    Ok(InstallCheckpoint {
+        instance_id: row.get("instance_id")?,
+        instance_path: row.get("instance_path")?,
+        archive_path: row.get("archive_path")?,
+        modpack_name: row.get("modpack_name")?,
+        modpack_icon_url: row.get("modpack_icon_url")?,
+        modpack_description: row.get("modpack_description")?,
+        stage: row
+            .get::<_, String>("stage")?
+            .parse::<CheckpointStage>()
+            .map_err(parse_enum_err)?,
+        failed_mods,
+        error: row.get("error")?,
+        updated_at: parse_dt(&row.get::<_, String>("updated_at")?)?,
+    })
+}
+
 fn row_to_account(row: &rusqlite::Row<'_>) -> rusqlite::Result<Account> {
     Ok(Account {
         id: row.get("id")?,
@@ -164,6 +281,7 @@ impl DatabaseService {
                 last_played_at TEXT,
                 total_play_time INTEGER NOT NULL DEFAULT 0,
                 is_active INTEGER NOT NULL DEFAULT 1,
+                min_release_channel TEXT NOT NULL DEFAULT 'release',
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
                 updated_at TEXT NOT NULL DEFAULT (datetime('now'))
             );
@@ -180,6 +298,20 @@ impl DatabaseService {
                 source_project_id TEXT,
                 source_version_id TEXT,
                 is_active INTEGER NOT NULL DEFAULT 1,
+                is_enabled INTEGER NOT NULL DEFAULT 1,
+                is_missing INTEGER NOT NULL DEFAULT 0,
+                metadata_format TEXT,
+                embedded_mod_id TEXT,
+                embedded_name TEXT,
+                embedded_version TEXT,
+                authors TEXT NOT NULL DEFAULT '[]',
+                minecraft_range TEXT,
+                loader_range TEXT,
+                environment TEXT NOT NULL DEFAULT 'both',
+                declared_dependencies TEXT NOT NULL DEFAULT '[]',
+                provides TEXT NOT NULL DEFAULT '[]',
+                platform_dependencies TEXT NOT NULL DEFAULT '[]',
+                auto_installed INTEGER NOT NULL DEFAULT 0,
                 installed_at TEXT NOT NULL DEFAULT (datetime('now'))
             );
 
@@ -203,12 +335,58 @@ impl DatabaseService {
                 mods_removed INTEGER NOT NULL DEFAULT 0,
                 mods_updated INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+
+            CREATE TABLE IF NOT EXISTS install_checkpoints (
+                instance_id TEXT PRIMARY KEY,
+                instance_path TEXT NOT NULL,
+                archive_path TEXT NOT NULL,
+                modpack_name TEXT,
+                modpack_icon_url TEXT,
+                modpack_description TEXT,
+                stage TEXT NOT NULL,
+                failed_mods TEXT NOT NULL DEFAULT '[]',
+                error TEXT,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
             );",
         )?;
 
         // Migrations for existing DBs (ignore duplicate column errors)
         let _ = conn.execute_batch("ALTER TABLE instances ADD COLUMN icon_url TEXT;");
         let _ = conn.execute_batch("ALTER TABLE instances ADD COLUMN description TEXT;");
+        let _ = conn.execute_batch(
+            "ALTER TABLE instances ADD COLUMN min_release_channel TEXT NOT NULL DEFAULT 'release';",
+        );
+        let _ = conn.execute_batch(
+            "ALTER TABLE instance_mods ADD COLUMN is_enabled INTEGER NOT NULL DEFAULT 1;",
+        );
+        let _ = conn.execute_batch(
+            "ALTER TABLE instance_mods ADD COLUMN is_missing INTEGER NOT NULL DEFAULT 0;",
+        );
+        let _ = conn.execute_batch("ALTER TABLE instance_mods ADD COLUMN metadata_format TEXT;");
+        let _ = conn.execute_batch("ALTER TABLE instance_mods ADD COLUMN embedded_mod_id TEXT;");
+        let _ = conn.execute_batch(
+            "ALTER TABLE instance_mods ADD COLUMN authors TEXT NOT NULL DEFAULT '[]';",
+        );
+        let _ = conn.execute_batch("ALTER TABLE instance_mods ADD COLUMN minecraft_range TEXT;");
+        let _ = conn.execute_batch("ALTER TABLE instance_mods ADD COLUMN loader_range TEXT;");
+        let _ = conn.execute_batch(
+            "ALTER TABLE instance_mods ADD COLUMN environment TEXT NOT NULL DEFAULT 'both';",
+        );
+        let _ = conn.execute_batch(
+            "ALTER TABLE instance_mods ADD COLUMN declared_dependencies TEXT NOT NULL DEFAULT '[]';",
+        );
+        let _ = conn.execute_batch(
+            "ALTER TABLE instance_mods ADD COLUMN provides TEXT NOT NULL DEFAULT '[]';",
+        );
+        let _ = conn.execute_batch(
+            "ALTER TABLE instance_mods ADD COLUMN platform_dependencies TEXT NOT NULL DEFAULT '[]';",
+        );
+        let _ = conn.execute_batch(
+            "ALTER TABLE instance_mods ADD COLUMN auto_installed INTEGER NOT NULL DEFAULT 0;",
+        );
+        let _ = conn.execute_batch("ALTER TABLE instance_mods ADD COLUMN embedded_name TEXT;");
+        let _ = conn.execute_batch("ALTER TABLE instance_mods ADD COLUMN embedded_version TEXT;");
 
         Ok(())
     }
@@ -224,8 +402,8 @@ impl DatabaseService {
         conn.execute(
             "INSERT INTO instances (id, name, minecraft_version, loader_type, loader_version,
              instance_path, icon_path, icon_url, description, last_played_at,
-             total_play_time, is_active, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+             total_play_time, is_active, min_release_channel, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 instance.id,
                 instance.name,
@@ -239,6 +417,7 @@ impl DatabaseService {
                 instance.last_played_at.map(|dt| format_dt(&dt)),
                 instance.total_play_time,
                 instance.is_active as i32,
+                instance.min_release_channel.to_string(),
                 format_dt(&instance.created_at),
                 format_dt(&instance.updated_at),
             ],
@@ -276,8 +455,8 @@ impl DatabaseService {
             "UPDATE instances SET name = ?1, minecraft_version = ?2, loader_type = ?3,
              loader_version = ?4, instance_path = ?5, icon_path = ?6, icon_url = ?7,
              description = ?8, last_played_at = ?9, total_play_time = ?10,
-             updated_at = datetime('now')
-             WHERE id = ?11",
+             min_release_channel = ?11, updated_at = datetime('now')
+             WHERE id = ?12",
             params![
                 instance.name,
                 instance.minecraft_version,
@@ -289,12 +468,30 @@ impl DatabaseService {
                 instance.description,
                 instance.last_played_at.map(|dt| format_dt(&dt)),
                 instance.total_play_time,
+                instance.min_release_channel.to_string(),
                 instance.id,
             ],
         )?;
         Ok(())
     }
 
+    pub fn set_instance_release_channel(
+        &self,
+        instance_id: &str,
+        channel: ReleaseChannel,
+    ) -> AppResult<()> {
+        let conn = self.conn()?;
+        let updated = conn.execute(
+            "UPDATE instances SET min_release_channel = ?1, updated_at = datetime('now')
+             WHERE id = ?2 AND is_active = 1",
+            params![channel.to_string(), instance_id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::Custom(format!("Instance not found: {instance_id}")));
+        }
+        Ok(())
+    }
+
     pub fn delete_instance(&self, id: &str) -> AppResult<()> {
         let conn = self.conn()?;
         conn.execute(
@@ -321,12 +518,17 @@ impl DatabaseService {
     // --- Mod CRUD ---
 
     pub fn add_mod_to_instance(&self, mod_info: &ModInfo) -> AppResult<()> {
+        let meta = jar_metadata_columns(mod_info.metadata.as_ref())?;
         let conn = self.conn()?;
         conn.execute(
             "INSERT INTO instance_mods (id, instance_id, mod_name, mod_slug, mod_version,
              file_name, file_hash, source, source_project_id, source_version_id,
-             is_active, installed_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+             is_active, is_enabled, is_missing, installed_at, metadata_format,
+             embedded_mod_id, authors, minecraft_range, loader_range, environment,
+             declared_dependencies, provides, auto_installed, embedded_name,
+             embedded_version)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14,
+             ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25)",
             params![
                 mod_info.id,
                 mod_info.instance_id,
@@ -339,7 +541,20 @@ impl DatabaseService {
                 mod_info.source_project_id,
                 mod_info.source_version_id,
                 mod_info.is_active as i32,
+                mod_info.enabled as i32,
+                mod_info.file_missing as i32,
                 format_dt(&mod_info.installed_at),
+                meta.format,
+                meta.mod_id,
+                meta.authors,
+                meta.minecraft_range,
+                meta.loader_range,
+                meta.environment,
+                meta.dependencies,
+                meta.provides,
+                mod_info.auto_installed as i32,
+                meta.name,
+                meta.version,
             ],
         )?;
         Ok(())
@@ -357,6 +572,19 @@ impl DatabaseService {
         Ok(mods)
     }
 
+    /// Lowercased SHA1 of every active mod file, across all instances.
+    pub fn list_mod_file_hashes(&self) -> AppResult<HashSet<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT lower(file_hash) FROM instance_mods
+             WHERE is_active = 1 AND file_hash IS NOT NULL",
+        )?;
+        let hashes = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<HashSet<String>, _>>()?;
+        Ok(hashes)
+    }
+
     pub fn get_mod_by_id(&self, mod_id: &str) -> AppResult<Option<ModInfo>> {
         let conn = self.conn()?;
         let mut stmt = conn.prepare("SELECT * FROM instance_mods WHERE id = ?1 AND is_active = 1")?;
@@ -376,6 +604,154 @@ impl DatabaseService {
         Ok(())
     }
 
+    /// Overwrite the mutable fields of an existing mod row.
+    pub fn update_mod(&self, mod_info: &ModInfo) -> AppResult<()> {
+        let meta = jar_metadata_columns(mod_info.metadata.as_ref())?;
+        let conn = self.conn()?;
+        conn.execute(
+            "UPDATE instance_mods SET mod_name = ?1, mod_slug = ?2, mod_version = ?3,
+             file_name = ?4, file_hash = ?5, source = ?6, source_project_id = ?7,
+             source_version_id = ?8, is_enabled = ?9, is_missing = ?10,
+             metadata_format = ?12, embedded_mod_id = ?13, authors = ?14,
+             minecraft_range = ?15, loader_range = ?16, environment = ?17,
+             declared_dependencies = ?18, provides = ?19, embedded_name = ?20,
+             embedded_version = ?21
+             WHERE id = ?11",
+            params![
+                mod_info.name,
+                mod_info.slug,
+                mod_info.version,
+                mod_info.file_name,
+                mod_info.file_hash,
+                mod_info.source.to_string(),
+                mod_info.source_project_id,
+                mod_info.source_version_id,
+                mod_info.enabled as i32,
+                mod_info.file_missing as i32,
+                mod_info.id,
+                meta.format,
+                meta.mod_id,
+                meta.authors,
+                meta.minecraft_range,
+                meta.loader_range,
+                meta.environment,
+                meta.dependencies,
+                meta.provides,
+                meta.name,
+                meta.version,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Cache the dependencies a platform lists for a mod's installed version,
+    /// so instance checks can run offline.
+    pub fn set_mod_platform_dependencies(
+        &self,
+        mod_id: &str,
+        dependencies: &[ModDependency],
+    ) -> AppResult<()> {
+        let json = serde_json::to_string(dependencies)?;
+        let conn = self.conn()?;
+        conn.execute(
+            "UPDATE instance_mods SET platform_dependencies = ?1 WHERE id = ?2",
+            params![json, mod_id],
+        )?;
+        Ok(())
+    }
+
+    /// Cached platform dependencies of an instance's active mods, keyed by mod row id.
+    pub fn list_platform_dependencies(
+        &self,
+        instance_id: &str,
+    ) -> AppResult<HashMap<String, Vec<ModDependency>>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, platform_dependencies FROM instance_mods
+             WHERE instance_id = ?1 AND is_active = 1",
+        )?;
+        let rows = stmt
+            .query_map(params![instance_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut deps = HashMap::new();
+        for (id, json) in rows {
+            deps.insert(id, serde_json::from_str(&json)?);
+        }
+        Ok(deps)
+    }
+
+    /// Record whether a mod is enabled. Does not touch the soft-delete flag.
+    pub fn set_mod_enabled(&self, mod_id: &str, enabled: bool) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "UPDATE instance_mods SET is_enabled = ?1 WHERE id = ?2 AND is_active = 1",
+            params![enabled as i32, mod_id],
+        )?;
+        Ok(())
+    }
+
+    // --- Install checkpoints ---
+
+    /// Insert or replace the checkpoint of an unfinished modpack install.
+    pub fn save_install_checkpoint(&self, checkpoint: &InstallCheckpoint) -> AppResult<()> {
+        let failed_mods = serde_json::to_string(&checkpoint.failed_mods)?;
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT OR REPLACE INTO install_checkpoints (instance_id, instance_path,
+             archive_path, modpack_name, modpack_icon_url, modpack_description, stage,
+             failed_mods, error, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                checkpoint.instance_id,
+                checkpoint.instance_path,
+                checkpoint.archive_path,
+                checkpoint.modpack_name,
+                checkpoint.modpack_icon_url,
+                checkpoint.modpack_description,
+                checkpoint.stage.to_string(),
+                failed_mods,
+                checkpoint.error,
+                format_dt(&checkpoint.updated_at),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_install_checkpoint(
+        &self,
+        instance_id: &str,
+    ) -> AppResult<Option<InstallCheckpoint>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT * FROM install_checkpoints WHERE instance_id = ?1")?;
+        let mut rows = stmt.query_map(params![instance_id], row_to_install_checkpoint)?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn list_install_checkpoints(&self) -> AppResult<Vec<InstallCheckpoint>> {
+        let conn = self.conn()?;
+        let mut stmt =
+            conn.prepare("SELECT * FROM install_checkpoints ORDER BY updated_at DESC")?;
+        let checkpoints = stmt
+            .query_map([], row_to_install_checkpoint)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(checkpoints)
+    }
+
+    pub fn delete_install_checkpoint(&self, instance_id: &str) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "DELETE FROM install_checkpoints WHERE instance_id = ?1",
+            params![instance_id],
+        )?;
+        Ok(())
+    }
+
     // --- Sync Session CRUD ---
 
     pub fn create_sync_session(&self, session: &SyncSession) -> AppResult<()> {
@@ -521,3 +897,53 @@ impl DatabaseService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{test_instance, test_mod};
+
+    #[test]
+    fn embedded_metadata_keeps_the_jar_name_and_version() -> AppResult<()> {
+        let root = std::env::temp_dir().join(format!("minesync_test_db_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let instance = test_instance(&root.join("instance"));
+        db.create_instance(&instance)?;
+
+        let metadata = JarMetadata {
+            format: MetadataFormat::Fabric,
+            mod_id: "sodium".to_string(),
+            name: "Sodium".to_string(),
+            version: "0.5.8+mc1.20.1".to_string(),
+            authors: vec!["JellySquid".to_string()],
+            minecraft_range: Some(">=1.20".to_string()),
+            loader_range: None,
+            dependencies: Vec::new(),
+            provides: Vec::new(),
+            environment: ModEnvironment::Client,
+        };
+        // The display values come from the platform and differ from the jar's
+        let mut mod_info = ModInfo {
+            name: "Sodium (Modrinth)".to_string(),
+            version: "mc1.20.1-0.5.8".to_string(),
+            metadata: Some(metadata.clone()),
+            ..test_mod(&instance.id, "sodium.jar")
+        };
+        db.add_mod_to_instance(&mod_info)?;
+        let stored = db.get_mod_by_id(&mod_info.id)?.and_then(|m| m.metadata);
+        assert_eq!(stored.as_ref(), mod_info.metadata.as_ref());
+
+        mod_info.metadata = Some(JarMetadata {
+            version: "0.5.11+mc1.20.1".to_string(),
+            ..metadata
+        });
+        db.update_mod(&mod_info)?;
+        let stored = db.get_mod_by_id(&mod_info.id)?.and_then(|m| m.metadata);
+        assert_eq!(stored.as_ref(), mod_info.metadata.as_ref());
+
+        let _ = std::fs::remove_dir_all(&root);
+        Ok(())
+    }
+}
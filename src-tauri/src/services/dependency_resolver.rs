@@ -0,0 +1,566 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::errors::{AppError, AppResult};
+use crate::models::dependency_plan::{
+    DependencyConflict, DependencyPlan, PlannedMod, SkippedDependency, UnresolvedDependency,
+};
+use crate::models::instance::{MinecraftInstance, ModLoader};
+use crate::models::mod_info::{ModInfo, ModSource};
+use crate::models::mod_platform::{DependencyType, ModDependency, ModVersionInfo};
+use crate::services::database::DatabaseService;
+use crate::services::mod_platform::{find_incompatibility, pick_version, UnifiedModClient};
+
+/// Plan installing one version of a project into an instance.
+pub async fn plan_install(
+    db: &DatabaseService,
+    mod_client: &UnifiedModClient,
+    instance_id: &str,
+    source: &ModSource,
+    project_id: &str,
+    version_id: &str,
+) -> AppResult<DependencyPlan> {
+    let instance = db
+        .get_instance(instance_id)?
+        .ok_or_else(|| AppError::Custom(format!("Instance not found: {instance_id}")))?;
+    let version = mod_client
+        .get_version(source, project_id, version_id)
+        .await?;
+    resolve_plan(db, mod_client, &instance, version).await
+}
+
+/// Walk the required dependencies of `version` against what the instance
+/// already has.
+///
+/// Pinned dependency versions are used as is; otherwise the newest version
+/// for the instance's Minecraft version, loader and release channel is
+/// picked. A dependency counts as installed when a mod with the same
+/// project id, or (across platforms) the same slug or embedded mod id, is
+/// in the instance. CurseForge dependencies with nothing compatible there
+/// are looked up on Modrinth by slug.
+pub async fn resolve_plan(
+    db: &DatabaseService,
+    mod_client: &UnifiedModClient,
+    instance: &MinecraftInstance,
+    version: ModVersionInfo,
+) -> AppResult<DependencyPlan> {
+    let installed = db.list_instance_mods(&instance.id)?;
+    let installed_deps = db.list_platform_dependencies(&instance.id)?;
+
+    let mut plan = DependencyPlan {
+        instance_id: instance.id.clone(),
+        ..Default::default()
+    };
+    let mut queue: VecDeque<(ModDependency, ModSource, String)> = VecDeque::new();
+    let mut visited: HashSet<(String, String)> = HashSet::new();
+
+    add_planned(
+        &mut plan,
+        &mut queue,
+        &installed,
+        &installed_deps,
+        PlannedMod {
+            version,
+            slug: None,
+            required_by: None,
+        },
+    );
+
+    while let Some((dep, source, required_by)) = queue.pop_front() {
+        if !visited.insert((source.to_string(), dep.project_id.clone())) {
+            continue;
+        }
+        if is_planned(&plan, &source, &dep.project_id, None) {
+            continue;
+        }
+        if let Some(m) = find_installed(&installed, &source, &dep.project_id, None) {
+            plan.skip.push(skipped(&dep, &source, &required_by, m));
+            continue;
+        }
+
+        let slug = match mod_client.get_mod(&source, &dep.project_id).await {
+            Ok(details) => Some(details.slug),
+            Err(e) => {
+                log::warn!("Could not look up dependency {}: {e}", dep.project_id);
+                None
+            }
+        };
+        if let Some(slug) = slug.as_deref() {
+            if is_planned(&plan, &source, &dep.project_id, Some(slug)) {
+                continue;
+            }
+            if let Some(m) = find_installed(&installed, &source, &dep.project_id, Some(slug)) {
+                plan.skip.push(skipped(&dep, &source, &required_by, m));
+                continue;
+            }
+        }
+
+        match fetch_dependency(mod_client, instance, &dep, &source, slug.as_deref()).await {
+            Ok(version) => add_planned(
+                &mut plan,
+                &mut queue,
+                &installed,
+                &installed_deps,
+                PlannedMod {
+                    version,
+                    slug,
+                    required_by: Some(required_by),
+                },
+            ),
+            Err(reason) => plan.unresolved.push(UnresolvedDependency {
+                project_id: dep.project_id.clone(),
+                source,
+                required_by,
+                reason,
+            }),
+        }
+    }
+
+    Ok(plan)
+}
+
+fn add_planned(
+    plan: &mut DependencyPlan,
+    queue: &mut VecDeque<(ModDependency, ModSource, String)>,
+    installed: &[ModInfo],
+    installed_deps: &HashMap<String, Vec<ModDependency>>,
+    planned: PlannedMod,
+) {
+    for conflict in find_conflicts(&planned.version, installed, installed_deps, &plan.install) {
+        if !plan.conflicts.contains(&conflict) {
+            plan.conflicts.push(conflict);
+        }
+    }
+
+    let version = &planned.version;
+    for dep in &version.dependencies {
+        if dep.dependency_type == DependencyType::Required {
+            queue.push_back((
+                dep.clone(),
+                version.source.clone(),
+                version.project_id.clone(),
+            ));
+        }
+    }
+    plan.install.push(planned);
+}
+
+/// Pinned version if there is one, it can be fetched and it fits the
+/// instance, else the best compatible version, else the same project on
+/// Modrinth.
+async fn fetch_dependency(
+    mod_client: &UnifiedModClient,
+    instance: &MinecraftInstance,
+    dep: &ModDependency,
+    source: &ModSource,
+    slug: Option<&str>,
+) -> Result<ModVersionInfo, String> {
+    if let Some(version_id) = &dep.version_id {
+        match mod_client
+            .get_version(source, &dep.project_id, version_id)
+            .await
+        {
+            Ok(version) => match find_incompatibility(&version, instance) {
+                None => return Ok(version),
+                Some(_) => log::warn!(
+                    "Pinned version {version_id} of {} does not fit Minecraft {} / {}, picking another",
+                    dep.project_id,
+                    instance.minecraft_version,
+                    instance.loader
+                ),
+            },
+            Err(e) => log::warn!(
+                "Pinned version {version_id} of {} unavailable, picking another: {e}",
+                dep.project_id
+            ),
+        }
+    }
+
+    let mut candidates = vec![(source.clone(), dep.project_id.clone())];
+    if *source == ModSource::CurseForge {
+        if let Some(slug) = slug {
+            candidates.push((ModSource::Modrinth, slug.to_string()));
+        }
+    }
+
+    let loader = (instance.loader != ModLoader::Vanilla).then(|| instance.loader.to_string());
+    let mut reason = format!(
+        "No version for Minecraft {} / {}",
+        instance.minecraft_version, instance.loader
+    );
+    for (source, project_id) in candidates {
+        match mod_client
+            .get_versions(
+                &source,
+                &project_id,
+                Some(&instance.minecraft_version),
+                loader.as_deref(),
+            )
+            .await
+        {
+            Ok(versions) => {
+                if let Some(version) = pick_version(versions, instance.min_release_channel) {
+                    return Ok(version);
+                }
+            }
+            Err(e) => reason = e.to_string(),
+        }
+    }
+    Err(reason)
+}
+
+/// Auto-installed mods of an instance that no manually installed mod still
+/// needs, directly or through other dependencies.
+pub fn find_orphaned_dependencies(
+    db: &DatabaseService,
+    instance_id: &str,
+) -> AppResult<Vec<ModInfo>> {
+    let mods = db.list_instance_mods(instance_id)?;
+    let platform_deps = db.list_platform_dependencies(instance_id)?;
+    Ok(orphaned_dependencies(&mods, &platform_deps))
+}
+
+fn orphaned_dependencies(
+    mods: &[ModInfo],
+    platform_deps: &HashMap<String, Vec<ModDependency>>,
+) -> Vec<ModInfo> {
+    let mut needed: HashSet<&str> = HashSet::new();
+    let mut queue: VecDeque<&ModInfo> = VecDeque::new();
+    for m in mods.iter().filter(|m| !m.auto_installed) {
+        needed.insert(&m.id);
+        queue.push_back(m);
+    }
+
+    while let Some(m) = queue.pop_front() {
+        for d in mods {
+            if !needed.contains(d.id.as_str()) && requires(m, d, platform_deps) {
+                needed.insert(&d.id);
+                queue.push_back(d);
+            }
+        }
+    }
+
+    mods.iter()
+        .filter(|m| !needed.contains(m.id.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Whether `m` declares a required dependency satisfied by `d`, either on the
+/// platform or in its jar descriptor.
+fn requires(m: &ModInfo, d: &ModInfo, platform_deps: &HashMap<String, Vec<ModDependency>>) -> bool {
+    let on_platform = m.source == d.source
+        && d.source_project_id.as_deref().is_some_and(|project_id| {
+            platform_deps.get(&m.id).is_some_and(|deps| {
+                deps.iter().any(|dep| {
+                    dep.dependency_type == DependencyType::Required && dep.project_id == project_id
+                })
+            })
+        });
+    if on_platform {
+        return true;
+    }
+
+    let (Some(theirs), Some(ours)) = (&m.metadata, &d.metadata) else {
+        return false;
+    };
+    theirs.dependencies.iter().any(|dep| {
+        dep.dependency_type == DependencyType::Required
+            && (dep.mod_id == ours.mod_id || ours.provides.contains(&dep.mod_id))
+    })
+}
+
+// --- Matching ---
+
+fn is_planned(
+    plan: &DependencyPlan,
+    source: &ModSource,
+    project_id: &str,
+    slug: Option<&str>,
+) -> bool {
+    plan.install.iter().any(|p| {
+        (p.version.source == *source && p.version.project_id == project_id)
+            || (slug.is_some() && p.slug.as_deref() == slug)
+    })
+}
+
+/// Installed mod standing for a project: same platform and project id, or
+/// (when `slug` is known) a mod from any platform with that slug or whose
+/// jar declares or provides that mod id.
+fn find_installed<'a>(
+    installed: &'a [ModInfo],
+    source: &ModSource,
+    project_id: &str,
+    slug: Option<&str>,
+) -> Option<&'a ModInfo> {
+    installed.iter().find(|m| {
+        if m.source == *source && m.source_project_id.as_deref() == Some(project_id) {
+            return true;
+        }
+        let Some(slug) = slug else {
+            return false;
+        };
+        m.slug.as_deref() == Some(slug)
+            || m.metadata
+                .as_ref()
+                .is_some_and(|meta| meta.mod_id == slug || meta.provides.iter().any(|p| p == slug))
+    })
+}
+
+fn skipped(
+    dep: &ModDependency,
+    source: &ModSource,
+    required_by: &str,
+    m: &ModInfo,
+) -> SkippedDependency {
+    SkippedDependency {
+        project_id: dep.project_id.clone(),
+        source: source.clone(),
+        required_by: required_by.to_string(),
+        installed_mod_id: m.id.clone(),
+        installed_name: m.name.clone(),
+        installed_enabled: m.enabled,
+    }
+}
+
+/// `Incompatible` declarations between `version` and the enabled installed
+/// mods or the versions planned so far, whichever side declares them.
+fn find_conflicts(
+    version: &ModVersionInfo,
+    installed: &[ModInfo],
+    installed_deps: &HashMap<String, Vec<ModDependency>>,
+    planned: &[PlannedMod],
+) -> Vec<DependencyConflict> {
+    let declares = |deps: &[ModDependency], project_id: &str| {
+        deps.iter().any(|d| {
+            d.dependency_type == DependencyType::Incompatible && d.project_id == project_id
+        })
+    };
+
+    let mut conflicts = Vec::new();
+    for m in installed
+        .iter()
+        .filter(|m| m.enabled && m.source == version.source)
+    {
+        let Some(other_id) = m.source_project_id.as_deref() else {
+            continue;
+        };
+        let theirs = installed_deps
+            .get(&m.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if declares(&version.dependencies, other_id) || declares(theirs, &version.project_id) {
+            conflicts.push(DependencyConflict {
+                project_id: version.project_id.clone(),
+                mod_name: version.name.clone(),
+                other_project_id: other_id.to_string(),
+                other_name: m.name.clone(),
+                installed_mod_id: Some(m.id.clone()),
+            });
+        }
+    }
+    for p in planned
+        .iter()
+        .filter(|p| p.version.source == version.source)
+    {
+        let other = &p.version;
+        if declares(&version.dependencies, &other.project_id)
+            || declares(&other.dependencies, &version.project_id)
+        {
+            conflicts.push(DependencyConflict {
+                project_id: version.project_id.clone(),
+                mod_name: version.name.clone(),
+                other_project_id: other.project_id.clone(),
+                other_name: other.name.clone(),
+                installed_mod_id: None,
+            });
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::mod_info::{JarMetadata, MetadataFormat, ModEnvironment};
+    use std::path::Path;
+
+    use crate::models::endpoints::Endpoints;
+    use crate::services::http_cache::HttpCache;
+    use crate::test_support::{http_response, stub_server, test_instance, test_mod, test_version};
+
+    fn dep(project_id: &str, dependency_type: DependencyType) -> ModDependency {
+        ModDependency {
+            project_id: project_id.to_string(),
+            dependency_type,
+            version_id: None,
+            file_name: None,
+        }
+    }
+
+    fn version(project_id: &str, dependencies: Vec<ModDependency>) -> ModVersionInfo {
+        ModVersionInfo {
+            name: project_id.to_string(),
+            version_number: "1.0.0".to_string(),
+            game_versions: vec!["1.21.1".to_string()],
+            dependencies,
+            ..test_version(&format!("{project_id}-v1"), project_id)
+        }
+    }
+
+    fn installed(id: &str, source: ModSource, project_id: &str) -> ModInfo {
+        ModInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            source,
+            source_project_id: Some(project_id.to_string()),
+            ..test_mod("inst", &format!("{id}.jar"))
+        }
+    }
+
+    #[test]
+    fn installed_matches_across_platforms_by_slug_or_mod_id() {
+        let mut fabric_api = installed("row-fapi", ModSource::CurseForge, "306612");
+        fabric_api.metadata = Some(JarMetadata {
+            format: MetadataFormat::Fabric,
+            mod_id: "fabric-api".to_string(),
+            name: "Fabric API".to_string(),
+            version: "0.100.0".to_string(),
+            authors: Vec::new(),
+            minecraft_range: None,
+            loader_range: None,
+            dependencies: Vec::new(),
+            provides: vec!["fabric".to_string()],
+            environment: ModEnvironment::Both,
+        });
+        let mods = vec![fabric_api];
+
+        let same = find_installed(&mods, &ModSource::CurseForge, "306612", None);
+        assert_eq!(same.map(|m| m.id.as_str()), Some("row-fapi"));
+        assert!(find_installed(&mods, &ModSource::Modrinth, "P7dR8mSH", None).is_none());
+        let cross = find_installed(&mods, &ModSource::Modrinth, "P7dR8mSH", Some("fabric-api"));
+        assert_eq!(cross.map(|m| m.id.as_str()), Some("row-fapi"));
+        let provided = find_installed(&mods, &ModSource::Modrinth, "xyz", Some("fabric"));
+        assert!(provided.is_some());
+    }
+
+    #[test]
+    fn conflicts_are_found_in_both_directions() {
+        let sodium = installed("row-sodium", ModSource::Modrinth, "AANobbMI");
+        let mut disabled = installed("row-of", ModSource::Modrinth, "optifine");
+        disabled.enabled = false;
+        let installed_mods = vec![sodium, disabled];
+        let installed_deps = HashMap::from([(
+            "row-sodium".to_string(),
+            vec![dep("rubidium", DependencyType::Incompatible)],
+        )]);
+
+        // The new mod declares the clash with an installed mod
+        let v = version("iris", vec![dep("AANobbMI", DependencyType::Incompatible)]);
+        let found = find_conflicts(&v, &installed_mods, &installed_deps, &[]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].installed_mod_id.as_deref(), Some("row-sodium"));
+
+        // The installed mod declares it
+        let v = version("rubidium", Vec::new());
+        assert_eq!(
+            find_conflicts(&v, &installed_mods, &installed_deps, &[]).len(),
+            1
+        );
+
+        // Disabled mods do not load, so they do not conflict
+        let v = version("x", vec![dep("optifine", DependencyType::Incompatible)]);
+        assert!(find_conflicts(&v, &installed_mods, &installed_deps, &[]).is_empty());
+
+        // Between two planned versions
+        let planned = vec![PlannedMod {
+            version: version("a", vec![dep("b", DependencyType::Incompatible)]),
+            slug: None,
+            required_by: None,
+        }];
+        let found = find_conflicts(&version("b", Vec::new()), &[], &HashMap::new(), &planned);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].installed_mod_id.is_none());
+    }
+
+    #[test]
+    fn orphans_are_auto_installed_mods_nothing_requires() {
+        let mut sodium_extra = installed("row-extra", ModSource::Modrinth, "sodium-extra");
+        sodium_extra.auto_installed = false;
+        let mut sodium = installed("row-sodium", ModSource::Modrinth, "AANobbMI");
+        sodium.auto_installed = true;
+        let mut indium = installed("row-indium", ModSource::Modrinth, "Orvt0mRa");
+        indium.auto_installed = true;
+        let mut lib = installed("row-lib", ModSource::Modrinth, "lib");
+        lib.auto_installed = true;
+        let deps = HashMap::from([
+            (
+                "row-extra".to_string(),
+                vec![dep("AANobbMI", DependencyType::Required)],
+            ),
+            // Transitively needed through sodium
+            (
+                "row-sodium".to_string(),
+                vec![dep("lib", DependencyType::Required)],
+            ),
+            // Only an optional dependency of a needed mod: still an orphan
+            (
+                "row-lib".to_string(),
+                vec![dep("Orvt0mRa", DependencyType::Optional)],
+            ),
+        ]);
+        let mods = vec![sodium_extra, sodium, indium, lib];
+
+        let orphans = orphaned_dependencies(&mods, &deps);
+        let ids: Vec<&str> = orphans.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["row-indium"]);
+
+        // Once the manually installed mod is gone, its dependencies are orphans
+        let orphans = orphaned_dependencies(&mods[1..], &deps);
+        assert_eq!(orphans.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn incompatible_pinned_version_falls_back_to_a_compatible_one() {
+        let version_json = |id: &str, loader: &str| {
+            format!(
+                r#"{{"id":"{id}","project_id":"fapi","name":"{id}","version_number":"{id}",
+                    "game_versions":["1.20.1"],"loaders":["{loader}"],
+                    "date_published":"2024-08-01","dependencies":[],"files":[]}}"#
+            )
+        };
+        let pinned = version_json("forge-build", "forge");
+        let listed = format!("[{}]", version_json("fabric-build", "fabric"));
+        let server = stub_server(2, move |head| {
+            let body = if head.starts_with("GET /modrinth/version/") {
+                &pinned
+            } else {
+                &listed
+            };
+            Some(http_response("200 OK", body.as_bytes()))
+        });
+        let cache = HttpCache::new(
+            std::env::temp_dir().join(format!("minesync_http_{}", uuid::Uuid::new_v4())),
+        );
+        let client = UnifiedModClient::new(None, &Endpoints::all_at(&server.base), cache);
+        let dep = ModDependency {
+            project_id: "fapi".to_string(),
+            dependency_type: DependencyType::Required,
+            version_id: Some("forge-build".to_string()),
+            file_name: None,
+        };
+
+        let version = fetch_dependency(
+            &client,
+            &test_instance(Path::new("")),
+            &dep,
+            &ModSource::Modrinth,
+            None,
+        )
+        .await
+        .expect("a compatible version");
+
+        assert_eq!(version.id, "fabric-build");
+        assert_eq!(server.requests().len(), 2);
+    }
+}
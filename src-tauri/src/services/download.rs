@@ -1,22 +1,87 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, MutexGuard};
 
+use md5::Md5;
 use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
+use sha2::{Sha256, Sha512};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::errors::{AppError, AppResult};
+use crate::services::jobs::CancelToken;
+use crate::services::store::{self, FileStore};
 
 const MAX_RETRIES: usize = 3;
-const DEFAULT_CONCURRENT: usize = 4;
+/// Suffix of the file a download streams into before it is verified.
+const PART_SUFFIX: &str = ".part";
+/// Files downloading at once, shared by every job.
+const DEFAULT_CONCURRENT: usize = 8;
 
 // --- Public types ---
 
 #[derive(Debug, Clone)]
 pub struct DownloadTask {
-    pub url: String,
+    /// Mirrors of the file, tried in order.
+    pub urls: Vec<String>,
     pub dest: PathBuf,
-    pub sha1: Option<String>,
+    pub hashes: FileHashes,
     pub size: u64,
+    /// Kept in the shared store by SHA1, so other instances link the file
+    /// instead of downloading it again.
+    pub shared: bool,
+}
+
+impl DownloadTask {
+    fn primary_url(&self) -> &str {
+        self.urls.first().map_or("", String::as_str)
+    }
+}
+
+/// Expected digests of a file, as hex. The strongest one present is checked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileHashes {
+    pub sha1: Option<String>,
+    pub sha256: Option<String>,
+    pub sha512: Option<String>,
+    pub md5: Option<String>,
+}
+
+impl FileHashes {
+    pub fn sha1(sha1: impl Into<String>) -> Self {
+        Self {
+            sha1: Some(sha1.into()),
+            ..Self::default()
+        }
+    }
+
+    /// From a platform hash map keyed by algorithm ("sha1", "sha512", "md5"...).
+    pub fn from_map(hashes: &HashMap<String, String>) -> Self {
+        Self {
+            sha1: hashes.get("sha1").cloned(),
+            sha256: hashes.get("sha256").cloned(),
+            sha512: hashes.get("sha512").cloned(),
+            md5: hashes.get("md5").cloned(),
+        }
+    }
+
+    fn strongest(&self) -> Option<(HashAlgorithm, &str)> {
+        [
+            (HashAlgorithm::Sha512, &self.sha512),
+            (HashAlgorithm::Sha256, &self.sha256),
+            (HashAlgorithm::Sha1, &self.sha1),
+            (HashAlgorithm::Md5, &self.md5),
+        ]
+        .into_iter()
+        .find_map(|(algorithm, hash)| hash.as_deref().map(|h| (algorithm, h)))
+    }
+}
+
+/// A task that still failed after all retries.
+#[derive(Debug, Clone)]
+pub struct FailedDownload {
+    pub dest: PathBuf,
+    pub error: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,44 +100,79 @@ pub enum DownloadState {
     Idle,
     Downloading,
     Completed,
+    Cancelled,
     Failed { message: String },
 }
 
+impl DownloadProgress {
+    pub fn idle() -> Self {
+        Self {
+            total_files: 0,
+            completed_files: 0,
+            total_bytes: 0,
+            downloaded_bytes: 0,
+            failed_files: Vec::new(),
+            state: DownloadState::Idle,
+        }
+    }
+}
+
 // --- DownloadService ---
 
 #[derive(Clone)]
 pub struct DownloadService {
     client: reqwest::Client,
     progress: Arc<Mutex<DownloadProgress>>,
-    max_concurrent: usize,
+    slots: Arc<tokio::sync::Semaphore>,
+    cancel: CancelToken,
+    store: Option<FileStore>,
 }
 
 impl DownloadService {
     pub fn new() -> Self {
         Self {
             client: reqwest::Client::new(),
-            progress: Arc::new(Mutex::new(DownloadProgress {
-                total_files: 0,
-                completed_files: 0,
-                total_bytes: 0,
-                downloaded_bytes: 0,
-                failed_files: Vec::new(),
-                state: DownloadState::Idle,
-            })),
-            max_concurrent: DEFAULT_CONCURRENT,
+            progress: Arc::new(Mutex::new(DownloadProgress::idle())),
+            slots: Arc::new(tokio::sync::Semaphore::new(DEFAULT_CONCURRENT)),
+            cancel: CancelToken::new(),
+            store: None,
         }
     }
 
-    pub fn get_progress(&self) -> AppResult<DownloadProgress> {
-        Ok(self.lock_progress()?.clone())
+    /// Serve and keep shared tasks' files through `store`.
+    pub fn with_store(mut self, store: FileStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    pub fn store(&self) -> Option<&FileStore> {
+        self.store.as_ref()
     }
 
-    pub fn is_downloading(&self) -> AppResult<bool> {
-        Ok(self.lock_progress()?.state == DownloadState::Downloading)
+    /// Same client and concurrency budget, reporting into `progress` and
+    /// stopping when `cancel` fires.
+    pub fn for_job(&self, progress: Arc<Mutex<DownloadProgress>>, cancel: CancelToken) -> Self {
+        Self {
+            client: self.client.clone(),
+            progress,
+            slots: Arc::clone(&self.slots),
+            cancel,
+            store: self.store.clone(),
+        }
     }
 
     /// Download all tasks with parallel execution and progress tracking
     pub async fn download_all(&self, tasks: Vec<DownloadTask>) -> AppResult<()> {
+        self.download_all_with_failures(tasks).await?;
+        Ok(())
+    }
+
+    /// Like [`download_all`](Self::download_all), returning the tasks that
+    /// failed so callers can report or retry them.
+    pub async fn download_all_with_failures(
+        &self,
+        tasks: Vec<DownloadTask>,
+    ) -> AppResult<Vec<FailedDownload>> {
         let total_bytes: u64 = tasks.iter().map(|t| t.size).sum();
         let total_files = tasks.len();
 
@@ -97,36 +197,57 @@ impl DownloadService {
             progress.completed_files = skipped;
         }
 
-        // Download with concurrency limit
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(self.max_concurrent));
+        // Download within the budget shared by all jobs
         let mut handles = Vec::with_capacity(pending.len());
 
         for task in pending {
-            let sem = Arc::clone(&semaphore);
+            let sem = Arc::clone(&self.slots);
             let service = self.clone();
-
-            handles.push(tokio::spawn(async move {
-                let _permit = sem
-                    .acquire_owned()
-                    .await
-                    .map_err(|e| AppError::Custom(format!("Semaphore error: {e}")))?;
-                service.download_file(&task).await
-            }));
+            let dest = task.dest.clone();
+
+            let handle = tokio::spawn(async move {
+                let download = async {
+                    let _permit = sem
+                        .acquire_owned()
+                        .await
+                        .map_err(|e| AppError::Custom(format!("Semaphore error: {e}")))?;
+                    service.download_file(&task).await
+                };
+                tokio::select! {
+                    result = download => result,
+                    () = service.cancel.cancelled() => {
+                        // A cancelled job leaves no partial download behind
+                        let _ = tokio::fs::remove_file(part_path(&task.dest)).await;
+                        Err(AppError::Cancelled)
+                    }
+                }
+            });
+            handles.push((dest, handle));
         }
 
         // Await all tasks
-        for handle in handles {
-            match handle.await {
-                Ok(Ok(())) => {}
-                Ok(Err(e)) => log::error!("Download error: {e}"),
-                Err(e) => log::error!("Download task panicked: {e}"),
-            }
+        let mut failures = Vec::new();
+        for (dest, handle) in handles {
+            let error = match handle.await {
+                Ok(Ok(()) | Err(AppError::Cancelled)) => continue,
+                Ok(Err(e)) => {
+                    log::error!("Download error: {e}");
+                    e.to_string()
+                }
+                Err(e) => {
+                    log::error!("Download task panicked: {e}");
+                    e.to_string()
+                }
+            };
+            failures.push(FailedDownload { dest, error });
         }
 
         // Finalize state
         {
             let mut progress = self.lock_progress()?;
-            if progress.failed_files.is_empty() {
+            if self.cancel.is_cancelled() {
+                progress.state = DownloadState::Cancelled;
+            } else if progress.failed_files.is_empty() {
                 progress.state = DownloadState::Completed;
             } else {
                 progress.state = DownloadState::Failed {
@@ -134,79 +255,222 @@ impl DownloadService {
                 };
             }
         }
+        if self.cancel.is_cancelled() {
+            return Err(AppError::Cancelled);
+        }
 
-        Ok(())
+        Ok(failures)
     }
 
     // --- Private methods ---
 
-    /// Download a single file with retry logic
+    /// Download a single file, trying each mirror in turn and retrying the
+    /// whole list with backoff. Retries continue from whatever an earlier
+    /// attempt already wrote.
     async fn download_file(&self, task: &DownloadTask) -> AppResult<()> {
+        if task.urls.is_empty() {
+            return Err(AppError::Custom(format!(
+                "No download URL for {}",
+                task.dest.display()
+            )));
+        }
         if let Some(parent) = task.dest.parent() {
             tokio::fs::create_dir_all(parent).await?;
         }
 
+        // Bytes of this file included in `downloaded_bytes`
+        let mut counted = 0;
+        if self.link_from_store(task, &mut counted).await? {
+            let mut progress = self.lock_progress()?;
+            progress.completed_files += 1;
+            return Ok(());
+        }
+
+        let mut last_error = None;
         for attempt in 1..=MAX_RETRIES {
-            match self.try_download(task).await {
-                Ok(()) => {
-                    let mut progress = self.lock_progress()?;
-                    progress.completed_files += 1;
-                    return Ok(());
-                }
-                Err(e) if attempt < MAX_RETRIES => {
-                    log::warn!(
-                        "Download attempt {attempt}/{MAX_RETRIES} failed for {}: {e}",
-                        task.url
-                    );
-                    let backoff = std::time::Duration::from_secs(attempt as u64);
-                    tokio::time::sleep(backoff).await;
-                }
-                Err(e) => {
-                    let mut progress = self.lock_progress()?;
-                    progress.failed_files.push(task.url.clone());
-                    return Err(e);
+            if attempt > 1 {
+                let backoff = std::time::Duration::from_secs(attempt as u64 - 1);
+                tokio::time::sleep(backoff).await;
+            }
+            for url in &task.urls {
+                match self.try_download(task, url, &mut counted).await {
+                    Ok(()) => {
+                        self.add_to_store(task).await;
+                        let mut progress = self.lock_progress()?;
+                        progress.completed_files += 1;
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Download attempt {attempt}/{MAX_RETRIES} failed for {url}: {e}"
+                        );
+                        last_error = Some(e);
+                    }
                 }
             }
         }
 
-        Err(AppError::Custom(format!(
-            "Download failed after {MAX_RETRIES} attempts: {}",
-            task.url
-        )))
+        let mut progress = self.lock_progress()?;
+        progress.failed_files.push(task.primary_url().to_string());
+        Err(last_error.unwrap_or_else(|| {
+            AppError::Custom(format!(
+                "Download failed after {MAX_RETRIES} attempts: {}",
+                task.primary_url()
+            ))
+        }))
     }
 
-    /// Attempt a single download + SHA1 verification
-    async fn try_download(&self, task: &DownloadTask) -> AppResult<()> {
-        let response = self.client.get(&task.url).send().await?;
+    /// Attempt a single download: stream into `<dest>.part` while hashing,
+    /// resuming a partial file with a Range request, then verify and rename
+    /// into place so `dest` never holds a partial file.
+    async fn try_download(
+        &self,
+        task: &DownloadTask,
+        url: &str,
+        counted: &mut u64,
+    ) -> AppResult<()> {
+        let part = part_path(&task.dest);
+        let expected = task.hashes.strongest();
+        let new_hasher = || expected.map(|(algorithm, _)| FileHasher::new(algorithm));
+        let mut hasher = new_hasher();
+        let mut written = match tokio::fs::metadata(&part).await {
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        };
+        if written > 0 {
+            if let Some(ref mut hasher) = hasher {
+                hash_into(&part, hasher).await?;
+            }
+        }
+        self.count_bytes(counted, written)?;
 
-        if !response.status().is_success() {
-            return Err(AppError::Custom(format!(
-                "HTTP {} for {}",
-                response.status(),
-                task.url
-            )));
+        let mut request = self.client.get(url);
+        if written > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={written}-"));
+        }
+        let mut response = request.send().await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            // The partial file does not match the remote one; start over next attempt
+            let _ = tokio::fs::remove_file(&part).await;
+            self.count_bytes(counted, 0)?;
+            return Err(AppError::Custom(format!("HTTP {status} resuming {url}")));
+        }
+        if !status.is_success() {
+            return Err(AppError::Custom(format!("HTTP {status} for {url}")));
         }
 
-        let bytes = response.bytes().await?;
+        // A full response to a Range request replaces the partial file
+        let resumed = written > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+        let mut file = if resumed {
+            tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(&part)
+                .await?
+        } else {
+            hasher = new_hasher();
+            written = 0;
+            self.count_bytes(counted, 0)?;
+            tokio::fs::File::create(&part).await?
+        };
 
-        // SHA1 verification
-        if let Some(ref expected) = task.sha1 {
-            let actual = compute_sha1(&bytes);
-            if actual != *expected {
+        loop {
+            let chunk = match response.chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => {
+                    // Keep what arrived for the next attempt to resume from
+                    file.flush().await?;
+                    return Err(e.into());
+                }
+            };
+            if let Some(ref mut hasher) = hasher {
+                hasher.update(&chunk);
+            }
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+            self.count_bytes(counted, written)?;
+        }
+        file.sync_all().await?;
+        drop(file);
+
+        if let (Some((algorithm, expected)), Some(hasher)) = (expected, hasher) {
+            let actual = hasher.finalize_hex();
+            if !actual.eq_ignore_ascii_case(expected) {
+                // A corrupt mirror must not leave bytes for the next one to resume
+                let _ = tokio::fs::remove_file(&part).await;
+                self.count_bytes(counted, 0)?;
                 return Err(AppError::Custom(format!(
-                    "SHA1 mismatch for {}: expected {expected}, got {actual}",
+                    "{} mismatch for {} from {url}: expected {expected}, got {actual}",
+                    algorithm.label(),
                     task.dest.display()
                 )));
             }
         }
 
-        // Update progress
-        {
-            let mut progress = self.lock_progress()?;
-            progress.downloaded_bytes += bytes.len() as u64;
+        tokio::fs::rename(&part, &task.dest).await?;
+        Ok(())
+    }
+
+    /// Place `task.dest` from a verified store blob instead of downloading it.
+    async fn link_from_store(&self, task: &DownloadTask, counted: &mut u64) -> AppResult<bool> {
+        let Some((store, sha1)) = self.store_key(task) else {
+            return Ok(false);
+        };
+        let Some(blob) = store.blob_path(sha1) else {
+            return Ok(false);
+        };
+        let mut hasher = FileHasher::new(HashAlgorithm::Sha1);
+        if hash_into(&blob, &mut hasher).await.is_err() {
+            return Ok(false);
+        }
+        if !hasher.finalize_hex().eq_ignore_ascii_case(sha1) {
+            // Edited in place through an instance's hardlink, or stored under a wrong key
+            log::warn!("Store blob {sha1} does not match its hash, removing it");
+            let _ = tokio::fs::remove_file(&blob).await;
+            return Ok(false);
+        }
+
+        let part = part_path(&task.dest);
+        let _ = tokio::fs::remove_file(&part).await;
+        let linked = async {
+            store::link_or_copy(&blob, &part).await?;
+            tokio::fs::rename(&part, &task.dest).await?;
+            AppResult::Ok(())
+        }
+        .await;
+        if let Err(e) = linked {
+            log::warn!("Failed to link {} from the store: {e}", task.dest.display());
+            let _ = tokio::fs::remove_file(&part).await;
+            return Ok(false);
         }
+        self.count_bytes(counted, task.size)?;
+        Ok(true)
+    }
 
-        tokio::fs::write(&task.dest, &bytes).await?;
+    /// Keep a verified shared file in the store for other instances.
+    async fn add_to_store(&self, task: &DownloadTask) {
+        if let Some((store, sha1)) = self.store_key(task) {
+            if let Err(e) = store.insert(&task.dest, sha1).await {
+                log::warn!("Failed to add {} to the store: {e}", task.dest.display());
+            }
+        }
+    }
+
+    fn store_key<'a>(&'a self, task: &'a DownloadTask) -> Option<(&'a FileStore, &'a str)> {
+        if !task.shared {
+            return None;
+        }
+        Some((self.store.as_ref()?, task.hashes.sha1.as_deref()?))
+    }
+
+    /// Record that `bytes` of a file are now on disk, replacing the
+    /// `counted` bytes reported for it so far.
+    fn count_bytes(&self, counted: &mut u64, bytes: u64) -> AppResult<()> {
+        let mut progress = self.lock_progress()?;
+        progress.downloaded_bytes = progress.downloaded_bytes.saturating_sub(*counted) + bytes;
+        *counted = bytes;
         Ok(())
     }
 
@@ -216,6 +480,8 @@ impl DownloadService {
 
         for task in tasks {
             if is_file_cached(&task).await {
+                // Files from before the store existed get shared too
+                self.add_to_store(&task).await;
                 continue;
             }
             pending.push(task);
@@ -243,16 +509,16 @@ async fn is_file_cached(task: &DownloadTask) -> bool {
         return false;
     }
 
-    // Verify SHA1 when available to detect corrupted/tampered files
-    if let Some(ref expected_sha1) = task.sha1 {
-        let bytes = match tokio::fs::read(&task.dest).await {
-            Ok(b) => b,
-            Err(_) => return false,
-        };
-        let actual = compute_sha1(&bytes);
-        if actual != *expected_sha1 {
+    // Verify the strongest hash available to detect corrupted/tampered files
+    if let Some((algorithm, expected)) = task.hashes.strongest() {
+        let mut hasher = FileHasher::new(algorithm);
+        if hash_into(&task.dest, &mut hasher).await.is_err() {
+            return false;
+        }
+        if !hasher.finalize_hex().eq_ignore_ascii_case(expected) {
             log::warn!(
-                "Cache SHA1 mismatch for {}, re-downloading",
+                "Cache {} mismatch for {}, re-downloading",
+                algorithm.label(),
                 task.dest.display()
             );
             return false;
@@ -262,7 +528,263 @@ async fn is_file_cached(task: &DownloadTask) -> bool {
     true
 }
 
-fn compute_sha1(data: &[u8]) -> String {
-    let hash = Sha1::digest(data);
-    format!("{hash:x}")
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(PART_SUFFIX);
+    dest.with_file_name(name)
+}
+
+/// Feed a file to `hasher` without loading it whole.
+async fn hash_into(path: &Path, hasher: &mut FileHasher) -> std::io::Result<()> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        hasher.update(&buf[..n]);
+    }
+}
+
+// --- Hashing ---
+
+#[derive(Debug, Clone, Copy)]
+enum HashAlgorithm {
+    Sha512,
+    Sha256,
+    Sha1,
+    Md5,
+}
+
+impl HashAlgorithm {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Sha512 => "SHA512",
+            Self::Sha256 => "SHA256",
+            Self::Sha1 => "SHA1",
+            Self::Md5 => "MD5",
+        }
+    }
+}
+
+/// Incremental digest in one of the supported algorithms.
+enum FileHasher {
+    Sha512(Sha512),
+    Sha256(Sha256),
+    Sha1(Sha1),
+    Md5(Md5),
+}
+
+impl FileHasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha512 => Self::Sha512(Sha512::new()),
+            HashAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            HashAlgorithm::Sha1 => Self::Sha1(Sha1::new()),
+            HashAlgorithm::Md5 => Self::Md5(Md5::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha512(h) => h.update(data),
+            Self::Sha256(h) => h.update(data),
+            Self::Sha1(h) => h.update(data),
+            Self::Md5(h) => h.update(data),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Self::Sha512(h) => format!("{:x}", h.finalize()),
+            Self::Sha256(h) => format!("{:x}", h.finalize()),
+            Self::Sha1(h) => format!("{:x}", h.finalize()),
+            Self::Md5(h) => format!("{:x}", h.finalize()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{http_response, stub_server};
+
+    /// A mirror answering every request with `status` and `body`.
+    fn mirror(status: &str, body: &[u8]) -> String {
+        let response = http_response(status, body);
+        let server = stub_server(usize::MAX, move |_| Some(response.clone()));
+        format!("{}/mod.jar", server.base)
+    }
+
+    #[tokio::test]
+    async fn cancelled_download_stops_and_removes_partial_file() -> AppResult<()> {
+        // A server that accepts connections but never answers
+        let server = stub_server(usize::MAX, |_| None);
+
+        let dest = std::env::temp_dir()
+            .join(format!("minesync_download_{}", uuid::Uuid::new_v4()))
+            .join("file.jar");
+        std::fs::create_dir_all(dest.parent().expect("parent"))?;
+        let part = part_path(&dest);
+        std::fs::write(&part, b"partial")?;
+
+        let cancel = CancelToken::new();
+        let progress = Arc::new(Mutex::new(DownloadProgress::idle()));
+        let service = DownloadService::new().for_job(Arc::clone(&progress), cancel.clone());
+        let task = DownloadTask {
+            urls: vec![format!("{}/file.jar", server.base)],
+            dest: dest.clone(),
+            hashes: FileHashes::default(),
+            size: 1024,
+            shared: false,
+        };
+
+        let download = tokio::spawn(async move { service.download_all(vec![task]).await });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        cancel.cancel();
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), download)
+            .await
+            .expect("download must stop once cancelled")
+            .expect("download task");
+
+        assert!(matches!(result, Err(AppError::Cancelled)));
+        assert!(!part.exists(), "Partial file must be removed");
+        assert!(!dest.exists());
+        assert_eq!(
+            progress.lock().expect("progress").state,
+            DownloadState::Cancelled
+        );
+
+        let _ = std::fs::remove_dir_all(dest.parent().expect("parent"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn interrupted_download_resumes_with_range_request() -> AppResult<()> {
+        let body: Vec<u8> = (0..192 * 1024).map(|i| (i % 251) as u8).collect();
+        let served = body.clone();
+        // Drops the connection halfway unless the request asks for a range
+        let server = stub_server(usize::MAX, move |head| {
+            let response = match range_start(head) {
+                Some(start) => http_response(
+                    &format!(
+                        "206 Partial Content\r\nContent-Range: bytes {start}-{}/{}",
+                        served.len() - 1,
+                        served.len()
+                    ),
+                    &served[start..],
+                ),
+                None => {
+                    let mut response = http_response("200 OK", &served);
+                    response.truncate(response.len() - served.len() / 2);
+                    response
+                }
+            };
+            Some(response)
+        });
+
+        let dest = std::env::temp_dir()
+            .join(format!("minesync_download_{}", uuid::Uuid::new_v4()))
+            .join("pack.zip");
+        let progress = Arc::new(Mutex::new(DownloadProgress::idle()));
+        let service = DownloadService::new().for_job(Arc::clone(&progress), CancelToken::new());
+        let task = DownloadTask {
+            urls: vec![format!("{}/pack.zip", server.base)],
+            dest: dest.clone(),
+            hashes: FileHashes::sha1(format!("{:x}", Sha1::digest(&body))),
+            size: body.len() as u64,
+            shared: false,
+        };
+
+        let failures = service.download_all_with_failures(vec![task]).await?;
+
+        assert!(failures.is_empty(), "{failures:?}");
+        assert_eq!(std::fs::read(&dest)?, body);
+        assert!(!part_path(&dest).exists());
+        let ranges: Vec<_> = server.requests().iter().map(|h| range_start(h)).collect();
+        assert_eq!(ranges, vec![None, Some(body.len() / 2)]);
+        let progress = progress.lock().expect("progress");
+        assert_eq!(progress.downloaded_bytes, body.len() as u64);
+        assert_eq!(progress.state, DownloadState::Completed);
+
+        let _ = std::fs::remove_dir_all(dest.parent().expect("parent"));
+        Ok(())
+    }
+
+    fn range_start(head: &str) -> Option<usize> {
+        head.to_ascii_lowercase()
+            .lines()
+            .find_map(|line| line.strip_prefix("range: bytes="))
+            .and_then(|r| r.trim_end_matches('-').parse().ok())
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_next_mirror_and_checks_strongest_hash() -> AppResult<()> {
+        let body = b"the real mod jar".to_vec();
+        let urls = vec![
+            mirror("404 Not Found", b"missing"),
+            mirror("200 OK", b"a tampered jar"),
+            mirror("200 OK", &body),
+        ];
+        let dest = std::env::temp_dir()
+            .join(format!("minesync_download_{}", uuid::Uuid::new_v4()))
+            .join("mod.jar");
+        let task = DownloadTask {
+            urls,
+            dest: dest.clone(),
+            hashes: FileHashes {
+                // A wrong weaker hash is ignored when sha512 is known
+                sha1: Some("0000".to_string()),
+                sha512: Some(format!("{:x}", Sha512::digest(&body))),
+                md5: Some(format!("{:x}", Md5::digest(&body))),
+                ..FileHashes::default()
+            },
+            size: body.len() as u64,
+            shared: false,
+        };
+
+        let failures = DownloadService::new()
+            .download_all_with_failures(vec![task])
+            .await?;
+
+        assert!(failures.is_empty(), "{failures:?}");
+        assert_eq!(std::fs::read(&dest)?, body);
+
+        let _ = std::fs::remove_dir_all(dest.parent().expect("parent"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn shared_files_are_linked_from_store_instead_of_downloaded() -> AppResult<()> {
+        let body = b"sodium".to_vec();
+        let root = std::env::temp_dir().join(format!("minesync_store_{}", uuid::Uuid::new_v4()));
+        let service = DownloadService::new().with_store(FileStore::new(root.join("store")));
+        let task = |url: String, instance: &str| DownloadTask {
+            urls: vec![url],
+            dest: root.join(instance).join("mods").join("sodium.jar"),
+            hashes: FileHashes::sha1(format!("{:x}", Sha1::digest(&body))),
+            size: body.len() as u64,
+            shared: true,
+        };
+
+        let first = task(mirror("200 OK", &body), "a");
+        service.download_all(vec![first]).await?;
+        // The second instance never reaches the network
+        let second = task(mirror("404 Not Found", b"missing"), "b");
+        let failures = service
+            .download_all_with_failures(vec![second.clone()])
+            .await?;
+
+        assert!(failures.is_empty(), "{failures:?}");
+        assert_eq!(std::fs::read(&second.dest)?, body);
+        let blob = root
+            .join("store")
+            .join(format!("{:x}", Sha1::digest(&body)));
+        assert_eq!(std::fs::read(blob)?, body);
+
+        let _ = std::fs::remove_dir_all(&root);
+        Ok(())
+    }
 }
@@ -0,0 +1,603 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+use sha1::Sha1;
+use sha2::{Digest, Sha512};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::errors::{AppError, AppResult};
+use crate::models::export::{CurseForgeExportOptions, ExportReport, MrpackExportOptions};
+use crate::models::install::{
+    CfManifest, CfManifestFile, CfMinecraftInfo, CfModLoaderInfo, MrEnvSupport, MrFileEnv,
+    MrFileHashes, MrIndex, MrIndexFile, CF_MANIFEST_TYPE, CF_MANIFEST_VERSION,
+    MR_INDEX_FORMAT_VERSION,
+};
+use crate::models::instance::{MinecraftInstance, ModLoader};
+use crate::models::mod_info::{ModEnvironment, ModInfo, ModSource};
+use crate::models::mod_platform::ModVersionInfo;
+use crate::services::database::DatabaseService;
+use crate::services::install::safe_relative_path;
+use crate::services::mod_platform::UnifiedModClient;
+
+/// Export an instance as a Modrinth `.mrpack`.
+///
+/// Mods whose exact file is published on Modrinth are referenced by URL;
+/// everything else (CurseForge and local jars) is bundled under
+/// `overrides/mods/`. Disabled and missing mods are left out.
+pub async fn export_instance_mrpack(
+    db: &DatabaseService,
+    mod_client: &UnifiedModClient,
+    instance_id: &str,
+    options: &MrpackExportOptions,
+) -> AppResult<ExportReport> {
+    let instance = db
+        .get_instance(instance_id)?
+        .ok_or_else(|| AppError::Custom(format!("Instance not found: {instance_id}")))?;
+    let mods = exported_mods(db, instance_id)?;
+
+    let version_ids: Vec<String> = mods
+        .iter()
+        .filter(|m| m.source == ModSource::Modrinth)
+        .filter_map(|m| m.source_version_id.clone())
+        .collect();
+    let versions = if version_ids.is_empty() {
+        HashMap::new()
+    } else {
+        mod_client
+            .get_modrinth_versions(&version_ids)
+            .await?
+            .into_iter()
+            .map(|v| (v.id.clone(), v))
+            .collect()
+    };
+
+    let options = options.clone();
+    write_archive_blocking(options.output_path.clone(), move |zip| {
+        write_mrpack(zip, &instance, &mods, &versions, &options)
+    })
+    .await
+}
+
+/// Mods that are installed and switched on.
+fn exported_mods(db: &DatabaseService, instance_id: &str) -> AppResult<Vec<ModInfo>> {
+    Ok(db
+        .list_instance_mods(instance_id)?
+        .into_iter()
+        .filter(|m| m.enabled && !m.file_missing)
+        .collect())
+}
+
+fn write_mrpack(
+    zip: &mut ZipWriter<File>,
+    instance: &MinecraftInstance,
+    mods: &[ModInfo],
+    versions: &HashMap<String, ModVersionInfo>,
+    options: &MrpackExportOptions,
+) -> AppResult<ExportReport> {
+    let dependencies = mr_dependencies(instance)?;
+    let instance_path = Path::new(&instance.instance_path);
+
+    let mut files = Vec::new();
+    let mut bundled_mods = Vec::new();
+    for m in mods {
+        let data = read_mod_file(instance_path, m)?;
+        let sha1 = format!("{:x}", Sha1::digest(&data));
+        match modrinth_url(m, versions, &sha1) {
+            Some(url) => files.push(MrIndexFile {
+                path: format!("mods/{}", m.file_name),
+                hashes: MrFileHashes {
+                    sha1,
+                    sha512: Some(format!("{:x}", Sha512::digest(&data))),
+                },
+                env: Some(file_env(m)),
+                downloads: vec![url],
+                file_size: data.len() as u64,
+            }),
+            None => {
+                add_file(zip, &format!("overrides/mods/{}", m.file_name), &data)?;
+                bundled_mods.push(m.file_name.clone());
+            }
+        }
+    }
+    let warnings = bundled_warnings(&bundled_mods, "Modrinth");
+
+    for folder in &options.include_folders {
+        add_override_folder(zip, instance_path, folder, "overrides")?;
+    }
+
+    let index = MrIndex {
+        format_version: MR_INDEX_FORMAT_VERSION,
+        game: "minecraft".to_string(),
+        name: options
+            .name
+            .clone()
+            .unwrap_or_else(|| instance.name.clone()),
+        version_id: Some(options.version_id.clone()),
+        summary: options.summary.clone(),
+        dependencies,
+        files,
+    };
+    add_file(
+        zip,
+        "modrinth.index.json",
+        &serde_json::to_vec_pretty(&index)?,
+    )?;
+
+    Ok(ExportReport {
+        output_path: options.output_path.clone(),
+        linked_files: index.files.len(),
+        bundled_mods,
+        warnings,
+    })
+}
+
+/// `dependencies` block: the Minecraft version plus the loader, keyed the
+/// way Modrinth names them.
+fn mr_dependencies(instance: &MinecraftInstance) -> AppResult<HashMap<String, String>> {
+    let mut deps = HashMap::from([("minecraft".to_string(), instance.minecraft_version.clone())]);
+    let key = match instance.loader {
+        ModLoader::Vanilla => return Ok(deps),
+        ModLoader::Fabric => "fabric-loader",
+        ModLoader::Forge => "forge",
+        ModLoader::NeoForge => "neoforge",
+        ModLoader::Quilt => "quilt-loader",
+    };
+    let version = instance.loader_version.clone().ok_or_else(|| {
+        AppError::Custom(format!(
+            "Instance has no {} version to export",
+            instance.loader
+        ))
+    })?;
+    deps.insert(key.to_string(), version);
+    Ok(deps)
+}
+
+/// Download URL of the mod's file on Modrinth, if the installed jar is the
+/// exact file published there.
+fn modrinth_url(
+    m: &ModInfo,
+    versions: &HashMap<String, ModVersionInfo>,
+    sha1: &str,
+) -> Option<String> {
+    if m.source != ModSource::Modrinth {
+        return None;
+    }
+    let version = versions.get(m.source_version_id.as_ref()?)?;
+    version
+        .files
+        .iter()
+        .find(|f| f.hashes.get("sha1").map(String::as_str) == Some(sha1))
+        .map(|f| f.url.clone())
+}
+
+fn file_env(m: &ModInfo) -> MrFileEnv {
+    let (client, server) = match m.metadata.as_ref().map(|meta| &meta.environment) {
+        Some(ModEnvironment::Client) => (MrEnvSupport::Required, MrEnvSupport::Unsupported),
+        Some(ModEnvironment::Server) => (MrEnvSupport::Unsupported, MrEnvSupport::Required),
+        _ => (MrEnvSupport::Required, MrEnvSupport::Required),
+    };
+    MrFileEnv { client, server }
+}
+
+/// Export an instance as a CurseForge modpack zip (`manifest.json`,
+/// `modlist.html` and `overrides/`).
+///
+/// CurseForge mods are referenced by project and file id; mods without a
+/// CurseForge mapping are bundled under `overrides/mods/` and reported as
+/// warnings.
+pub async fn export_instance_curseforge(
+    db: &DatabaseService,
+    instance_id: &str,
+    options: &CurseForgeExportOptions,
+) -> AppResult<ExportReport> {
+    let instance = db
+        .get_instance(instance_id)?
+        .ok_or_else(|| AppError::Custom(format!("Instance not found: {instance_id}")))?;
+    let mods = exported_mods(db, instance_id)?;
+
+    let options = options.clone();
+    write_archive_blocking(options.output_path.clone(), move |zip| {
+        write_curseforge_pack(zip, &instance, &mods, &options)
+    })
+    .await
+}
+
+fn write_curseforge_pack(
+    zip: &mut ZipWriter<File>,
+    instance: &MinecraftInstance,
+    mods: &[ModInfo],
+    options: &CurseForgeExportOptions,
+) -> AppResult<ExportReport> {
+    let instance_path = Path::new(&instance.instance_path);
+
+    let mut files = Vec::new();
+    let mut listed = Vec::new();
+    let mut bundled_mods = Vec::new();
+    for m in mods {
+        match curseforge_ids(m) {
+            Some((project_id, file_id)) => {
+                files.push(CfManifestFile {
+                    project_i_d: project_id,
+                    file_i_d: file_id,
+                    required: true,
+                });
+                listed.push(m);
+            }
+            None => {
+                let data = read_mod_file(instance_path, m)?;
+                add_file(zip, &format!("overrides/mods/{}", m.file_name), &data)?;
+                bundled_mods.push(m.file_name.clone());
+            }
+        }
+    }
+    let warnings = bundled_warnings(&bundled_mods, "CurseForge");
+
+    for folder in &options.include_folders {
+        add_override_folder(zip, instance_path, folder, "overrides")?;
+    }
+
+    let mod_loaders = cf_loader_id(instance)?
+        .map(|id| CfModLoaderInfo { id, primary: true })
+        .into_iter()
+        .collect();
+    let manifest = CfManifest {
+        minecraft: CfMinecraftInfo {
+            version: instance.minecraft_version.clone(),
+            mod_loaders,
+        },
+        manifest_type: CF_MANIFEST_TYPE.to_string(),
+        manifest_version: CF_MANIFEST_VERSION,
+        name: options
+            .name
+            .clone()
+            .unwrap_or_else(|| instance.name.clone()),
+        version: options.version.clone(),
+        author: options.author.clone(),
+        files,
+        overrides: "overrides".to_string(),
+    };
+    add_file(zip, "manifest.json", &serde_json::to_vec_pretty(&manifest)?)?;
+    add_file(zip, "modlist.html", modlist_html(&listed).as_bytes())?;
+
+    Ok(ExportReport {
+        output_path: options.output_path.clone(),
+        linked_files: manifest.files.len(),
+        bundled_mods,
+        warnings,
+    })
+}
+
+/// CurseForge project and file id of a mod installed from CurseForge.
+fn curseforge_ids(m: &ModInfo) -> Option<(u32, u32)> {
+    if m.source != ModSource::CurseForge {
+        return None;
+    }
+    let project_id = m.source_project_id.as_deref()?.parse().ok()?;
+    let file_id = m.source_version_id.as_deref()?.parse().ok()?;
+    Some((project_id, file_id))
+}
+
+/// Loader id as CurseForge writes it, e.g. "fabric-0.15.0".
+fn cf_loader_id(instance: &MinecraftInstance) -> AppResult<Option<String>> {
+    if instance.loader == ModLoader::Vanilla {
+        return Ok(None);
+    }
+    let version = instance.loader_version.as_deref().ok_or_else(|| {
+        AppError::Custom(format!(
+            "Instance has no {} version to export",
+            instance.loader
+        ))
+    })?;
+    Ok(Some(format!("{}-{version}", instance.loader)))
+}
+
+/// The `modlist.html` CurseForge ships next to the manifest.
+fn modlist_html(mods: &[&ModInfo]) -> String {
+    let mut html = String::from("<ul>\n");
+    for m in mods {
+        let url = match (&m.slug, &m.source_project_id) {
+            (Some(slug), _) => format!("https://www.curseforge.com/minecraft/mc-mods/{slug}"),
+            (None, Some(id)) => format!("https://www.curseforge.com/projects/{id}"),
+            (None, None) => continue,
+        };
+        html.push_str(&format!(
+            "<li><a href=\"{}\">{}</a></li>\n",
+            escape_html(&url),
+            escape_html(&m.name)
+        ));
+    }
+    html.push_str("</ul>\n");
+    html
+}
+
+fn escape_html(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn bundled_warnings(bundled_mods: &[String], platform: &str) -> Vec<String> {
+    bundled_mods
+        .iter()
+        .map(|file_name| {
+            let warning =
+                format!("{file_name} has no {platform} mapping and was bundled in overrides/mods");
+            log::warn!("[EXPORT] {warning}");
+            warning
+        })
+        .collect()
+}
+
+fn read_mod_file(instance_path: &Path, m: &ModInfo) -> AppResult<Vec<u8>> {
+    let path = instance_path.join("mods").join(&m.file_name);
+    std::fs::read(&path)
+        .map_err(|e| AppError::Custom(format!("Failed to read mod file {}: {e}", path.display())))
+}
+
+// --- Archive writing ---
+
+/// [`write_archive`] on a blocking thread, since reading every mod and
+/// compressing it would otherwise stall the async runtime.
+async fn write_archive_blocking<T: Send + 'static>(
+    output_path: String,
+    build: impl FnOnce(&mut ZipWriter<File>) -> AppResult<T> + Send + 'static,
+) -> AppResult<T> {
+    tokio::task::spawn_blocking(move || write_archive(&output_path, build))
+        .await
+        .map_err(|e| AppError::Custom(format!("Export task panicked: {e}")))?
+}
+
+/// Build an archive in a temporary file next to `output_path`, moving it into
+/// place only once it is complete.
+fn write_archive<T>(
+    output_path: &str,
+    build: impl FnOnce(&mut ZipWriter<File>) -> AppResult<T>,
+) -> AppResult<T> {
+    let output = PathBuf::from(output_path);
+    let tmp = output.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+
+    let result = (|| {
+        let mut zip = ZipWriter::new(File::create(&tmp)?);
+        let value = build(&mut zip)?;
+        zip.finish()
+            .map_err(|e| AppError::Custom(format!("Failed to write ZIP: {e}")))?;
+        std::fs::rename(&tmp, &output)?;
+        Ok(value)
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+fn add_file(zip: &mut ZipWriter<File>, name: &str, data: &[u8]) -> AppResult<()> {
+    zip.start_file(name, SimpleFileOptions::default())
+        .map_err(|e| AppError::Custom(format!("Failed to add {name} to ZIP: {e}")))?;
+    zip.write_all(data)?;
+    Ok(())
+}
+
+/// Copy an instance folder (e.g. `config`) into the archive under `prefix`.
+/// Mods are exported separately, so `mods/` itself is skipped.
+fn add_override_folder(
+    zip: &mut ZipWriter<File>,
+    instance_path: &Path,
+    folder: &str,
+    prefix: &str,
+) -> AppResult<()> {
+    let relative = safe_relative_path(folder)
+        .ok_or_else(|| AppError::Custom(format!("Invalid folder to export: {folder}")))?;
+    if relative.components().next() == Some(Component::Normal("mods".as_ref())) {
+        log::warn!("[EXPORT] Skipping {folder}: mods are exported individually");
+        return Ok(());
+    }
+
+    let dir = instance_path.join(&relative);
+    if !dir.is_dir() {
+        log::warn!("[EXPORT] Skipping {folder}: not a folder in the instance");
+        return Ok(());
+    }
+    let archive_dir = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .fold(prefix.to_string(), |acc, part| format!("{acc}/{part}"));
+    add_dir(zip, &dir, &archive_dir)
+}
+
+fn add_dir(zip: &mut ZipWriter<File>, dir: &Path, archive_dir: &str) -> AppResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = format!("{archive_dir}/{}", entry.file_name().to_string_lossy());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            add_dir(zip, &entry.path(), &name)?;
+        } else if file_type.is_file() {
+            add_file(zip, &name, &std::fs::read(entry.path())?)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    use crate::models::mod_platform::ModVersionFile;
+    use crate::test_support::{test_instance, test_mod, test_version};
+
+    fn mod_info(file_name: &str, source: ModSource, version_id: Option<&str>) -> ModInfo {
+        ModInfo {
+            name: file_name.to_string(),
+            version: "1.0".to_string(),
+            source,
+            source_version_id: version_id.map(str::to_string),
+            ..test_mod("instance", file_name)
+        }
+    }
+
+    /// An instance folder with two jars and a config file.
+    fn pack_instance(root: &Path) -> AppResult<MinecraftInstance> {
+        let instance_path = root.join("instance");
+        std::fs::create_dir_all(instance_path.join("mods"))?;
+        std::fs::create_dir_all(instance_path.join("config").join("sodium"))?;
+        std::fs::write(instance_path.join("mods").join("sodium.jar"), b"sodium")?;
+        std::fs::write(instance_path.join("mods").join("homemade.jar"), b"homemade")?;
+        std::fs::write(
+            instance_path
+                .join("config")
+                .join("sodium")
+                .join("options.json"),
+            b"{}",
+        )?;
+
+        Ok(MinecraftInstance {
+            id: "instance".to_string(),
+            name: "Pack".to_string(),
+            ..test_instance(&instance_path)
+        })
+    }
+
+    fn read_entry(archive: &mut zip::ZipArchive<File>, name: &str) -> AppResult<String> {
+        let mut raw = String::new();
+        archive
+            .by_name(name)
+            .map_err(|e| AppError::Custom(format!("Missing {name}: {e}")))?
+            .read_to_string(&mut raw)?;
+        Ok(raw)
+    }
+
+    fn open_archive(path: &Path) -> AppResult<zip::ZipArchive<File>> {
+        zip::ZipArchive::new(File::open(path)?)
+            .map_err(|e| AppError::Custom(format!("Failed to open ZIP: {e}")))
+    }
+
+    #[test]
+    fn mrpack_round_trips_through_the_index_parser() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_mrpack_{}", uuid::Uuid::new_v4()));
+        let instance = pack_instance(&root)?;
+        let mods = vec![
+            mod_info("sodium.jar", ModSource::Modrinth, Some("v1")),
+            mod_info("homemade.jar", ModSource::Local, None),
+        ];
+        let sodium_url = "https://cdn.modrinth.com/data/AANobbMI/versions/v1/sodium.jar";
+        let versions = HashMap::from([(
+            "v1".to_string(),
+            ModVersionInfo {
+                name: "Sodium".to_string(),
+                version_number: "0.5.3".to_string(),
+                files: vec![ModVersionFile {
+                    url: sodium_url.to_string(),
+                    filename: "sodium.jar".to_string(),
+                    size: 6,
+                    hashes: HashMap::from([(
+                        "sha1".to_string(),
+                        format!("{:x}", Sha1::digest(b"sodium")),
+                    )]),
+                    primary: true,
+                }],
+                ..test_version("v1", "AANobbMI")
+            },
+        )]);
+        let output = root.join("pack.mrpack");
+        let options = MrpackExportOptions {
+            output_path: output.to_string_lossy().to_string(),
+            name: None,
+            version_id: "1.0.0".to_string(),
+            summary: None,
+            include_folders: vec!["config".to_string(), "mods".to_string()],
+        };
+
+        let report = write_archive(&options.output_path, |zip| {
+            write_mrpack(zip, &instance, &mods, &versions, &options)
+        })?;
+        assert_eq!(report.linked_files, 1);
+        assert_eq!(report.bundled_mods, vec!["homemade.jar".to_string()]);
+        assert_eq!(report.warnings.len(), 1);
+
+        let mut archive = open_archive(&output)?;
+        let index: MrIndex =
+            serde_json::from_str(&read_entry(&mut archive, "modrinth.index.json")?)?;
+
+        assert_eq!(index.format_version, MR_INDEX_FORMAT_VERSION);
+        assert_eq!(index.name, "Pack");
+        assert_eq!(index.dependencies["minecraft"], "1.20.1");
+        assert_eq!(index.dependencies["fabric-loader"], "0.15.0");
+        assert_eq!(index.files.len(), 1);
+        assert_eq!(index.files[0].path, "mods/sodium.jar");
+        assert_eq!(index.files[0].downloads, vec![sodium_url.to_string()]);
+        assert_eq!(index.files[0].file_size, 6);
+        assert!(index.files[0].hashes.sha512.is_some());
+        assert_eq!(
+            index.files[0].env,
+            Some(MrFileEnv {
+                client: MrEnvSupport::Required,
+                server: MrEnvSupport::Required,
+            })
+        );
+
+        let names: Vec<&str> = archive.file_names().collect();
+        assert!(names.contains(&"overrides/mods/homemade.jar"));
+        assert!(names.contains(&"overrides/config/sodium/options.json"));
+        assert!(!names.contains(&"overrides/mods/sodium.jar"));
+
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+
+    #[test]
+    fn curseforge_pack_round_trips_through_the_manifest_parser() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_cfpack_{}", uuid::Uuid::new_v4()));
+        let instance = pack_instance(&root)?;
+        let mut sodium = mod_info("sodium.jar", ModSource::CurseForge, Some("4712866"));
+        sodium.name = "Sodium <Fabric>".to_string();
+        sodium.source_project_id = Some("394468".to_string());
+        let mods = vec![sodium, mod_info("homemade.jar", ModSource::Local, None)];
+        let output = root.join("pack.zip");
+        let options = CurseForgeExportOptions {
+            output_path: output.to_string_lossy().to_string(),
+            name: Some("Exported".to_string()),
+            version: "2.0.0".to_string(),
+            author: "MineSync".to_string(),
+            include_folders: vec!["config".to_string()],
+        };
+
+        let report = write_archive(&options.output_path, |zip| {
+            write_curseforge_pack(zip, &instance, &mods, &options)
+        })?;
+        assert_eq!(report.linked_files, 1);
+        assert_eq!(report.bundled_mods, vec!["homemade.jar".to_string()]);
+        assert!(report.warnings[0].contains("homemade.jar"));
+
+        let mut archive = open_archive(&output)?;
+        let manifest: CfManifest =
+            serde_json::from_str(&read_entry(&mut archive, "manifest.json")?)?;
+        assert_eq!(manifest.manifest_type, CF_MANIFEST_TYPE);
+        assert_eq!(manifest.name, "Exported");
+        assert_eq!(manifest.minecraft.version, "1.20.1");
+        assert_eq!(manifest.minecraft.mod_loaders[0].id, "fabric-0.15.0");
+        assert!(manifest.minecraft.mod_loaders[0].primary);
+        assert_eq!(manifest.files.len(), 1);
+        assert_eq!(manifest.files[0].project_i_d, 394468);
+        assert_eq!(manifest.files[0].file_i_d, 4712866);
+
+        let modlist = read_entry(&mut archive, "modlist.html")?;
+        assert!(modlist.contains("https://www.curseforge.com/projects/394468"));
+        assert!(modlist.contains("Sodium &lt;Fabric&gt;"));
+
+        let names: Vec<&str> = archive.file_names().collect();
+        assert!(names.contains(&"overrides/mods/homemade.jar"));
+        assert!(names.contains(&"overrides/config/sodium/options.json"));
+        assert!(!names.contains(&"overrides/mods/sodium.jar"));
+
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+}
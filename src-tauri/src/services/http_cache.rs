@@ -0,0 +1,354 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::{AppError, AppResult};
+use crate::services::rate_limit::RateLimiter;
+
+// How long responses are served without revalidating them
+pub const SEARCH_TTL: Duration = Duration::from_secs(5 * 60);
+pub const VERSIONS_TTL: Duration = Duration::from_secs(10 * 60);
+pub const PROJECT_TTL: Duration = Duration::from_secs(60 * 60);
+pub const MANIFEST_TTL: Duration = Duration::from_secs(60 * 60);
+/// For documents that never change once published (version JSON, asset indexes).
+pub const IMMUTABLE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Entries not downloaded or revalidated for this long are pruned.
+pub const MAX_ENTRY_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// On-disk cache for metadata GET requests, shared by the platform clients.
+///
+/// Responses are stored by URL. Within their TTL they are served without a
+/// request; after it they are revalidated with `If-None-Match` /
+/// `If-Modified-Since`. When the server cannot be reached, or fails with a
+/// 5xx, a stale copy is served rather than an error.
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    dir: PathBuf,
+}
+
+/// A response body, fresh from the network or from the cache.
+#[derive(Debug)]
+pub struct CachedResponse {
+    status: StatusCode,
+    body: Vec<u8>,
+}
+
+impl CachedResponse {
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> AppResult<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    fn cached(body: Vec<u8>) -> Self {
+        Self {
+            status: StatusCode::OK,
+            body,
+        }
+    }
+}
+
+/// Validators and age of a stored response; the body sits next to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Unix timestamp of the last download or revalidation.
+    stored_at: i64,
+}
+
+impl HttpCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Send a GET request through the cache. Responses are cached only when
+    /// successful; other statuses are passed through for the caller to report.
+    pub async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        ttl: Duration,
+    ) -> AppResult<CachedResponse> {
+        self.fetch(request, ttl, None).await
+    }
+
+    /// Like [`send`](Self::send), paced by a platform's rate limiter. A rate
+    /// limit that outlasts the retries is answered from the cache if possible.
+    pub async fn send_limited(
+        &self,
+        request: reqwest::RequestBuilder,
+        ttl: Duration,
+        limiter: &RateLimiter,
+    ) -> AppResult<CachedResponse> {
+        self.fetch(request, ttl, Some(limiter)).await
+    }
+
+    async fn fetch(
+        &self,
+        request: reqwest::RequestBuilder,
+        ttl: Duration,
+        limiter: Option<&RateLimiter>,
+    ) -> AppResult<CachedResponse> {
+        let (client, request) = request.build_split();
+        let mut request = request?;
+        let url = request.url().to_string();
+        let key = cache_key(&url);
+
+        let cached = self.load(&key).await;
+        if let Some((entry, body)) = &cached {
+            if is_fresh(entry, ttl, now()) {
+                return Ok(CachedResponse::cached(body.clone()));
+            }
+            let headers = request.headers_mut();
+            if let Some(etag) = entry.etag.as_deref().and_then(|v| v.parse().ok()) {
+                headers.insert(IF_NONE_MATCH, etag);
+            }
+            if let Some(date) = entry.last_modified.as_deref().and_then(|v| v.parse().ok()) {
+                headers.insert(IF_MODIFIED_SINCE, date);
+            }
+        }
+
+        let result = match limiter {
+            Some(limiter) => limiter.execute(&client, request).await,
+            None => client.execute(request).await.map_err(AppError::from),
+        };
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                return match cached {
+                    Some((_, body)) => {
+                        log::warn!("[CACHE] {url} failed, using cached copy: {e}");
+                        Ok(CachedResponse::cached(body))
+                    }
+                    None => Err(e),
+                };
+            }
+        };
+
+        let status = response.status();
+        match cached {
+            Some((mut entry, body)) if status == StatusCode::NOT_MODIFIED => {
+                entry.stored_at = now();
+                self.store(&key, &entry, None).await;
+                Ok(CachedResponse::cached(body))
+            }
+            Some((_, body)) if status.is_server_error() => {
+                log::warn!("[CACHE] {url} failed with HTTP {status}, using cached copy");
+                Ok(CachedResponse::cached(body))
+            }
+            _ if status.is_success() => {
+                let entry = CacheEntry {
+                    url,
+                    etag: header_value(response.headers(), ETAG),
+                    last_modified: header_value(response.headers(), LAST_MODIFIED),
+                    stored_at: now(),
+                };
+                let body = response.bytes().await?.to_vec();
+                self.store(&key, &entry, Some(&body)).await;
+                Ok(CachedResponse { status, body })
+            }
+            _ => Ok(CachedResponse {
+                status,
+                body: response.bytes().await?.to_vec(),
+            }),
+        }
+    }
+
+    /// Delete entries last stored more than `max_age` ago, along with bodies
+    /// and temporary files left without an entry. Search URLs change with
+    /// every query, so without this the cache only grows.
+    pub async fn prune(&self, max_age: Duration) -> AppResult<usize> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let now = now();
+        let mut removed = 0;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let (key, extension) = name.split_once('.').unwrap_or((name, ""));
+
+            let expired = match extension {
+                "json" => tokio::fs::read(&path)
+                    .await
+                    .ok()
+                    .and_then(|meta| serde_json::from_slice::<CacheEntry>(&meta).ok())
+                    .map_or(true, |cached| !is_fresh(&cached, max_age, now)),
+                "body" => !tokio::fs::try_exists(self.dir.join(format!("{key}.json"))).await?,
+                // Leftovers of interrupted writes, once no write can still be running
+                _ => entry
+                    .metadata()
+                    .await?
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok())
+                    .map_or(true, |age| age >= max_age),
+            };
+            if !expired {
+                continue;
+            }
+            tokio::fs::remove_file(&path).await?;
+            if extension == "json" {
+                let _ = tokio::fs::remove_file(self.dir.join(format!("{key}.body"))).await;
+                removed += 1;
+            }
+        }
+
+        log::info!("[CACHE] Pruned {removed} entries");
+        Ok(removed)
+    }
+
+    async fn load(&self, key: &str) -> Option<(CacheEntry, Vec<u8>)> {
+        let meta = tokio::fs::read(self.dir.join(format!("{key}.json")))
+            .await
+            .ok()?;
+        let entry: CacheEntry = serde_json::from_slice(&meta).ok()?;
+        let body = tokio::fs::read(self.dir.join(format!("{key}.body")))
+            .await
+            .ok()?;
+        Some((entry, body))
+    }
+
+    /// Write an entry, and its body when it changed. Failures only cost a
+    /// future cache miss, so they are logged rather than returned.
+    async fn store(&self, key: &str, entry: &CacheEntry, body: Option<&[u8]>) {
+        let result: AppResult<()> = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            if let Some(body) = body {
+                write_atomic(self.dir.join(format!("{key}.body")), body).await?;
+            }
+            let meta = serde_json::to_vec(entry)?;
+            write_atomic(self.dir.join(format!("{key}.json")), &meta).await
+        }
+        .await;
+
+        if let Err(e) = result {
+            log::warn!("[CACHE] Failed to store {}: {e}", entry.url);
+        }
+    }
+}
+
+// --- Helpers ---
+
+fn cache_key(url: &str) -> String {
+    format!("{:x}", Sha256::digest(url.as_bytes()))
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn is_fresh(entry: &CacheEntry, ttl: Duration, now: i64) -> bool {
+    let age = now.saturating_sub(entry.stored_at);
+    age >= 0 && (age as u64) < ttl.as_secs()
+}
+
+fn header_value(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Write through a temporary file so readers never see a partial body.
+async fn write_atomic(path: PathBuf, data: &[u8]) -> AppResult<()> {
+    let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+    tokio::fs::write(&tmp, data).await?;
+    if let Err(e) = tokio::fs::rename(&tmp, &path).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{canned_server, http_response};
+
+    #[tokio::test]
+    async fn revalidates_with_etag_and_falls_back_when_offline() -> AppResult<()> {
+        let dir = std::env::temp_dir().join(format!("minesync_http_{}", uuid::Uuid::new_v4()));
+        let cache = HttpCache::new(dir.clone());
+        let client = reqwest::Client::new();
+        let server = canned_server(vec![
+            http_response("200 OK\r\nETag: \"v1\"", br#"{"n":1}"#),
+            http_response("304 Not Modified", b""),
+        ]);
+        let url = format!("{}/manifest.json", server.base);
+        let get = |ttl| cache.send(client.get(&url), ttl);
+
+        let first: serde_json::Value = get(Duration::ZERO).await?.json()?;
+        let second: serde_json::Value = get(Duration::ZERO).await?.json()?;
+        // Fresh entries are served without a request
+        let fresh = get(MANIFEST_TTL).await?;
+
+        let requests: Vec<String> = server.requests().iter().map(|r| r.to_lowercase()).collect();
+        assert_eq!(first["n"], 1);
+        assert_eq!(second["n"], 1);
+        assert!(fresh.status().is_success());
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"v1\""));
+
+        // The server is gone: the stale copy is served, unknown URLs fail
+        let offline = get(Duration::ZERO).await?;
+        assert_eq!(offline.json::<serde_json::Value>()?["n"], 1);
+        assert!(cache
+            .send(
+                client.get(format!("{}/other.json", server.base)),
+                Duration::ZERO
+            )
+            .await
+            .is_err());
+
+        let _ = std::fs::remove_dir_all(dir);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn prune_drops_old_entries_and_orphans() -> AppResult<()> {
+        let dir = std::env::temp_dir().join(format!("minesync_http_{}", uuid::Uuid::new_v4()));
+        let cache = HttpCache::new(dir.clone());
+        let entry = |url: &str, age: i64| CacheEntry {
+            url: url.to_string(),
+            etag: None,
+            last_modified: None,
+            stored_at: now() - age,
+        };
+        let old_key = cache_key("https://example.com/search?query=old");
+        let new_key = cache_key("https://example.com/search?query=new");
+        let max_age = MAX_ENTRY_AGE.as_secs() as i64;
+        cache
+            .store(&old_key, &entry("old", max_age + 60), Some(b"old"))
+            .await;
+        cache.store(&new_key, &entry("new", 60), Some(b"new")).await;
+        std::fs::write(dir.join("orphan.body"), b"orphan")?;
+
+        assert_eq!(cache.prune(MAX_ENTRY_AGE).await?, 1);
+        assert!(cache.load(&old_key).await.is_none());
+        assert!(!dir.join(format!("{old_key}.body")).exists());
+        assert!(!dir.join("orphan.body").exists());
+        assert!(cache.load(&new_key).await.is_some());
+
+        let _ = std::fs::remove_dir_all(dir);
+        Ok(())
+    }
+}
@@ -1,126 +1,234 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::Mutex;
 
 use chrono::Utc;
 
 use crate::errors::{AppError, AppResult};
-use crate::models::install::{CfManifest, InstallProgress, InstallStage, MrIndex};
+use crate::models::dependency_plan::{ModInstallResult, PlannedMod, UnresolvedDependency};
+use crate::models::install::{
+    CfManifest, CheckpointStage, FailedModDownload, InstallCheckpoint, InstallStage, MrEnvSupport,
+    MrIndex,
+};
 use crate::models::instance::{MinecraftInstance, ModLoader};
-use crate::models::mod_info::{ModInfo, ModSource};
+use crate::models::mod_info::{JarMetadata, ModInfo, ModSource, DISABLED_SUFFIX};
+use crate::models::mod_platform::ReleaseChannel;
 use crate::services::database::DatabaseService;
-use crate::services::download::{DownloadService, DownloadTask};
+use crate::services::dependency_resolver;
+use crate::services::download::{DownloadService, DownloadTask, FileHashes};
+use crate::services::jar_metadata;
+use crate::services::jobs::{CancelToken, JobHandle};
 use crate::services::loader::LoaderService;
 use crate::services::minecraft::MinecraftService;
-use crate::services::mod_platform::UnifiedModClient;
+use crate::services::mod_platform::{find_incompatibility, UnifiedModClient};
 
-/// Orchestrates mod and modpack installation.
+/// Orchestrates mod and modpack installation. Installs run concurrently as
+/// jobs, but only one at a time per instance.
 pub struct InstallService {
-    progress: Arc<Mutex<InstallProgress>>,
-    install_in_progress: AtomicBool,
+    busy_instances: Mutex<HashSet<String>>,
 }
 
-struct InstallGuard<'a> {
-    flag: &'a AtomicBool,
+struct InstanceGuard<'a> {
+    busy_instances: &'a Mutex<HashSet<String>>,
+    instance_id: String,
 }
 
-impl Drop for InstallGuard<'_> {
+impl Drop for InstanceGuard<'_> {
     fn drop(&mut self) {
-        self.flag.store(false, Ordering::Release);
+        if let Ok(mut busy) = self.busy_instances.lock() {
+            busy.remove(&self.instance_id);
+        }
     }
 }
 
 impl InstallService {
     pub fn new() -> Self {
         Self {
-            progress: Arc::new(Mutex::new(InstallProgress::idle())),
-            install_in_progress: AtomicBool::new(false),
+            busy_instances: Mutex::new(HashSet::new()),
         }
     }
 
-    pub fn get_progress(&self) -> AppResult<InstallProgress> {
-        Ok(self.lock_progress()?.clone())
-    }
-
     /// Install a single mod into an existing instance.
+    ///
+    /// With `install_dependencies`, the required dependencies missing from
+    /// the instance are resolved for its Minecraft version and loader and
+    /// installed too, marked as auto-installed. Declared conflicts abort the
+    /// install before anything is downloaded, as does a version not built for
+    /// the instance's Minecraft version and loader.
+    #[allow(clippy::too_many_arguments)]
     pub async fn install_mod(
         &self,
         db: &DatabaseService,
         mod_client: &UnifiedModClient,
         download_service: &DownloadService,
+        job: &JobHandle,
         instance_id: &str,
         source: &ModSource,
         project_id: &str,
         version_id: &str,
-    ) -> AppResult<ModInfo> {
-        let _install_guard = self.begin_install()?;
+        install_dependencies: bool,
+    ) -> AppResult<ModInstallResult> {
+        let _instance_guard = match self.claim_instance(instance_id) {
+            Ok(guard) => guard,
+            Err(e) => {
+                job.fail(&e);
+                return Err(e);
+            }
+        };
+        let download_service = &job.download_service(download_service);
+        let result = self
+            .install_mod_files(
+                db,
+                mod_client,
+                download_service,
+                job,
+                instance_id,
+                source,
+                project_id,
+                version_id,
+                install_dependencies,
+            )
+            .await;
+        match &result {
+            Ok(_) => job.complete(),
+            Err(e) => job.fail(e),
+        }
+        result
+    }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn install_mod_files(
+        &self,
+        db: &DatabaseService,
+        mod_client: &UnifiedModClient,
+        download_service: &DownloadService,
+        job: &JobHandle,
+        instance_id: &str,
+        source: &ModSource,
+        project_id: &str,
+        version_id: &str,
+        install_dependencies: bool,
+    ) -> AppResult<ModInstallResult> {
         // Validate instance exists
         let instance = db
             .get_instance(instance_id)?
             .ok_or_else(|| AppError::Custom(format!("Instance not found: {instance_id}")))?;
 
-        self.set_progress_fresh(InstallStage::FetchingInfo, 10.0)?;
+        job.update_progress(|p| p.instance_id = Some(instance_id.to_string()))?;
+        job.set_progress(InstallStage::FetchingInfo, 10.0)?;
 
         // Fetch versions and find the requested one
-        let versions = mod_client
-            .get_versions(source, project_id, None, None)
+        let versions = job
+            .run(mod_client.get_versions(source, project_id, None, None))
             .await?;
 
         let version = versions
             .into_iter()
             .find(|v| v.id == version_id)
             .ok_or_else(|| AppError::Custom(format!("Version not found: {version_id}")))?;
+        if let Some(incompatibility) = find_incompatibility(&version, &instance) {
+            return Err(AppError::IncompatibleVersion(Box::new(incompatibility)));
+        }
 
-        let file = version
-            .files
-            .iter()
-            .find(|f| f.primary)
-            .or_else(|| version.files.first())
-            .ok_or_else(|| AppError::Custom("No files in version".to_string()))?;
-
-        self.set_progress(InstallStage::DownloadingMods { current: 1, total: 1 }, 40.0)?;
-
-        // Download the mod JAR
-        let mods_dir = PathBuf::from(&instance.instance_path).join("mods");
-        let dest = mods_dir.join(&file.filename);
+        let mut unresolved = Vec::new();
+        let mut to_enable = Vec::new();
+        let planned = if install_dependencies {
+            job.set_progress(InstallStage::ResolvingMods, 20.0)?;
+            let plan = job
+                .run(dependency_resolver::resolve_plan(
+                    db, mod_client, &instance, version,
+                ))
+                .await?;
 
-        let task = DownloadTask {
-            url: file.url.clone(),
-            dest,
-            sha1: file.hashes.get("sha1").cloned(),
-            size: file.size,
+            if !plan.conflicts.is_empty() {
+                let conflicts: Vec<String> = plan
+                    .conflicts
+                    .iter()
+                    .map(|c| format!("{} is incompatible with {}", c.mod_name, c.other_name))
+                    .collect();
+                return Err(AppError::Custom(format!(
+                    "Cannot install: {}",
+                    conflicts.join("; ")
+                )));
+            }
+            unresolved = plan.unresolved;
+            to_enable = plan
+                .skip
+                .into_iter()
+                .filter(|s| !s.installed_enabled)
+                .map(|s| s.installed_mod_id)
+                .collect();
+            plan.install
+        } else {
+            vec![PlannedMod {
+                version,
+                slug: None,
+                required_by: None,
+            }]
         };
 
-        download_service.download_all(vec![task]).await?;
-
-        self.set_progress(InstallStage::RegisteringMods, 80.0)?;
+        // The requested mod comes first: if it fails nothing else is installed
+        let mods_dir = PathBuf::from(&instance.instance_path).join("mods");
+        let total = planned.len() as u32;
+        let mut installed = None;
+        let mut dependencies = Vec::new();
+        for (index, planned) in planned.into_iter().enumerate() {
+            job.set_progress(
+                InstallStage::DownloadingMods {
+                    current: index as u32 + 1,
+                    total,
+                },
+                30.0 + 65.0 * index as f32 / total as f32,
+            )?;
 
-        // Register in DB
-        let mod_info = ModInfo {
-            id: uuid::Uuid::new_v4().to_string(),
-            instance_id: instance.id.clone(),
-            name: version.name.clone(),
-            slug: None,
-            version: version.version_number.clone(),
-            file_name: file.filename.clone(),
-            file_hash: file.hashes.get("sha1").cloned(),
-            source: source.clone(),
-            source_project_id: Some(project_id.to_string()),
-            source_version_id: Some(version_id.to_string()),
-            is_active: true,
-            installed_at: Utc::now(),
-        };
+            let Some(required_by) = planned.required_by.clone() else {
+                installed = Some(
+                    install_planned(db, download_service, &instance, &mods_dir, planned).await?,
+                );
+                continue;
+            };
+            let project_id = planned.version.project_id.clone();
+            let source = planned.version.source.clone();
+            match install_planned(db, download_service, &instance, &mods_dir, planned).await {
+                Ok(mod_info) => dependencies.push(mod_info),
+                Err(AppError::Cancelled) => return Err(AppError::Cancelled),
+                Err(e) => unresolved.push(UnresolvedDependency {
+                    project_id,
+                    source,
+                    required_by,
+                    reason: e.to_string(),
+                }),
+            }
+        }
 
-        db.add_mod_to_instance(&mod_info)?;
+        for dep in &unresolved {
+            log::warn!(
+                "[INSTALL] Dependency {} of {} not installed: {}",
+                dep.project_id,
+                dep.required_by,
+                dep.reason
+            );
+        }
+        let installed =
+            installed.ok_or_else(|| AppError::Custom(format!("Failed to install {project_id}")))?;
 
-        self.set_progress(InstallStage::Completed, 100.0)?;
-        Ok(mod_info)
+        // Disabled dependencies are only turned back on once the mod needing
+        // them is in place
+        for mod_id in &to_enable {
+            self.toggle_mod(db, mod_id, true)?;
+        }
+        Ok(ModInstallResult {
+            installed,
+            dependencies,
+            unresolved,
+        })
     }
 
     /// Remove a mod from an instance: delete file from disk, then disable it in DB.
-    pub fn remove_mod(&self, db: &DatabaseService, mod_id: &str) -> AppResult<()> {
+    ///
+    /// Returns the auto-installed dependencies nothing needs any more, so the
+    /// caller can offer to remove them too.
+    pub fn remove_mod(&self, db: &DatabaseService, mod_id: &str) -> AppResult<Vec<ModInfo>> {
         let mod_info = db
             .get_mod_by_id(mod_id)?
             .ok_or_else(|| AppError::Custom(format!("Mod not found: {mod_id}")))?;
@@ -131,7 +239,7 @@ impl InstallService {
 
         let mod_path = PathBuf::from(instance.instance_path)
             .join("mods")
-            .join(&mod_info.file_name);
+            .join(mod_info.disk_file_name());
 
         if let Err(e) = std::fs::remove_file(&mod_path) {
             if e.kind() != std::io::ErrorKind::NotFound {
@@ -142,10 +250,54 @@ impl InstallService {
             }
         }
 
-        db.remove_mod_from_instance(mod_id)
+        db.remove_mod_from_instance(mod_id)?;
+        dependency_resolver::find_orphaned_dependencies(db, &instance.id)
+    }
+
+    /// Enable or disable a mod without uninstalling it.
+    ///
+    /// Disabling renames `mods/<file>.jar` to `mods/<file>.jar.disabled` so the
+    /// loader skips it; enabling renames it back. The DB row stays active.
+    pub fn toggle_mod(
+        &self,
+        db: &DatabaseService,
+        mod_id: &str,
+        enabled: bool,
+    ) -> AppResult<ModInfo> {
+        let mut mod_info = db
+            .get_mod_by_id(mod_id)?
+            .ok_or_else(|| AppError::Custom(format!("Mod not found: {mod_id}")))?;
+
+        let instance = db
+            .get_instance(&mod_info.instance_id)?
+            .ok_or_else(|| AppError::Custom(format!("Instance not found: {}", mod_info.instance_id)))?;
+
+        if mod_info.enabled == enabled {
+            return Ok(mod_info);
+        }
+
+        let mods_dir = PathBuf::from(&instance.instance_path).join("mods");
+        let from = mods_dir.join(mod_info.disk_file_name());
+        mod_info.enabled = enabled;
+        let to = mods_dir.join(mod_info.disk_file_name());
+
+        // A missing file is not fatal: the state is recorded and applied
+        // to whatever file shows up before the next launch.
+        if let Err(e) = std::fs::rename(&from, &to) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(AppError::Custom(format!(
+                    "Failed to rename mod file {}: {e}",
+                    from.display()
+                )));
+            }
+        }
+
+        db.set_mod_enabled(mod_id, enabled)?;
+        Ok(mod_info)
     }
 
     /// Install a modpack: creates a complete new instance with MC + loader + all mods.
+    #[allow(clippy::too_many_arguments)]
     pub async fn install_modpack(
         &self,
         db: &DatabaseService,
@@ -153,6 +305,7 @@ impl InstallService {
         download_service: &DownloadService,
         mc_service: &MinecraftService,
         loader_service: &LoaderService,
+        job: &JobHandle,
         source: &ModSource,
         project_id: &str,
         version_id: &str,
@@ -160,209 +313,464 @@ impl InstallService {
         modpack_icon_url: Option<String>,
         modpack_description: Option<String>,
     ) -> AppResult<MinecraftInstance> {
-        let _install_guard = self.begin_install()?;
+        let archive = PackArchive::Remote {
+            source,
+            project_id,
+            version_id,
+        };
+        self.install_pack(
+            db,
+            mod_client,
+            download_service,
+            mc_service,
+            loader_service,
+            job,
+            archive,
+            modpack_name,
+            modpack_icon_url,
+            modpack_description,
+        )
+        .await
+    }
 
-        // Initialize progress with modpack metadata from the start
-        {
-            let mut progress = self.lock_progress()?;
-            *progress = InstallProgress::new(InstallStage::FetchingInfo, 0.0);
-            progress.modpack_name = modpack_name.clone();
-            progress.modpack_icon_url = modpack_icon_url.clone();
+    /// Install a modpack from a local `.mrpack` or CurseForge export zip, e.g.
+    /// a private pack that isn't published on any platform.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn import_modpack_file(
+        &self,
+        db: &DatabaseService,
+        mod_client: &UnifiedModClient,
+        download_service: &DownloadService,
+        mc_service: &MinecraftService,
+        loader_service: &LoaderService,
+        job: &JobHandle,
+        path: &Path,
+    ) -> AppResult<MinecraftInstance> {
+        if !path.is_file() {
+            return Err(AppError::Custom(format!(
+                "Modpack file not found: {}",
+                path.display()
+            )));
+        }
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        if !matches!(extension.as_deref(), Some("mrpack" | "zip")) {
+            return Err(AppError::Custom(
+                "Unsupported modpack file (expected .mrpack or .zip)".to_string(),
+            ));
         }
 
-        let temp_dir = std::env::temp_dir().join(format!("minesync_modpack_{}", uuid::Uuid::new_v4()));
-        tokio::fs::create_dir_all(&temp_dir).await?;
+        self.install_pack(
+            db,
+            mod_client,
+            download_service,
+            mc_service,
+            loader_service,
+            job,
+            PackArchive::Local(path),
+            None,
+            None,
+            None,
+        )
+        .await
+    }
 
-        let instance_id = uuid::Uuid::new_v4().to_string();
+    #[allow(clippy::too_many_arguments)]
+    async fn install_pack(
+        &self,
+        db: &DatabaseService,
+        mod_client: &UnifiedModClient,
+        download_service: &DownloadService,
+        mc_service: &MinecraftService,
+        loader_service: &LoaderService,
+        job: &JobHandle,
+        archive: PackArchive<'_>,
+        modpack_name: Option<String>,
+        modpack_icon_url: Option<String>,
+        modpack_description: Option<String>,
+    ) -> AppResult<MinecraftInstance> {
+        let download_service = &job.download_service(download_service);
+        job.update_progress(|p| {
+            p.modpack_name = modpack_name.clone();
+            p.modpack_icon_url = modpack_icon_url.clone();
+        })?;
+
+        // Nothing is worth resuming until the pack archive is on disk
         let base_dir = mc_service.base_dir();
-        let instance_path = base_dir.join("instances").join(&instance_id);
-        let mut maybe_instance_path = Some(instance_path.clone());
-
-        let install_result: AppResult<(MinecraftInstance, Vec<ModDownloadInfo>)> = async {
-            // 1. Fetch version info to get the modpack download URL
-            self.set_progress(InstallStage::FetchingInfo, 2.0)?;
-            let versions = mod_client
-                .get_versions(source, project_id, None, None)
-                .await?;
-            let version = versions
-                .into_iter()
-                .find(|v| v.id == version_id)
-                .ok_or_else(|| AppError::Custom(format!("Modpack version not found: {version_id}")))?;
-            let file = version
-                .files
-                .iter()
-                .find(|f| f.primary)
-                .or_else(|| version.files.first())
-                .ok_or_else(|| AppError::Custom("No files in modpack version".to_string()))?;
-
-            // 2. Download the modpack ZIP
-            self.set_progress(InstallStage::DownloadingPack, 5.0)?;
-            let zip_path = temp_dir.join(&file.filename);
-            let dl_task = DownloadTask {
-                url: file.url.clone(),
-                dest: zip_path.clone(),
-                sha1: file.hashes.get("sha1").cloned(),
-                size: file.size,
-            };
-            download_service.download_all(vec![dl_task]).await?;
-
-            // 3. Extract the ZIP
-            self.set_progress(InstallStage::ExtractingPack, 12.0)?;
-            let extract_dir = temp_dir.join("extracted");
-            extract_zip(&zip_path, &extract_dir)?;
-
-            // 4. Parse manifest and build instance metadata
-            let pack_info = parse_modpack_manifest(&extract_dir)?;
-            self.set_progress(InstallStage::CreatingInstance, 18.0)?;
-            // Set instance_id in progress so frontend can track which instance is installing
-            {
-                let mut p = self.lock_progress()?;
-                p.instance_id = Some(instance_id.clone());
+        let archive_path = match self
+            .fetch_archive(mod_client, download_service, job, base_dir, archive)
+            .await
+        {
+            Ok(path) => path,
+            Err(e) => {
+                job.fail(&e);
+                return Err(e);
             }
-            tokio::fs::create_dir_all(instance_path.join("mods")).await?;
-
-            let now = Utc::now();
-            let instance = MinecraftInstance {
-                id: instance_id.clone(),
-                name: pack_info.name.clone(),
-                minecraft_version: pack_info.mc_version.clone(),
-                loader: pack_info.loader,
-                loader_version: pack_info.loader_version.clone(),
-                instance_path: instance_path.to_string_lossy().to_string(),
-                icon_path: None,
-                icon_url: modpack_icon_url.clone(),
-                description: modpack_description.clone(),
-                last_played_at: None,
-                total_play_time: 0,
-                is_active: true,
-                created_at: now,
-                updated_at: now,
-            };
+        };
+
+        let instance_id = uuid::Uuid::new_v4().to_string();
+        let _instance_guard = self.claim_instance(&instance_id)?;
+        let checkpoint = InstallCheckpoint {
+            instance_path: base_dir
+                .join("instances")
+                .join(&instance_id)
+                .to_string_lossy()
+                .to_string(),
+            instance_id,
+            archive_path: archive_path.to_string_lossy().to_string(),
+            modpack_name,
+            modpack_icon_url,
+            modpack_description,
+            stage: CheckpointStage::PackReady,
+            failed_mods: Vec::new(),
+            error: None,
+            updated_at: Utc::now(),
+        };
+        if let Err(e) = db.save_install_checkpoint(&checkpoint) {
+            job.fail(&e);
+            return Err(e);
+        }
+
+        self.run_checkpoint(
+            db,
+            mod_client,
+            download_service,
+            mc_service,
+            loader_service,
+            job,
+            checkpoint,
+            false,
+        )
+        .await
+    }
+
+    /// Continue an unfinished modpack install from its last completed stage.
+    /// With `skip_failed_optional`, optional pack files that still fail to
+    /// download are left out instead of stopping the install again.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn resume_install(
+        &self,
+        db: &DatabaseService,
+        mod_client: &UnifiedModClient,
+        download_service: &DownloadService,
+        mc_service: &MinecraftService,
+        loader_service: &LoaderService,
+        job: &JobHandle,
+        instance_id: &str,
+        skip_failed_optional: bool,
+    ) -> AppResult<MinecraftInstance> {
+        let claimed = self.claim_instance(instance_id).and_then(|guard| {
+            let checkpoint = db.get_install_checkpoint(instance_id)?.ok_or_else(|| {
+                AppError::Custom(format!("No unfinished install for instance {instance_id}"))
+            })?;
+            Ok((guard, checkpoint))
+        });
+        let (_instance_guard, checkpoint) = match claimed {
+            Ok(claimed) => claimed,
+            Err(e) => {
+                job.fail(&e);
+                return Err(e);
+            }
+        };
+        let download_service = &job.download_service(download_service);
+        job.update_progress(|p| {
+            p.modpack_name = checkpoint.modpack_name.clone();
+            p.modpack_icon_url = checkpoint.modpack_icon_url.clone();
+        })?;
+
+        self.run_checkpoint(
+            db,
+            mod_client,
+            download_service,
+            mc_service,
+            loader_service,
+            job,
+            checkpoint,
+            skip_failed_optional,
+        )
+        .await
+    }
+
+    /// Give up on an unfinished install, deleting its files.
+    pub async fn discard_install(
+        &self,
+        db: &DatabaseService,
+        base_dir: &Path,
+        instance_id: &str,
+    ) -> AppResult<()> {
+        let _instance_guard = self.claim_instance(instance_id)?;
+        let checkpoint = db.get_install_checkpoint(instance_id)?.ok_or_else(|| {
+            AppError::Custom(format!("No unfinished install for instance {instance_id}"))
+        })?;
+
+        let _ = tokio::fs::remove_dir_all(&checkpoint.instance_path).await;
+        remove_cached_archive(base_dir, &checkpoint.archive_path).await;
+        db.delete_install_checkpoint(instance_id)
+    }
+
+    /// Run the remaining stages of a checkpointed install. On failure the
+    /// instance folder and pack archive are kept and the checkpoint records
+    /// where to pick up again; a cancelled install is discarded instead.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_checkpoint(
+        &self,
+        db: &DatabaseService,
+        mod_client: &UnifiedModClient,
+        download_service: &DownloadService,
+        mc_service: &MinecraftService,
+        loader_service: &LoaderService,
+        job: &JobHandle,
+        mut checkpoint: InstallCheckpoint,
+        skip_failed_optional: bool,
+    ) -> AppResult<MinecraftInstance> {
+        let temp_dir =
+            std::env::temp_dir().join(format!("minesync_modpack_{}", uuid::Uuid::new_v4()));
+        let result = self
+            .run_stages(
+                db,
+                mod_client,
+                download_service,
+                mc_service,
+                loader_service,
+                job,
+                &mut checkpoint,
+                &temp_dir,
+                skip_failed_optional,
+            )
+            .await;
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+
+        match result {
+            Ok(instance) => {
+                if let Err(e) = db.delete_install_checkpoint(&instance.id) {
+                    log::warn!("Failed to clear install checkpoint of {}: {e}", instance.id);
+                }
+                remove_cached_archive(mc_service.base_dir(), &checkpoint.archive_path).await;
+                job.complete();
+                Ok(instance)
+            }
+            Err(AppError::Cancelled) => {
+                let _ = tokio::fs::remove_dir_all(&checkpoint.instance_path).await;
+                remove_cached_archive(mc_service.base_dir(), &checkpoint.archive_path).await;
+                if let Err(e) = db.delete_install_checkpoint(&checkpoint.instance_id) {
+                    log::warn!(
+                        "Failed to clear install checkpoint of {}: {e}",
+                        checkpoint.instance_id
+                    );
+                }
+                job.fail(&AppError::Cancelled);
+                Err(AppError::Cancelled)
+            }
+            Err(e) => {
+                checkpoint.error = Some(e.to_string());
+                checkpoint.updated_at = Utc::now();
+                if let Err(save_err) = db.save_install_checkpoint(&checkpoint) {
+                    log::warn!("Failed to save install checkpoint: {save_err}");
+                }
+                job.fail(&e);
+                Err(e)
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn run_stages(
+        &self,
+        db: &DatabaseService,
+        mod_client: &UnifiedModClient,
+        download_service: &DownloadService,
+        mc_service: &MinecraftService,
+        loader_service: &LoaderService,
+        job: &JobHandle,
+        checkpoint: &mut InstallCheckpoint,
+        temp_dir: &Path,
+        skip_failed_optional: bool,
+    ) -> AppResult<MinecraftInstance> {
+        let instance_path = PathBuf::from(&checkpoint.instance_path);
+
+        // 3. Extract the ZIP
+        job.set_progress(InstallStage::ExtractingPack, 12.0)?;
+        let extract_dir = temp_dir.join("extracted");
+        extract_zip(Path::new(&checkpoint.archive_path), &extract_dir)?;
+
+        // 4. Parse manifest and build instance metadata
+        let pack_info = parse_modpack_manifest(&extract_dir)?;
+        job.set_progress(InstallStage::CreatingInstance, 18.0)?;
+        // Set instance_id in progress so frontend can track which instance is installing
+        job.update_progress(|p| {
+            p.instance_id = Some(checkpoint.instance_id.clone());
+            // Local files carry no platform metadata; name them after their manifest
+            if p.modpack_name.is_none() {
+                p.modpack_name = Some(pack_info.name.clone());
+            }
+        })?;
+        tokio::fs::create_dir_all(instance_path.join("mods")).await?;
 
-            // 5. Download Minecraft version
-            self.set_progress(InstallStage::DownloadingMinecraft, 22.0)?;
-            mc_service.fetch_version_manifest().await?;
-            let detail = mc_service.fetch_version_detail(&pack_info.mc_version).await?;
-            let mc_tasks = mc_service.resolve_downloads(&detail).await?;
-            download_service.download_all(mc_tasks).await?;
+        let now = Utc::now();
+        let instance = MinecraftInstance {
+            id: checkpoint.instance_id.clone(),
+            name: pack_info.name.clone(),
+            minecraft_version: pack_info.mc_version.clone(),
+            loader: pack_info.loader.clone(),
+            loader_version: pack_info.loader_version.clone(),
+            instance_path: checkpoint.instance_path.clone(),
+            icon_path: None,
+            icon_url: checkpoint.modpack_icon_url.clone(),
+            description: checkpoint.modpack_description.clone(),
+            last_played_at: None,
+            total_play_time: 0,
+            is_active: true,
+            min_release_channel: ReleaseChannel::default(),
+            created_at: now,
+            updated_at: now,
+        };
 
-            // 6. Install mod loader (if not Vanilla) + download loader libraries
+        // 5. Download Minecraft version
+        if checkpoint.stage < CheckpointStage::MinecraftReady {
+            job.set_progress(InstallStage::DownloadingMinecraft, 22.0)?;
+            job.run(async {
+                mc_service.fetch_version_manifest().await?;
+                let detail = mc_service
+                    .fetch_version_detail(&pack_info.mc_version)
+                    .await?;
+                let mc_tasks = mc_service.resolve_downloads(&detail).await?;
+                download_service.download_all(mc_tasks).await
+            })
+            .await?;
+            advance_checkpoint(db, checkpoint, CheckpointStage::MinecraftReady)?;
+        }
+
+        // 6. Install mod loader (if not Vanilla) + download loader libraries
+        if checkpoint.stage < CheckpointStage::LoaderReady {
             if instance.loader != ModLoader::Vanilla {
-                self.set_progress(InstallStage::InstallingLoader, 35.0)?;
+                job.set_progress(InstallStage::InstallingLoader, 35.0)?;
                 if let Some(ref lv) = pack_info.loader_version {
-                    let loader_profile = loader_service
-                        .install_loader(&instance.loader, &pack_info.mc_version, lv)
-                        .await?;
-
-                    loader_service
-                        .download_loader_libraries(&loader_profile, download_service)
-                        .await?;
+                    job.run(async {
+                        let loader_profile = loader_service
+                            .install_loader(&instance.loader, &pack_info.mc_version, lv)
+                            .await?;
+
+                        loader_service
+                            .download_loader_libraries(&loader_profile, download_service)
+                            .await
+                    })
+                    .await?;
                 }
             }
+            advance_checkpoint(db, checkpoint, CheckpointStage::LoaderReady)?;
+        }
 
-            // 7. Resolve mod download URLs
-            self.set_progress(InstallStage::ResolvingMods, 42.0)?;
-            let mod_downloads = match pack_info.format {
-                PackFormat::CurseForge(ref manifest) => resolve_cf_mods(mod_client, manifest).await?,
-                PackFormat::Modrinth(ref index) => resolve_mr_mods(index),
-            };
+        // 7. Resolve mod download URLs
+        job.set_progress(InstallStage::ResolvingMods, 42.0)?;
+        let (mut mod_downloads, unavailable) = match pack_info.format {
+            PackFormat::CurseForge(ref manifest) => {
+                job.run(resolve_cf_mods(mod_client, manifest)).await?
+            }
+            PackFormat::Modrinth(ref index) => (resolve_mr_mods(index), Vec::new()),
+        };
 
-            // 8. Download all mods
+        // 8. Download all mods, keeping whatever succeeded for the next attempt
+        if checkpoint.stage < CheckpointStage::ModsReady {
             let total_mods = mod_downloads.len() as u32;
-            self.set_progress(
+            job.set_progress(
                 InstallStage::DownloadingMods { current: 0, total: total_mods },
                 50.0,
             )?;
             let mod_tasks: Vec<DownloadTask> = mod_downloads
                 .iter()
-                .map(|m| {
-                    // Use the full relative path when available (Modrinth packs
-                    // place files in mods/, shaderpacks/, resourcepacks/, etc.).
-                    // CurseForge packs always go into mods/.
-                    //
-                    // Defence-in-depth: validate again at download time even though
-                    // resolve_mr_mods already sanitises.  If the path is rejected
-                    // here, fall back to mods/ to avoid skipping the file entirely.
-                    let dest = match m.relative_path {
-                        Some(ref rp) => match safe_relative_path(rp) {
-                            Some(safe) => instance_path.join(safe),
-                            None => instance_path.join("mods").join(&m.filename),
-                        },
-                        None => instance_path.join("mods").join(&m.filename),
-                    };
-                    DownloadTask {
-                        url: m.url.clone(),
-                        dest,
-                        sha1: m.sha1.clone(),
-                        size: m.size,
-                    }
+                .map(|m| DownloadTask {
+                    urls: m.urls.clone(),
+                    dest: m.dest(&instance_path),
+                    hashes: m.hashes.clone(),
+                    size: m.size,
+                    shared: true,
                 })
                 .collect();
-            download_service.download_all(mod_tasks).await?;
+            let failures = download_service
+                .download_all_with_failures(mod_tasks)
+                .await?;
+
+            let mut failed_mods = unavailable;
+            for failure in failures {
+                if let Some(m) = mod_downloads
+                    .iter()
+                    .find(|m| m.dest(&instance_path) == failure.dest)
+                {
+                    failed_mods.push(m.failed(failure.error));
+                }
+            }
+            checkpoint.failed_mods = failed_mods;
 
-            // 9. Copy overrides
-            self.set_progress(InstallStage::CopyingOverrides, 85.0)?;
+            let blocking = checkpoint
+                .failed_mods
+                .iter()
+                .filter(|f| f.required || !skip_failed_optional)
+                .count();
+            if blocking > 0 {
+                return Err(AppError::Custom(format!(
+                    "{blocking} pack files could not be downloaded; resume the install to retry"
+                )));
+            }
+            for f in &checkpoint.failed_mods {
+                log::warn!("Skipping optional pack file {}: {}", f.name, f.reason);
+            }
+            advance_checkpoint(db, checkpoint, CheckpointStage::ModsReady)?;
+        }
+        // Files given up on are not registered
+        mod_downloads.retain(|m| {
+            let path = m.relative_dest().to_string_lossy().to_string();
+            !checkpoint
+                .failed_mods
+                .iter()
+                .any(|f| f.path.as_deref() == Some(path.as_str()))
+        });
+
+        // 9. Copy overrides
+        if checkpoint.stage < CheckpointStage::OverridesCopied {
+            job.set_progress(InstallStage::CopyingOverrides, 85.0)?;
             let overrides_dir = extract_dir.join(&pack_info.overrides_folder);
             if overrides_dir.exists() {
-                copy_dir_recursive(&overrides_dir, &instance_path).await?;
+                copy_dir_cancellable(&overrides_dir, &instance_path, job.cancel_token()).await?;
             }
 
             // 9b. Copy client-overrides (Modrinth packs — takes priority over overrides)
             let client_overrides_dir = extract_dir.join("client-overrides");
             if client_overrides_dir.exists() {
-                copy_dir_recursive(&client_overrides_dir, &instance_path).await?;
+                copy_dir_cancellable(&client_overrides_dir, &instance_path, job.cancel_token())
+                    .await?;
             }
-
-            Ok((instance, mod_downloads))
+            advance_checkpoint(db, checkpoint, CheckpointStage::OverridesCopied)?;
         }
-        .await;
-
-        let (instance, mod_downloads) = match install_result {
-            Ok(ok) => ok,
-            Err(e) => {
-                let _ = tokio::fs::remove_dir_all(&temp_dir).await;
-                if let Some(path) = &maybe_instance_path {
-                    let _ = tokio::fs::remove_dir_all(path).await;
-                }
-                let _ = self.set_progress(
-                    InstallStage::Failed {
-                        message: e.to_string(),
-                    },
-                    100.0,
-                );
-                return Err(e);
-            }
-        };
 
-        // 10. Persist in DB after successful downloads to avoid partial DB state.
-        self.set_progress(InstallStage::RegisteringMods, 92.0)?;
-        if let Err(e) = db.create_instance(&instance) {
-            let _ = tokio::fs::remove_dir_all(&temp_dir).await;
-            if let Some(path) = maybe_instance_path.take() {
-                let _ = tokio::fs::remove_dir_all(path).await;
-            }
-            let _ = self.set_progress(
-                InstallStage::Failed {
-                    message: e.to_string(),
-                },
-                100.0,
-            );
-            return Err(e);
-        }
+        // 10. Persist in DB once every file is in place
+        job.set_progress(InstallStage::RegisteringMods, 92.0)?;
+        db.create_instance(&instance)?;
 
         for m in &mod_downloads {
+            let metadata = read_installed_metadata(&m.dest(&instance_path), &instance.loader);
             let mod_info = ModInfo {
                 id: uuid::Uuid::new_v4().to_string(),
                 instance_id: instance.id.clone(),
-                name: m.name.clone(),
+                name: metadata
+                    .as_ref()
+                    .map_or_else(|| m.name.clone(), |meta| meta.name.clone()),
                 slug: None,
-                version: String::new(),
+                version: metadata
+                    .as_ref()
+                    .map(|meta| meta.version.clone())
+                    .unwrap_or_default(),
                 file_name: m.filename.clone(),
-                file_hash: m.sha1.clone(),
+                file_hash: m.hashes.sha1.clone(),
                 source: m.source.clone(),
                 source_project_id: m.project_id.clone(),
                 source_version_id: None,
                 is_active: true,
+                enabled: true,
+                file_missing: false,
+                auto_installed: false,
+                metadata,
                 installed_at: Utc::now(),
             };
             if let Err(e) = db.add_mod_to_instance(&mod_info) {
@@ -370,70 +778,251 @@ impl InstallService {
             }
         }
 
-        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
-        self.set_progress(InstallStage::Completed, 100.0)?;
         Ok(instance)
     }
 
+    /// Get the pack archive onto disk: downloaded ones go to the modpack cache
+    /// so a failed install can reuse them.
+    async fn fetch_archive(
+        &self,
+        mod_client: &UnifiedModClient,
+        download_service: &DownloadService,
+        job: &JobHandle,
+        base_dir: &Path,
+        archive: PackArchive<'_>,
+    ) -> AppResult<PathBuf> {
+        let (source, project_id, version_id) = match archive {
+            PackArchive::Local(path) => return Ok(path.to_path_buf()),
+            PackArchive::Remote {
+                source,
+                project_id,
+                version_id,
+            } => (source, project_id, version_id),
+        };
+
+        // 1. Fetch version info to get the modpack download URL
+        job.set_progress(InstallStage::FetchingInfo, 2.0)?;
+        let versions = job
+            .run(mod_client.get_versions(source, project_id, None, None))
+            .await?;
+        let version = versions
+            .into_iter()
+            .find(|v| v.id == version_id)
+            .ok_or_else(|| AppError::Custom(format!("Modpack version not found: {version_id}")))?;
+        let file = version
+            .files
+            .iter()
+            .find(|f| f.primary)
+            .or_else(|| version.files.first())
+            .ok_or_else(|| AppError::Custom("No files in modpack version".to_string()))?;
+
+        // 2. Download the modpack ZIP
+        job.set_progress(InstallStage::DownloadingPack, 5.0)?;
+        let zip_path = modpack_cache_dir(base_dir).join(format!("{version_id}-{}", file.filename));
+        let dl_task = DownloadTask {
+            urls: vec![file.url.clone()],
+            dest: zip_path.clone(),
+            hashes: FileHashes::from_map(&file.hashes),
+            size: file.size,
+            shared: false,
+        };
+        if let Some(failure) = download_service
+            .download_all_with_failures(vec![dl_task])
+            .await?
+            .into_iter()
+            .next()
+        {
+            return Err(AppError::Custom(format!(
+                "Failed to download modpack: {}",
+                failure.error
+            )));
+        }
+        Ok(zip_path)
+    }
+
     // --- Private helpers ---
 
-    fn begin_install(&self) -> AppResult<InstallGuard<'_>> {
-        match self.install_in_progress.compare_exchange(
-            false,
-            true,
-            Ordering::AcqRel,
-            Ordering::Acquire,
-        ) {
-            Ok(_) => Ok(InstallGuard {
-                flag: &self.install_in_progress,
-            }),
-            Err(_) => Err(AppError::Custom(
-                "Another installation is already in progress".to_string(),
-            )),
-        }
-    }
-
-    fn set_progress_fresh(&self, stage: InstallStage, percent: f32) -> AppResult<()> {
-        let mut progress = self.lock_progress()?;
-        *progress = InstallProgress::new(stage, percent);
-        Ok(())
+    /// Reserve an instance for one install job at a time.
+    fn claim_instance(&self, instance_id: &str) -> AppResult<InstanceGuard<'_>> {
+        let mut busy = self
+            .busy_instances
+            .lock()
+            .map_err(|e| AppError::Custom(format!("Install lock poisoned: {e}")))?;
+        if !busy.insert(instance_id.to_string()) {
+            return Err(AppError::Custom(format!(
+                "Another installation is already in progress for instance {instance_id}"
+            )));
+        }
+        Ok(InstanceGuard {
+            busy_instances: &self.busy_instances,
+            instance_id: instance_id.to_string(),
+        })
     }
+}
 
-    fn set_progress(&self, stage: InstallStage, percent: f32) -> AppResult<()> {
-        let mut progress = self.lock_progress()?;
-        // Preserve metadata across progress updates
-        let instance_id = progress.instance_id.clone();
-        let modpack_name = progress.modpack_name.clone();
-        let modpack_icon_url = progress.modpack_icon_url.clone();
-        *progress = InstallProgress::new(stage, percent);
-        progress.instance_id = instance_id;
-        progress.modpack_name = modpack_name;
-        progress.modpack_icon_url = modpack_icon_url;
-        Ok(())
+// --- Enabled state ---
+
+/// Make the files in `mods/` match the enabled state recorded in the DB.
+///
+/// Run before launch so a jar restored by hand (or a `.disabled` file left
+/// behind by a sync) never loads against the user's choice.
+pub fn apply_mod_enabled_states(db: &DatabaseService, instance: &MinecraftInstance) -> AppResult<()> {
+    let mods_dir = PathBuf::from(&instance.instance_path).join("mods");
+
+    for m in db.list_instance_mods(&instance.id)? {
+        let enabled_path = mods_dir.join(&m.file_name);
+        let disabled_path = mods_dir.join(format!("{}{DISABLED_SUFFIX}", m.file_name));
+
+        let (from, to) = if m.enabled {
+            (disabled_path, enabled_path)
+        } else {
+            (enabled_path, disabled_path)
+        };
+
+        if from.exists() && !to.exists() {
+            std::fs::rename(&from, &to)?;
+        }
     }
 
-    fn lock_progress(&self) -> AppResult<MutexGuard<'_, InstallProgress>> {
-        self.progress
-            .lock()
-            .map_err(|e| AppError::Custom(format!("Install progress lock poisoned: {e}")))
+    Ok(())
+}
+
+/// Download one planned version into `mods/` and register it.
+async fn install_planned(
+    db: &DatabaseService,
+    download_service: &DownloadService,
+    instance: &MinecraftInstance,
+    mods_dir: &Path,
+    planned: PlannedMod,
+) -> AppResult<ModInfo> {
+    let version = planned.version;
+    let file = version
+        .files
+        .iter()
+        .find(|f| f.primary)
+        .or_else(|| version.files.first())
+        .ok_or_else(|| AppError::Custom(format!("No files in version {}", version.id)))?;
+
+    let dest = mods_dir.join(&file.filename);
+    let task = DownloadTask {
+        urls: vec![file.url.clone()],
+        dest: dest.clone(),
+        hashes: FileHashes::from_map(&file.hashes),
+        size: file.size,
+        shared: true,
+    };
+    download_service.download_all(vec![task]).await?;
+    if !dest.exists() {
+        return Err(AppError::Custom(format!(
+            "Failed to download {}",
+            file.filename
+        )));
     }
+
+    // Register in DB, preferring what the jar says about itself
+    let metadata = read_installed_metadata(&dest, &instance.loader);
+    let name = metadata
+        .as_ref()
+        .map_or_else(|| version.name.clone(), |meta| meta.name.clone());
+    let mod_version = match &metadata {
+        Some(meta) if version.version_number.is_empty() => meta.version.clone(),
+        _ => version.version_number.clone(),
+    };
+    let mod_info = ModInfo {
+        id: uuid::Uuid::new_v4().to_string(),
+        instance_id: instance.id.clone(),
+        name,
+        slug: planned.slug,
+        version: mod_version,
+        file_name: file.filename.clone(),
+        file_hash: file.hashes.get("sha1").cloned(),
+        source: version.source.clone(),
+        source_project_id: Some(version.project_id.clone()),
+        source_version_id: Some(version.id.clone()),
+        is_active: true,
+        enabled: true,
+        file_missing: false,
+        auto_installed: planned.required_by.is_some(),
+        metadata,
+        installed_at: Utc::now(),
+    };
+
+    db.add_mod_to_instance(&mod_info)?;
+    db.set_mod_platform_dependencies(&mod_info.id, &version.dependencies)?;
+    Ok(mod_info)
+}
+
+/// Read a freshly downloaded jar's descriptor. Non-jar files and unreadable
+/// jars simply have no metadata.
+fn read_installed_metadata(path: &Path, loader: &ModLoader) -> Option<JarMetadata> {
+    if path.extension().and_then(|e| e.to_str()) != Some("jar") {
+        return None;
+    }
+    jar_metadata::read_jar_metadata(path, Some(loader)).unwrap_or_else(|e| {
+        log::warn!("Failed to read metadata from {}: {e}", path.display());
+        None
+    })
 }
 
 // --- Modpack parsing ---
 
 #[derive(Debug)]
 struct ModDownloadInfo {
-    url: String,
+    /// Mirrors of the file, tried in order.
+    urls: Vec<String>,
     filename: String,
     /// Relative path inside the instance directory (e.g. "mods/sodium.jar",
     /// "shaderpacks/BSL.zip"). Used to place files in the correct subdirectory.
     /// When `None`, falls back to `mods/{filename}`.
     relative_path: Option<String>,
     size: u64,
-    sha1: Option<String>,
+    hashes: FileHashes,
     name: String,
     source: ModSource,
     project_id: Option<String>,
+    /// Whether the pack needs this file; optional ones may be skipped when
+    /// they fail to download.
+    required: bool,
+}
+
+impl ModDownloadInfo {
+    /// Where the file lands inside the instance directory.
+    fn dest(&self, instance_path: &Path) -> PathBuf {
+        instance_path.join(self.relative_dest())
+    }
+
+    /// Path of the file relative to the instance directory.
+    ///
+    /// Modrinth packs place files in mods/, shaderpacks/, resourcepacks/, etc.;
+    /// CurseForge packs always go into mods/. The relative path is validated
+    /// again here as defence-in-depth even though resolve_mr_mods already
+    /// sanitises; a rejected path falls back to mods/ so the file isn't skipped.
+    fn relative_dest(&self) -> PathBuf {
+        match self.relative_path.as_deref().and_then(safe_relative_path) {
+            Some(safe) => safe,
+            None => Path::new("mods").join(&self.filename),
+        }
+    }
+
+    fn failed(&self, reason: String) -> FailedModDownload {
+        FailedModDownload {
+            name: self.name.clone(),
+            path: Some(self.relative_dest().to_string_lossy().to_string()),
+            url: self.urls.first().cloned(),
+            required: self.required,
+            reason,
+        }
+    }
+}
+
+/// Where a modpack archive comes from.
+enum PackArchive<'a> {
+    Remote {
+        source: &'a ModSource,
+        project_id: &'a str,
+        version_id: &'a str,
+    },
+    Local(&'a Path),
 }
 
 #[derive(Debug)]
@@ -549,37 +1138,52 @@ fn parse_mr_loader(deps: &HashMap<String, String>) -> (ModLoader, Option<String>
 
 // --- CurseForge mod resolution ---
 
+/// Resolve a CurseForge manifest's files. Files CurseForge no longer serves
+/// are returned as failures.
 async fn resolve_cf_mods(
     mod_client: &UnifiedModClient,
     manifest: &CfManifest,
-) -> AppResult<Vec<ModDownloadInfo>> {
+) -> AppResult<(Vec<ModDownloadInfo>, Vec<FailedModDownload>)> {
     let file_ids: Vec<u32> = manifest.files.iter().map(|f| f.file_i_d).collect();
 
     let resolved = mod_client.get_cf_files_batch(&file_ids).await?;
 
-    // Build a lookup: file_id -> project_id from manifest
-    let file_to_project: HashMap<u32, u32> = manifest
-        .files
-        .iter()
-        .map(|f| (f.file_i_d, f.project_i_d))
-        .collect();
-
     let mut downloads = Vec::with_capacity(resolved.len());
-    for f in resolved {
-        let project_id = file_to_project.get(&f.file_id).copied().unwrap_or(0);
+    for f in &resolved {
+        let Some(entry) = manifest.files.iter().find(|m| m.file_i_d == f.file_id) else {
+            continue;
+        };
         downloads.push(ModDownloadInfo {
-            url: f.download_url,
+            urls: f.download_urls.clone(),
             filename: f.file_name.clone(),
             relative_path: None,
             size: f.file_size,
-            sha1: f.sha1,
-            name: f.file_name,
+            hashes: FileHashes {
+                sha1: f.sha1.clone(),
+                md5: f.md5.clone(),
+                ..FileHashes::default()
+            },
+            name: f.file_name.clone(),
             source: ModSource::CurseForge,
-            project_id: Some(project_id.to_string()),
+            project_id: Some(entry.project_i_d.to_string()),
+            required: entry.required,
         });
     }
 
-    Ok(downloads)
+    let unavailable = manifest
+        .files
+        .iter()
+        .filter(|m| !resolved.iter().any(|f| f.file_id == m.file_i_d))
+        .map(|m| FailedModDownload {
+            name: format!("CurseForge project {} (file {})", m.project_i_d, m.file_i_d),
+            path: None,
+            url: None,
+            required: m.required,
+            reason: "File is not available from CurseForge".to_string(),
+        })
+        .collect();
+
+    Ok((downloads, unavailable))
 }
 
 // --- Modrinth mod resolution ---
@@ -589,7 +1193,9 @@ fn resolve_mr_mods(index: &MrIndex) -> Vec<ModDownloadInfo> {
         .files
         .iter()
         .filter_map(|f| {
-            let url = f.downloads.first()?;
+            if f.downloads.is_empty() {
+                return None;
+            }
             let filename = f
                 .path
                 .rsplit('/')
@@ -601,19 +1207,53 @@ fn resolve_mr_mods(index: &MrIndex) -> Vec<ModDownloadInfo> {
             let validated_path = safe_relative_path(&f.path)?;
 
             Some(ModDownloadInfo {
-                url: url.clone(),
+                urls: f.downloads.clone(),
                 filename: filename.clone(),
                 relative_path: Some(validated_path.to_string_lossy().to_string()),
                 size: f.file_size,
-                sha1: Some(f.hashes.sha1.clone()),
+                hashes: FileHashes {
+                    sha1: Some(f.hashes.sha1.clone()),
+                    sha512: f.hashes.sha512.clone(),
+                    ..FileHashes::default()
+                },
                 name: filename,
                 source: ModSource::Modrinth,
                 project_id: None,
+                required: f
+                    .env
+                    .as_ref()
+                    .map_or(true, |env| env.client != MrEnvSupport::Optional),
             })
         })
         .collect()
 }
 
+// --- Checkpoints ---
+
+fn advance_checkpoint(
+    db: &DatabaseService,
+    checkpoint: &mut InstallCheckpoint,
+    stage: CheckpointStage,
+) -> AppResult<()> {
+    checkpoint.stage = stage;
+    checkpoint.updated_at = Utc::now();
+    db.save_install_checkpoint(checkpoint)
+}
+
+/// Where downloaded pack archives wait until their install completes.
+fn modpack_cache_dir(base_dir: &Path) -> PathBuf {
+    base_dir.join("cache").join("modpacks")
+}
+
+/// Delete a pack archive if it was downloaded into the cache; imported
+/// local files belong to the user.
+async fn remove_cached_archive(base_dir: &Path, archive_path: &str) {
+    let archive_path = Path::new(archive_path);
+    if archive_path.starts_with(modpack_cache_dir(base_dir)) {
+        let _ = tokio::fs::remove_file(archive_path).await;
+    }
+}
+
 // --- Path safety ---
 
 /// Sanitise a relative path from an untrusted source (e.g. Modrinth `path`
@@ -624,7 +1264,7 @@ fn resolve_mr_mods(index: &MrIndex) -> Vec<ModDownloadInfo> {
 /// (ATLauncher) and CVE-2023-25307 (mrpack-install).
 ///
 /// Returns `None` if the path is malicious or empty.
-fn safe_relative_path(raw: &str) -> Option<PathBuf> {
+pub fn safe_relative_path(raw: &str) -> Option<PathBuf> {
     let candidate = Path::new(raw);
 
     // Reject absolute paths
@@ -685,25 +1325,34 @@ fn extract_zip(zip_path: &Path, dest: &Path) -> AppResult<()> {
 
 // --- Directory copy ---
 
-async fn copy_dir_recursive(src: &Path, dst: &Path) -> AppResult<()> {
+pub async fn copy_dir_recursive(src: &Path, dst: &Path) -> AppResult<()> {
+    copy_dir_cancellable(src, dst, CancelToken::new()).await
+}
+
+/// Copy a directory tree, stopping with `AppError::Cancelled` before the next
+/// file once `cancel` fires.
+async fn copy_dir_cancellable(src: &Path, dst: &Path, cancel: CancelToken) -> AppResult<()> {
     let src = src.to_path_buf();
     let dst = dst.to_path_buf();
 
     // Run blocking FS walk in a spawned blocking task
-    tokio::task::spawn_blocking(move || copy_dir_sync(&src, &dst))
+    tokio::task::spawn_blocking(move || copy_dir_sync(&src, &dst, &cancel))
         .await
         .map_err(|e| AppError::Custom(format!("Copy task panicked: {e}")))?
 }
 
-fn copy_dir_sync(src: &Path, dst: &Path) -> AppResult<()> {
+fn copy_dir_sync(src: &Path, dst: &Path, cancel: &CancelToken) -> AppResult<()> {
     for entry in std::fs::read_dir(src)? {
+        if cancel.is_cancelled() {
+            return Err(AppError::Cancelled);
+        }
         let entry = entry?;
         let file_type = entry.file_type()?;
         let dest_path = dst.join(entry.file_name());
 
         if file_type.is_dir() {
             std::fs::create_dir_all(&dest_path)?;
-            copy_dir_sync(&entry.path(), &dest_path)?;
+            copy_dir_sync(&entry.path(), &dest_path, cancel)?;
         } else {
             if let Some(parent) = dest_path.parent() {
                 std::fs::create_dir_all(parent)?;
@@ -719,13 +1368,117 @@ mod tests {
     use super::*;
     use std::io::Write;
 
-    use crate::models::instance::MinecraftInstance;
+    use sha1::{Digest, Sha1};
+
+    use crate::models::endpoints::Endpoints;
     use crate::models::mod_info::ModInfo;
+    use crate::services::http_cache::HttpCache;
+    use crate::services::jobs::{JobKind, JobRegistry};
+    use crate::test_support::{http_response, stub_server, test_instance, test_mod, StubServer};
 
     fn temp_path(label: &str) -> PathBuf {
         std::env::temp_dir().join(format!("minesync_test_{label}_{}", uuid::Uuid::new_v4()))
     }
 
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) -> AppResult<()> {
+        let zip_err = |e: zip::result::ZipError| AppError::Custom(format!("zip failed: {e}"));
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+        for (name, data) in entries {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .map_err(zip_err)?;
+            zip.write_all(data)?;
+        }
+        zip.finish().map_err(zip_err)?;
+        Ok(())
+    }
+
+    /// Serves `required.jar` under any path, CurseForge file lookups with
+    /// `cf_files`, and 404 for everything else.
+    fn pack_server(cf_files: serde_json::Value) -> StubServer {
+        let cf_files = serde_json::json!({ "data": cf_files }).to_string();
+        stub_server(usize::MAX, move |head| {
+            let path = head.split_whitespace().nth(1).unwrap_or_default();
+            Some(if path.ends_with("/required.jar") {
+                http_response("200 OK", b"required")
+            } else if head.starts_with("POST /curseforge/v1/mods/files ") {
+                http_response("200 OK", cf_files.as_bytes())
+            } else {
+                http_response("404 Not Found", b"")
+            })
+        })
+    }
+
+    /// Resume an install of `archive` that stopped after the loader stage.
+    /// The first attempt must stop on the failed optional files, a second one
+    /// skipping them must finish. Returns the failures the checkpoint recorded.
+    async fn resume_skipping_optional(
+        root: &Path,
+        server: &StubServer,
+        archive: &Path,
+    ) -> AppResult<(DatabaseService, MinecraftInstance, Vec<FailedModDownload>)> {
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let endpoints = Endpoints::all_at(&server.base);
+        let cache = HttpCache::new(root.join("http"));
+        let mod_client = UnifiedModClient::new(Some("key".to_string()), &endpoints, cache.clone());
+        let mc_service = MinecraftService::new(root.join("mc"), &endpoints, cache);
+        let loader_service = LoaderService::new(root.join("mc"), &endpoints);
+        let download_service = DownloadService::new();
+        let jobs = JobRegistry::new();
+        let service = InstallService::new();
+
+        let instance_id = uuid::Uuid::new_v4().to_string();
+        db.save_install_checkpoint(&InstallCheckpoint {
+            instance_id: instance_id.clone(),
+            instance_path: root.join("instance").to_string_lossy().to_string(),
+            archive_path: archive.to_string_lossy().to_string(),
+            modpack_name: None,
+            modpack_icon_url: None,
+            modpack_description: None,
+            stage: CheckpointStage::LoaderReady,
+            failed_mods: Vec::new(),
+            error: Some("Network unreachable".to_string()),
+            updated_at: Utc::now(),
+        })?;
+
+        let job = jobs.start(None, JobKind::ModpackInstall)?;
+        let first = service
+            .resume_install(
+                &db,
+                &mod_client,
+                &download_service,
+                &mc_service,
+                &loader_service,
+                &job,
+                &instance_id,
+                false,
+            )
+            .await;
+        assert!(first.is_err(), "Failed optional files stop the install");
+        let checkpoint = db
+            .get_install_checkpoint(&instance_id)?
+            .ok_or_else(|| AppError::Custom("checkpoint dropped".to_string()))?;
+        assert_eq!(checkpoint.stage, CheckpointStage::LoaderReady);
+        assert!(db.get_instance(&instance_id)?.is_none());
+        // Downloaded files stay for the next attempt
+        assert!(root.join("instance/mods/required.jar").exists());
+
+        let job = jobs.start(None, JobKind::ModpackInstall)?;
+        let instance = service
+            .resume_install(
+                &db,
+                &mod_client,
+                &download_service,
+                &mc_service,
+                &loader_service,
+                &job,
+                &instance_id,
+                true,
+            )
+            .await?;
+        assert!(db.get_install_checkpoint(&instance_id)?.is_none());
+        Ok((db, instance, checkpoint.failed_mods))
+    }
+
     #[test]
     fn extract_zip_skips_absolute_paths() -> AppResult<()> {
         let root = temp_path("zip_slip");
@@ -766,38 +1519,12 @@ mod tests {
         let instance_path = root.join("instance");
         std::fs::create_dir_all(instance_path.join("mods"))?;
 
-        let now = Utc::now();
-        let instance = MinecraftInstance {
-            id: uuid::Uuid::new_v4().to_string(),
-            name: "Test".to_string(),
-            minecraft_version: "1.20.1".to_string(),
-            loader: ModLoader::Fabric,
-            loader_version: Some("0.15.0".to_string()),
-            instance_path: instance_path.to_string_lossy().to_string(),
-            icon_path: None,
-            icon_url: None,
-            description: None,
-            last_played_at: None,
-            total_play_time: 0,
-            is_active: true,
-            created_at: now,
-            updated_at: now,
-        };
+        let instance = test_instance(&instance_path);
         db.create_instance(&instance)?;
 
         let mod_info = ModInfo {
-            id: uuid::Uuid::new_v4().to_string(),
-            instance_id: instance.id.clone(),
             name: "Test Mod".to_string(),
-            slug: None,
-            version: "1.0.0".to_string(),
-            file_name: "test-mod.jar".to_string(),
-            file_hash: None,
-            source: ModSource::Local,
-            source_project_id: None,
-            source_version_id: None,
-            is_active: true,
-            installed_at: Utc::now(),
+            ..test_mod(&instance.id, "test-mod.jar")
         };
         db.add_mod_to_instance(&mod_info)?;
 
@@ -819,41 +1546,418 @@ mod tests {
     }
 
     #[test]
-    fn begin_install_blocks_concurrent_installations() -> AppResult<()> {
+    fn toggle_mod_renames_jar_and_keeps_row_active() -> AppResult<()> {
+        let root = temp_path("toggle_mod");
+        std::fs::create_dir_all(&root)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+
+        let instance_path = root.join("instance");
+        std::fs::create_dir_all(instance_path.join("mods"))?;
+
+        let instance = test_instance(&instance_path);
+        db.create_instance(&instance)?;
+
+        let mod_info = ModInfo {
+            name: "Test Mod".to_string(),
+            ..test_mod(&instance.id, "test-mod.jar")
+        };
+        db.add_mod_to_instance(&mod_info)?;
+
+        let jar = instance_path.join("mods").join("test-mod.jar");
+        let parked = instance_path.join("mods").join("test-mod.jar.disabled");
+        std::fs::write(&jar, b"jar-bytes")?;
+
         let service = InstallService::new();
+        let disabled = service.toggle_mod(&db, &mod_info.id, false)?;
+        assert!(!disabled.enabled);
+        assert!(!jar.exists() && parked.exists());
+
+        let mods = db.list_instance_mods(&instance.id)?;
+        assert_eq!(mods.len(), 1, "Disabled mod must still be listed");
+        assert!(!mods[0].enabled);
+
+        // A jar put back by hand is parked again before launch
+        std::fs::rename(&parked, &jar)?;
+        apply_mod_enabled_states(&db, &instance)?;
+        assert!(!jar.exists() && parked.exists());
+
+        service.toggle_mod(&db, &mod_info.id, true)?;
+        assert!(jar.exists() && !parked.exists());
+        assert!(db.list_instance_mods(&instance.id)?[0].enabled);
 
-        let guard = service.begin_install()?;
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+
+    #[test]
+    fn claim_instance_blocks_concurrent_installs_into_one_instance() -> AppResult<()> {
+        let service = InstallService::new();
+
+        let guard = service.claim_instance("instance-a")?;
+        assert!(
+            service.claim_instance("instance-a").is_err(),
+            "A second install into the same instance must be rejected"
+        );
         assert!(
-            service.begin_install().is_err(),
-            "A second install must be rejected while one is active"
+            service.claim_instance("instance-b").is_ok(),
+            "Installs into other instances run concurrently"
         );
 
         drop(guard);
 
         assert!(
-            service.begin_install().is_ok(),
+            service.claim_instance("instance-a").is_ok(),
             "A new install should be allowed after the previous one completes"
         );
         Ok(())
     }
 
-    #[test]
-    fn set_progress_fresh_clears_stale_metadata() -> AppResult<()> {
+    #[tokio::test]
+    async fn installs_fail_their_job_when_they_cannot_start() -> AppResult<()> {
+        let root = temp_path("resume_fail");
+        std::fs::create_dir_all(&root)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let endpoints = Endpoints::all_at("http://127.0.0.1:9");
+        let cache = HttpCache::new(root.join("http"));
+        let mod_client = UnifiedModClient::new(None, &endpoints, cache.clone());
+        let mc_service = MinecraftService::new(root.join("mc"), &endpoints, cache);
+        let loader_service = LoaderService::new(root.join("mc"), &endpoints);
+        let download_service = DownloadService::new();
         let service = InstallService::new();
-        {
-            let mut progress = service.lock_progress()?;
-            progress.instance_id = Some("instance-123".to_string());
-            progress.modpack_name = Some("Old Pack".to_string());
-            progress.modpack_icon_url = Some("https://example.com/old.png".to_string());
+        let jobs = JobRegistry::new();
+
+        let busy = service.claim_instance("busy")?;
+        for instance_id in ["busy", "no-checkpoint"] {
+            let job = jobs.start(None, JobKind::ModpackInstall)?;
+            let result = service
+                .resume_install(
+                    &db,
+                    &mod_client,
+                    &download_service,
+                    &mc_service,
+                    &loader_service,
+                    &job,
+                    instance_id,
+                    false,
+                )
+                .await;
+            assert!(result.is_err(), "{instance_id}");
+            let info = jobs.get(job.id())?.expect("job kept");
+            assert!(
+                matches!(info.progress.stage, InstallStage::Failed { .. }),
+                "{instance_id}: {:?}",
+                info.progress.stage
+            );
         }
 
-        service.set_progress_fresh(InstallStage::FetchingInfo, 10.0)?;
-        let progress = service.get_progress()?;
+        let job = jobs.start(None, JobKind::ModInstall)?;
+        let result = service
+            .install_mod(
+                &db,
+                &mod_client,
+                &download_service,
+                &job,
+                "busy",
+                &ModSource::Modrinth,
+                "AANobbMI",
+                "v1",
+                true,
+            )
+            .await;
+        assert!(result.is_err());
+        let info = jobs.get(job.id())?.expect("job kept");
+        assert!(matches!(info.progress.stage, InstallStage::Failed { .. }));
+        drop(busy);
+
+        let _ = std::fs::remove_dir_all(&root);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn discard_install_removes_partial_files_and_checkpoint() -> AppResult<()> {
+        let root = temp_path("discard");
+        std::fs::create_dir_all(&root)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let service = InstallService::new();
+
+        let instance_path = root.join("instances").join("partial");
+        std::fs::create_dir_all(instance_path.join("mods"))?;
+        std::fs::write(instance_path.join("mods").join("sodium.jar"), b"jar")?;
+        let archive = modpack_cache_dir(&root).join("v1-pack.mrpack");
+        std::fs::create_dir_all(modpack_cache_dir(&root))?;
+        std::fs::write(&archive, b"zip")?;
+
+        let failed = FailedModDownload {
+            name: "iris.jar".to_string(),
+            path: Some("mods/iris.jar".to_string()),
+            url: Some("https://cdn.modrinth.com/iris.jar".to_string()),
+            required: false,
+            reason: "HTTP 404".to_string(),
+        };
+        db.save_install_checkpoint(&InstallCheckpoint {
+            instance_id: "partial".to_string(),
+            instance_path: instance_path.to_string_lossy().to_string(),
+            archive_path: archive.to_string_lossy().to_string(),
+            modpack_name: Some("Pack".to_string()),
+            modpack_icon_url: None,
+            modpack_description: None,
+            stage: CheckpointStage::LoaderReady,
+            failed_mods: vec![failed.clone()],
+            error: Some("1 pack files could not be downloaded".to_string()),
+            updated_at: Utc::now(),
+        })?;
+
+        let saved = db
+            .get_install_checkpoint("partial")?
+            .ok_or_else(|| AppError::Custom("checkpoint not saved".to_string()))?;
+        assert_eq!(saved.stage, CheckpointStage::LoaderReady);
+        assert!(saved.stage < CheckpointStage::ModsReady);
+        assert_eq!(saved.failed_mods, vec![failed]);
+
+        service.discard_install(&db, &root, "partial").await?;
+
+        assert!(!instance_path.exists());
+        assert!(!archive.exists());
+        assert!(db.list_install_checkpoints()?.is_empty());
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+
+    /// Registered file names of an installed pack, and whether its override
+    /// was copied.
+    fn installed_files(
+        db: &DatabaseService,
+        instance: &MinecraftInstance,
+    ) -> AppResult<Vec<String>> {
+        assert!(Path::new(&instance.instance_path)
+            .join("config/pack.txt")
+            .exists());
+        let mods = db.list_instance_mods(&instance.id)?;
+        Ok(mods.into_iter().map(|m| m.file_name).collect())
+    }
+
+    #[tokio::test]
+    async fn mrpack_resume_skips_client_optional_files_on_request() -> AppResult<()> {
+        let root = temp_path("resume_mrpack");
+        std::fs::create_dir_all(&root)?;
+        let server = pack_server(serde_json::json!([]));
+        let file = |name: &str, env: Option<&str>| {
+            let mut file = serde_json::json!({
+                "path": format!("mods/{name}"),
+                "hashes": { "sha1": format!("{:x}", Sha1::digest(b"required")) },
+                "downloads": [format!("{}/files/{name}", server.base)],
+                "fileSize": 8,
+            });
+            if let Some(client) = env {
+                file["env"] = serde_json::json!({ "client": client, "server": "optional" });
+            }
+            file
+        };
+        let index = serde_json::json!({
+            "formatVersion": 1,
+            "game": "minecraft",
+            "name": "Pack",
+            "versionId": "1.0.0",
+            "dependencies": { "minecraft": "1.20.1", "fabric-loader": "0.15.0" },
+            "files": [
+                file("required.jar", None),
+                file("optional.jar", Some("optional")),
+            ],
+        });
+        let archive = root.join("pack.mrpack");
+        write_zip(
+            &archive,
+            &[
+                ("modrinth.index.json", index.to_string().as_bytes()),
+                ("overrides/config/pack.txt", b"pack"),
+            ],
+        )?;
+
+        let (db, instance, failed) = resume_skipping_optional(&root, &server, &archive).await?;
+
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].path.as_deref(), Some("mods/optional.jar"));
+        assert!(!failed[0].required, "env.client optional maps to optional");
+        assert_eq!(installed_files(&db, &instance)?, vec!["required.jar"]);
+
+        let _ = std::fs::remove_dir_all(&root);
+        Ok(())
+    }
 
-        assert_eq!(progress.overall_percent, 10.0);
-        assert!(progress.instance_id.is_none());
-        assert!(progress.modpack_name.is_none());
-        assert!(progress.modpack_icon_url.is_none());
+    #[tokio::test]
+    async fn curseforge_resume_skips_optional_files_on_request() -> AppResult<()> {
+        let root = temp_path("resume_curseforge");
+        std::fs::create_dir_all(&root)?;
+        let cf_file = |id: u32, name: &str| {
+            serde_json::json!({
+                "id": id,
+                "displayName": name,
+                "fileName": name,
+                "fileLength": 8,
+                // Served from the CDN endpoint
+                "downloadUrl": null,
+                "gameVersions": ["1.20.1"],
+                "hashes": [{ "value": format!("{:x}", Sha1::digest(b"required")), "algo": 1 }],
+                "fileDate": "2024-08-01T00:00:00Z",
+            })
+        };
+        // File 1003 is no longer served by CurseForge
+        let files =
+            serde_json::json!([cf_file(1001, "required.jar"), cf_file(1002, "optional.jar")]);
+        let server = pack_server(files);
+        let manifest = serde_json::json!({
+            "minecraft": {
+                "version": "1.20.1",
+                "modLoaders": [{ "id": "fabric-0.15.0", "primary": true }],
+            },
+            "manifestType": "minecraftModpack",
+            "manifestVersion": 1,
+            "name": "Pack",
+            "version": "1.0.0",
+            "author": "Tester",
+            "files": [
+                { "projectID": 1, "fileID": 1001, "required": true },
+                { "projectID": 2, "fileID": 1002, "required": false },
+                { "projectID": 3, "fileID": 1003, "required": false },
+            ],
+            "overrides": "overrides",
+        });
+        let archive = root.join("pack.zip");
+        write_zip(
+            &archive,
+            &[
+                ("manifest.json", manifest.to_string().as_bytes()),
+                ("overrides/config/pack.txt", b"pack"),
+            ],
+        )?;
+
+        let (db, instance, failed) = resume_skipping_optional(&root, &server, &archive).await?;
+
+        let mut failed: Vec<_> = failed
+            .iter()
+            .map(|f| (f.path.clone(), f.required))
+            .collect();
+        failed.sort();
+        assert_eq!(
+            failed,
+            vec![
+                (None, false),
+                (Some("mods/optional.jar".to_string()), false)
+            ]
+        );
+        assert_eq!(installed_files(&db, &instance)?, vec!["required.jar"]);
+
+        let _ = std::fs::remove_dir_all(&root);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn cancelled_copy_stops_before_the_next_file() -> AppResult<()> {
+        let root = temp_path("copy_cancel");
+        let src = root.join("overrides");
+        std::fs::create_dir_all(src.join("config"))?;
+        std::fs::write(src.join("config").join("a.toml"), b"a")?;
+        std::fs::write(src.join("options.txt"), b"b")?;
+
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let result = copy_dir_cancellable(&src, &root.join("instance"), cancel).await;
+        assert!(matches!(result, Err(AppError::Cancelled)));
+        assert!(!root.join("instance").join("options.txt").exists());
+
+        copy_dir_recursive(&src, &root.join("instance")).await?;
+        assert!(root.join("instance").join("config").join("a.toml").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+        Ok(())
+    }
+
+    /// Install Sodium, which requires an already installed but disabled
+    /// Fabric API, with the jar download answering `jar_response`.
+    async fn install_with_disabled_dependency(
+        jar_response: Vec<u8>,
+    ) -> AppResult<(AppResult<ModInstallResult>, ModInfo)> {
+        let root = temp_path("install_dep");
+        std::fs::create_dir_all(root.join("instance").join("mods"))?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let instance = test_instance(&root.join("instance"));
+        db.create_instance(&instance)?;
+        let api = ModInfo {
+            source: ModSource::Modrinth,
+            source_project_id: Some("P7dR8mSH".to_string()),
+            enabled: false,
+            ..test_mod(&instance.id, "fabric-api.jar")
+        };
+        db.add_mod_to_instance(&api)?;
+
+        let files = stub_server(usize::MAX, move |_| Some(jar_response.clone()));
+        let versions = serde_json::json!([{
+            "id": "v1",
+            "project_id": "AANobbMI",
+            "name": "Sodium",
+            "version_number": "0.5.8",
+            "game_versions": ["1.20.1"],
+            "loaders": ["fabric"],
+            "files": [{
+                "url": format!("{}/sodium.jar", files.base),
+                "filename": "sodium.jar",
+                "size": 6,
+                "hashes": {},
+                "primary": true,
+            }],
+            "dependencies": [{
+                "project_id": "P7dR8mSH",
+                "version_id": null,
+                "file_name": null,
+                "dependency_type": "required",
+            }],
+            "date_published": "2024-01-01T00:00:00Z",
+            "version_type": "release",
+        }])
+        .to_string();
+        let api_server = stub_server(usize::MAX, move |_| {
+            Some(http_response("200 OK", versions.as_bytes()))
+        });
+
+        let endpoints = Endpoints::all_at(&api_server.base);
+        let mod_client = UnifiedModClient::new(None, &endpoints, HttpCache::new(root.join("http")));
+        let jobs = JobRegistry::new();
+        let job = jobs.start(None, JobKind::ModInstall)?;
+        let result = InstallService::new()
+            .install_mod_files(
+                &db,
+                &mod_client,
+                &DownloadService::new(),
+                &job,
+                &instance.id,
+                &ModSource::Modrinth,
+                "AANobbMI",
+                "v1",
+                true,
+            )
+            .await;
+
+        let api = db.get_mod_by_id(&api.id)?.expect("dependency row kept");
+        let _ = std::fs::remove_dir_all(&root);
+        Ok((result, api))
+    }
+
+    #[tokio::test]
+    async fn disabled_dependencies_are_enabled_once_the_mod_is_installed() -> AppResult<()> {
+        let (result, api) =
+            install_with_disabled_dependency(http_response("200 OK", b"sodium")).await?;
+        let result = result?;
+        assert_eq!(result.installed.file_name, "sodium.jar");
+        assert!(result.dependencies.is_empty());
+        assert!(api.enabled);
+
+        let (result, api) =
+            install_with_disabled_dependency(http_response("404 Not Found", b"")).await?;
+        assert!(result.is_err());
+        assert!(
+            !api.enabled,
+            "A failed install leaves the dependency disabled"
+        );
         Ok(())
     }
 }
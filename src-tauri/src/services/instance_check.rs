@@ -0,0 +1,426 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::errors::{AppError, AppResult};
+use crate::models::instance::MinecraftInstance;
+use crate::models::instance_check::{
+    DuplicateMod, InstanceCheckReport, LoaderMismatch, MissingDependency, ModIncompatibility,
+    VersionRangeViolation,
+};
+use crate::models::mod_info::ModInfo;
+use crate::models::mod_platform::{DependencyType, ModDependency};
+use crate::services::database::DatabaseService;
+use crate::services::jar_metadata::formats_read_by;
+use crate::services::version_range::satisfies;
+
+/// Dependency ids provided by the game or the loader itself.
+const BUILTIN_IDS: [&str; 6] = [
+    "minecraft",
+    "java",
+    "fabricloader",
+    "quilt_loader",
+    "forge",
+    "neoforge",
+];
+
+/// Check an instance's enabled mods without touching the network.
+///
+/// Mods with embedded metadata are checked from their descriptor; mods
+/// without one fall back to the platform dependencies cached at install or
+/// rescan time.
+pub fn check_instance(db: &DatabaseService, instance_id: &str) -> AppResult<InstanceCheckReport> {
+    let instance = db
+        .get_instance(instance_id)?
+        .ok_or_else(|| AppError::Custom(format!("Instance not found: {instance_id}")))?;
+
+    let mods: Vec<ModInfo> = db
+        .list_instance_mods(instance_id)?
+        .into_iter()
+        .filter(|m| m.enabled && !m.file_missing)
+        .collect();
+    let platform_deps = db.list_platform_dependencies(instance_id)?;
+
+    Ok(check_mods(&instance, &mods, &platform_deps))
+}
+
+/// A mod satisfying a dependency id, with its version when known.
+struct Provider<'a> {
+    row: &'a ModInfo,
+    version: Option<&'a str>,
+}
+
+fn check_mods(
+    instance: &MinecraftInstance,
+    mods: &[ModInfo],
+    platform_deps: &HashMap<String, Vec<ModDependency>>,
+) -> InstanceCheckReport {
+    let mut report = InstanceCheckReport {
+        instance_id: instance.id.clone(),
+        ..Default::default()
+    };
+
+    let mut providers: HashMap<&str, Vec<Provider>> = HashMap::new();
+    for m in mods {
+        let Some(meta) = &m.metadata else { continue };
+        providers.entry(&meta.mod_id).or_default().push(Provider {
+            row: m,
+            version: Some(&meta.version),
+        });
+        for id in &meta.provides {
+            providers.entry(id).or_default().push(Provider {
+                row: m,
+                version: None,
+            });
+        }
+    }
+
+    check_duplicates(mods, &mut report);
+
+    let accepted_formats = formats_read_by(&instance.loader);
+    let mut seen_pairs = HashSet::new();
+
+    for m in mods {
+        let Some(meta) = &m.metadata else {
+            check_platform_dependencies(m, mods, platform_deps, &mut seen_pairs, &mut report);
+            continue;
+        };
+
+        if !accepted_formats.contains(&meta.format) {
+            report.loader_mismatches.push(LoaderMismatch {
+                mod_row_id: m.id.clone(),
+                mod_name: m.name.clone(),
+                format: meta.format.clone(),
+                instance_loader: instance.loader.clone(),
+            });
+            // Nothing else about this mod matters if it never loads
+            continue;
+        }
+
+        if let Some(range) = &meta.minecraft_range {
+            if satisfies(range, &instance.minecraft_version, &meta.format) == Some(false) {
+                report
+                    .minecraft_version_violations
+                    .push(VersionRangeViolation {
+                        mod_row_id: m.id.clone(),
+                        mod_name: m.name.clone(),
+                        declared_range: range.clone(),
+                        actual_version: instance.minecraft_version.clone(),
+                    });
+            }
+        }
+
+        // A Fabric mod's loader range targets fabricloader, not Quilt's version
+        let native_format = accepted_formats.first() == Some(&meta.format);
+        if let (Some(range), Some(loader_version), true) =
+            (&meta.loader_range, &instance.loader_version, native_format)
+        {
+            if satisfies(range, loader_version, &meta.format) == Some(false) {
+                report
+                    .loader_version_violations
+                    .push(VersionRangeViolation {
+                        mod_row_id: m.id.clone(),
+                        mod_name: m.name.clone(),
+                        declared_range: range.clone(),
+                        actual_version: loader_version.clone(),
+                    });
+            }
+        }
+
+        for dep in &meta.dependencies {
+            if BUILTIN_IDS.contains(&dep.mod_id.as_str()) {
+                continue;
+            }
+            let found = providers.get(dep.mod_id.as_str());
+            let in_range = |p: &&Provider| match (&dep.version_range, p.version) {
+                (Some(range), Some(version)) => {
+                    satisfies(range, version, &meta.format) != Some(false)
+                }
+                _ => true,
+            };
+
+            match dep.dependency_type {
+                DependencyType::Required => {
+                    let candidates = found.map(Vec::as_slice).unwrap_or_default();
+                    if candidates.iter().any(|p| in_range(&p)) {
+                        continue;
+                    }
+                    report.missing_dependencies.push(MissingDependency {
+                        mod_row_id: m.id.clone(),
+                        mod_name: m.name.clone(),
+                        dependency_id: dep.mod_id.clone(),
+                        version_range: dep.version_range.clone(),
+                        found_version: candidates
+                            .first()
+                            .and_then(|p| p.version.map(str::to_string)),
+                        unverified: false,
+                    });
+                }
+                DependencyType::Incompatible => {
+                    for p in found.into_iter().flatten().filter(|p| in_range(p)) {
+                        push_incompatibility(m, p.row, &mut seen_pairs, &mut report);
+                    }
+                }
+                DependencyType::Optional | DependencyType::Embedded => {}
+            }
+        }
+    }
+
+    report
+}
+
+/// Fallback for mods without a descriptor, matched on platform project ids,
+/// or on slugs and mod ids for projects referenced by those. A dependency
+/// that could be one of the instance's unidentified or other-platform jars
+/// is reported as unverified rather than missing.
+fn check_platform_dependencies(
+    m: &ModInfo,
+    mods: &[ModInfo],
+    platform_deps: &HashMap<String, Vec<ModDependency>>,
+    seen_pairs: &mut HashSet<(String, String)>,
+    report: &mut InstanceCheckReport,
+) {
+    for dep in platform_deps.get(&m.id).into_iter().flatten() {
+        let id = dep.project_id.as_str();
+        let present = mods.iter().find(|other| {
+            other.source_project_id.as_deref() == Some(id)
+                || other.slug.as_deref() == Some(id)
+                || other
+                    .metadata
+                    .as_ref()
+                    .is_some_and(|meta| meta.mod_id == id || meta.provides.iter().any(|p| p == id))
+        });
+
+        match (&dep.dependency_type, present) {
+            (DependencyType::Required, None) => {
+                let unverified = mods.iter().any(|other| {
+                    other.id != m.id
+                        && (other.source != m.source || other.source_project_id.is_none())
+                });
+                report.missing_dependencies.push(MissingDependency {
+                    mod_row_id: m.id.clone(),
+                    mod_name: m.name.clone(),
+                    dependency_id: dep.project_id.clone(),
+                    version_range: None,
+                    found_version: None,
+                    unverified,
+                });
+            }
+            (DependencyType::Incompatible, Some(other)) => {
+                push_incompatibility(m, other, seen_pairs, report);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_incompatibility(
+    m: &ModInfo,
+    other: &ModInfo,
+    seen_pairs: &mut HashSet<(String, String)>,
+    report: &mut InstanceCheckReport,
+) {
+    if m.id == other.id {
+        return;
+    }
+    // Both sides may declare the same break
+    let pair = if m.id < other.id {
+        (m.id.clone(), other.id.clone())
+    } else {
+        (other.id.clone(), m.id.clone())
+    };
+    if seen_pairs.insert(pair) {
+        report.incompatibilities.push(ModIncompatibility {
+            mod_row_id: m.id.clone(),
+            mod_name: m.name.clone(),
+            other_mod_row_id: other.id.clone(),
+            other_mod_name: other.name.clone(),
+        });
+    }
+}
+
+/// Group by embedded mod id, or by platform project for jars without one.
+fn check_duplicates(mods: &[ModInfo], report: &mut InstanceCheckReport) {
+    let mut groups: HashMap<String, Vec<&ModInfo>> = HashMap::new();
+    for m in mods {
+        let key = match (&m.metadata, &m.source_project_id) {
+            (Some(meta), _) => meta.mod_id.clone(),
+            (None, Some(project_id)) => format!("{}:{project_id}", m.source),
+            (None, None) => continue,
+        };
+        groups.entry(key).or_default().push(m);
+    }
+
+    let mut duplicates: Vec<DuplicateMod> = groups
+        .into_iter()
+        .filter(|(_, rows)| rows.len() > 1)
+        .map(|(mod_id, rows)| DuplicateMod {
+            mod_id,
+            mod_row_ids: rows.iter().map(|m| m.id.clone()).collect(),
+            file_names: rows.iter().map(|m| m.file_name.clone()).collect(),
+        })
+        .collect();
+    duplicates.sort_by(|a, b| a.mod_id.cmp(&b.mod_id));
+    report.duplicates = duplicates;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    use crate::models::mod_info::{
+        DeclaredDependency, JarMetadata, MetadataFormat, ModEnvironment, ModSource,
+    };
+    use crate::test_support::{test_instance, test_mod};
+
+    fn instance() -> MinecraftInstance {
+        MinecraftInstance {
+            id: "inst".to_string(),
+            ..test_instance(Path::new(""))
+        }
+    }
+
+    fn dep(mod_id: &str, range: &str, dependency_type: DependencyType) -> DeclaredDependency {
+        DeclaredDependency {
+            mod_id: mod_id.to_string(),
+            version_range: Some(range.to_string()),
+            dependency_type,
+        }
+    }
+
+    fn jar(id: &str, version: &str, format: MetadataFormat) -> ModInfo {
+        ModInfo {
+            id: format!("row-{id}-{version}"),
+            name: id.to_string(),
+            version: version.to_string(),
+            metadata: Some(JarMetadata {
+                format,
+                mod_id: id.to_string(),
+                name: id.to_string(),
+                version: version.to_string(),
+                authors: Vec::new(),
+                minecraft_range: None,
+                loader_range: None,
+                dependencies: Vec::new(),
+                provides: Vec::new(),
+                environment: ModEnvironment::Both,
+            }),
+            ..test_mod("inst", &format!("{id}-{version}.jar"))
+        }
+    }
+
+    fn with_meta(mut m: ModInfo, edit: impl FnOnce(&mut JarMetadata)) -> ModInfo {
+        if let Some(meta) = m.metadata.as_mut() {
+            edit(meta);
+        }
+        m
+    }
+
+    #[test]
+    fn clean_instance_has_no_issues() {
+        let api = with_meta(jar("fabric-api", "0.92.0", MetadataFormat::Fabric), |m| {
+            m.provides = vec!["fabric-api-base".to_string()];
+        });
+        let sodium = with_meta(jar("sodium", "0.5.8", MetadataFormat::Fabric), |m| {
+            m.minecraft_range = Some("~1.20".to_string());
+            m.loader_range = Some(">=0.12".to_string());
+            m.dependencies = vec![
+                dep("fabric-api-base", "*", DependencyType::Required),
+                dep("fabricloader", ">=0.12", DependencyType::Required),
+            ];
+        });
+
+        let report = check_mods(&instance(), &[api, sodium], &HashMap::new());
+        assert!(report.is_clean(), "{:?}", report.summary());
+    }
+
+    #[test]
+    fn reports_each_problem_kind() {
+        let needs_api = with_meta(jar("iris", "1.6", MetadataFormat::Fabric), |m| {
+            m.minecraft_range = Some("1.19.x".to_string());
+            m.dependencies = vec![
+                dep("fabric-api", "*", DependencyType::Required),
+                dep("sodium", ">=0.5", DependencyType::Required),
+                dep("optifabric", "*", DependencyType::Incompatible),
+            ];
+        });
+        let old_sodium = jar("sodium", "0.4.10", MetadataFormat::Fabric);
+        let optifabric = with_meta(jar("optifabric", "1.0", MetadataFormat::Fabric), |m| {
+            m.dependencies = vec![dep("iris", "*", DependencyType::Incompatible)];
+        });
+        let forge_mod = jar("jei", "15.2", MetadataFormat::Forge);
+        let dupe_a = jar("lithium", "0.11", MetadataFormat::Fabric);
+        let dupe_b = jar("lithium", "0.12", MetadataFormat::Fabric);
+
+        let mods = [needs_api, old_sodium, optifabric, forge_mod, dupe_a, dupe_b];
+        let report = check_mods(&instance(), &mods, &HashMap::new());
+
+        let missing: Vec<(&str, Option<&str>)> = report
+            .missing_dependencies
+            .iter()
+            .map(|d| (d.dependency_id.as_str(), d.found_version.as_deref()))
+            .collect();
+        assert_eq!(
+            missing,
+            vec![("fabric-api", None), ("sodium", Some("0.4.10"))]
+        );
+        assert_eq!(
+            report.incompatibilities.len(),
+            1,
+            "Mutual breaks count once"
+        );
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(report.duplicates[0].mod_id, "lithium");
+        assert_eq!(report.minecraft_version_violations.len(), 1);
+        assert_eq!(report.loader_mismatches.len(), 1);
+        assert_eq!(report.loader_mismatches[0].mod_name, "jei");
+        assert!(report.is_blocking());
+    }
+
+    #[test]
+    fn range_violations_alone_do_not_block() {
+        let m = with_meta(jar("old", "1.0", MetadataFormat::Fabric), |m| {
+            m.minecraft_range = Some("<1.20".to_string());
+        });
+        let report = check_mods(&instance(), &[m], &HashMap::new());
+        assert!(!report.is_clean());
+        assert!(!report.is_blocking());
+    }
+
+    #[test]
+    fn mods_without_metadata_use_cached_platform_dependencies() {
+        let mut addon = jar("addon", "1.0", MetadataFormat::Fabric);
+        addon.metadata = None;
+        addon.source = ModSource::Modrinth;
+        addon.source_project_id = Some("AANobbMI".to_string());
+
+        let platform_deps = HashMap::from([(
+            addon.id.clone(),
+            vec![ModDependency {
+                project_id: "P7dR8mSH".to_string(),
+                dependency_type: DependencyType::Required,
+                version_id: None,
+                file_name: None,
+            }],
+        )]);
+
+        let report = check_mods(&instance(), &[addon.clone()], &platform_deps);
+        assert_eq!(report.missing_dependencies.len(), 1);
+        assert_eq!(report.missing_dependencies[0].dependency_id, "P7dR8mSH");
+        assert!(report.is_blocking());
+
+        // A hand-added jar might be the dependency under another identity
+        let handmade = test_mod("inst", "handmade.jar");
+        let report = check_mods(&instance(), &[addon.clone(), handmade], &platform_deps);
+        assert!(report.missing_dependencies[0].unverified);
+        assert!(!report.is_blocking(), "{:?}", report.summary());
+
+        let by_slug = ModInfo {
+            source: ModSource::Modrinth,
+            slug: Some("P7dR8mSH".to_string()),
+            source_project_id: Some("other".to_string()),
+            ..test_mod("inst", "dep.jar")
+        };
+        let report = check_mods(&instance(), &[addon, by_slug], &platform_deps);
+        assert!(report.missing_dependencies.is_empty());
+    }
+}
@@ -0,0 +1,807 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::errors::{AppError, AppResult};
+use crate::models::instance::ModLoader;
+use crate::models::mod_info::{DeclaredDependency, JarMetadata, MetadataFormat, ModEnvironment};
+use crate::models::mod_platform::DependencyType;
+
+const FABRIC_DESCRIPTOR: &str = "fabric.mod.json";
+const QUILT_DESCRIPTOR: &str = "quilt.mod.json";
+const FORGE_DESCRIPTOR: &str = "META-INF/mods.toml";
+const NEOFORGE_DESCRIPTOR: &str = "META-INF/neoforge.mods.toml";
+
+/// Where Fabric/Quilt (`jars`) and Forge/NeoForge (Jar-in-Jar) keep bundled mods.
+const NESTED_JAR_DIRS: [&str; 2] = ["META-INF/jars/", "META-INF/jarjar/"];
+
+/// Dependency ids that describe the platform rather than another mod.
+const JAVA_DEPENDENCY: &str = "java";
+
+// --- fabric.mod.json ---
+
+#[derive(Deserialize)]
+struct FabricModJson {
+    id: String,
+    version: String,
+    name: Option<String>,
+    #[serde(default)]
+    authors: Vec<FabricPerson>,
+    #[serde(default)]
+    depends: HashMap<String, VersionPredicate>,
+    #[serde(default)]
+    recommends: HashMap<String, VersionPredicate>,
+    #[serde(default)]
+    suggests: HashMap<String, VersionPredicate>,
+    #[serde(default)]
+    breaks: HashMap<String, VersionPredicate>,
+    #[serde(default)]
+    provides: Vec<String>,
+    environment: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FabricPerson {
+    Name(String),
+    Detailed { name: String },
+}
+
+/// A single predicate, or a list where any may match.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VersionPredicate {
+    One(String),
+    Any(Vec<String>),
+}
+
+impl VersionPredicate {
+    fn into_range(self) -> String {
+        match self {
+            Self::One(p) => p,
+            Self::Any(ps) => ps.join(" || "),
+        }
+    }
+}
+
+// --- quilt.mod.json ---
+
+#[derive(Deserialize)]
+struct QuiltModJson {
+    quilt_loader: QuiltLoaderSection,
+    minecraft: Option<QuiltMinecraftSection>,
+}
+
+#[derive(Deserialize)]
+struct QuiltLoaderSection {
+    id: String,
+    version: String,
+    metadata: Option<QuiltMetadata>,
+    #[serde(default)]
+    depends: Vec<QuiltDependency>,
+    #[serde(default)]
+    breaks: Vec<QuiltDependency>,
+    /// Same shape as a dependency: an id or `{ id, version }`.
+    #[serde(default)]
+    provides: Vec<QuiltDependency>,
+}
+
+#[derive(Deserialize)]
+struct QuiltMetadata {
+    name: Option<String>,
+    /// Contributor name -> role.
+    #[serde(default)]
+    contributors: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct QuiltMinecraftSection {
+    environment: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuiltDependency {
+    Id(String),
+    Detailed {
+        id: String,
+        versions: Option<VersionPredicate>,
+        #[serde(default)]
+        optional: bool,
+    },
+}
+
+// --- META-INF/mods.toml and META-INF/neoforge.mods.toml ---
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForgeModsToml {
+    mods: Vec<ForgeModEntry>,
+    /// Mod id -> dependencies declared by that mod.
+    #[serde(default)]
+    dependencies: HashMap<String, Vec<ForgeDependency>>,
+    #[serde(default)]
+    client_side_only: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForgeModEntry {
+    mod_id: String,
+    version: Option<String>,
+    display_name: Option<String>,
+    authors: Option<ForgeAuthors>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ForgeAuthors {
+    Joined(String),
+    List(Vec<String>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForgeDependency {
+    mod_id: String,
+    /// Forge: `mandatory = true|false`.
+    mandatory: Option<bool>,
+    /// NeoForge: `type = "required"|"optional"|"incompatible"|"discouraged"`.
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    version_range: Option<String>,
+    side: Option<String>,
+}
+
+/// Read the mod metadata embedded in a jar.
+///
+/// Multi-loader jars ship several descriptors; when `preferred` is given the
+/// descriptor that loader would read wins, otherwise the first one found.
+///
+/// Returns `Ok(None)` for jars without a recognised descriptor (libraries,
+/// resource packs renamed to .jar, ...). A descriptor that exists but cannot
+/// be parsed is also treated as absent so one broken mod never aborts a scan.
+pub fn read_jar_metadata(
+    path: &Path,
+    preferred: Option<&ModLoader>,
+) -> AppResult<Option<JarMetadata>> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| AppError::Custom(format!("Failed to open jar {}: {e}", path.display())))?;
+
+    let mut found = parse_descriptors(&mut archive);
+    if found.is_empty() {
+        return Ok(None);
+    }
+
+    let preferred_index = preferred.and_then(|loader| {
+        formats_read_by(loader)
+            .iter()
+            .find_map(|format| found.iter().position(|m| m.format == *format))
+    });
+    let mut metadata = match preferred_index {
+        Some(i) => found.swap_remove(i),
+        None => found.swap_remove(0),
+    };
+
+    // Bundled mods satisfy dependencies just like the outer mod does
+    for nested in nested_metadata(&mut archive) {
+        metadata.provides.push(nested.mod_id);
+        metadata.provides.extend(nested.provides);
+    }
+    metadata.provides.sort();
+    metadata.provides.dedup();
+    metadata.provides.retain(|id| *id != metadata.mod_id);
+
+    Ok(Some(metadata))
+}
+
+/// Descriptor formats a loader reads, most specific first.
+pub fn formats_read_by(loader: &ModLoader) -> &'static [MetadataFormat] {
+    match loader {
+        ModLoader::Fabric => &[MetadataFormat::Fabric],
+        ModLoader::Quilt => &[MetadataFormat::Quilt, MetadataFormat::Fabric],
+        ModLoader::Forge => &[MetadataFormat::Forge],
+        ModLoader::NeoForge => &[MetadataFormat::NeoForge, MetadataFormat::Forge],
+        ModLoader::Vanilla => &[],
+    }
+}
+
+/// Parse every descriptor present in an archive.
+fn parse_descriptors<R: Read + Seek>(archive: &mut zip::ZipArchive<R>) -> Vec<JarMetadata> {
+    let mut found = Vec::new();
+    if let Some(data) = read_entry(archive, QUILT_DESCRIPTOR) {
+        found.extend(parse_quilt(&data));
+    }
+    if let Some(data) = read_entry(archive, FABRIC_DESCRIPTOR) {
+        found.extend(parse_fabric(&data));
+    }
+    if let Some(data) = read_entry(archive, NEOFORGE_DESCRIPTOR) {
+        found.extend(parse_mods_toml(&data, MetadataFormat::NeoForge, archive));
+    }
+    if let Some(data) = read_entry(archive, FORGE_DESCRIPTOR) {
+        found.extend(parse_mods_toml(&data, MetadataFormat::Forge, archive));
+    }
+
+    found
+}
+
+/// Metadata of the jars bundled one level deep inside an archive.
+fn nested_metadata<R: Read + Seek>(archive: &mut zip::ZipArchive<R>) -> Vec<JarMetadata> {
+    let nested_names: Vec<String> = archive
+        .file_names()
+        .filter(|name| {
+            name.ends_with(".jar") && NESTED_JAR_DIRS.iter().any(|dir| name.starts_with(dir))
+        })
+        .map(str::to_string)
+        .collect();
+
+    let mut found = Vec::new();
+    for name in nested_names {
+        let mut data = Vec::new();
+        let Ok(mut entry) = archive.by_name(&name) else {
+            continue;
+        };
+        if entry.read_to_end(&mut data).is_err() {
+            continue;
+        }
+        drop(entry);
+        if let Ok(mut nested) = zip::ZipArchive::new(Cursor::new(data)) {
+            found.extend(parse_descriptors(&mut nested));
+        }
+    }
+    found
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut zip::ZipArchive<R>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut data = String::new();
+    entry.read_to_string(&mut data).ok()?;
+    Some(data)
+}
+
+// --- Parsers ---
+
+fn parse_fabric(data: &str) -> Option<JarMetadata> {
+    let json: FabricModJson = serde_json::from_str(data).ok()?;
+
+    let mut minecraft_range = None;
+    let mut loader_range = None;
+    let mut dependencies = Vec::new();
+
+    for (id, predicate) in json.depends {
+        match id.as_str() {
+            "minecraft" => minecraft_range = Some(predicate.into_range()),
+            "fabricloader" => loader_range = Some(predicate.into_range()),
+            JAVA_DEPENDENCY => {}
+            _ => dependencies.push(declared(id, predicate, DependencyType::Required)),
+        }
+    }
+    for (id, predicate) in json.recommends.into_iter().chain(json.suggests) {
+        dependencies.push(declared(id, predicate, DependencyType::Optional));
+    }
+    for (id, predicate) in json.breaks {
+        dependencies.push(declared(id, predicate, DependencyType::Incompatible));
+    }
+    dependencies.sort_by(|a, b| a.mod_id.cmp(&b.mod_id));
+
+    let authors = json
+        .authors
+        .into_iter()
+        .map(|a| match a {
+            FabricPerson::Name(name) | FabricPerson::Detailed { name } => name,
+        })
+        .collect();
+
+    Some(JarMetadata {
+        format: MetadataFormat::Fabric,
+        name: json.name.unwrap_or_else(|| json.id.clone()),
+        mod_id: json.id,
+        version: json.version,
+        authors,
+        minecraft_range,
+        loader_range,
+        dependencies,
+        provides: json.provides,
+        environment: parse_environment(json.environment.as_deref()),
+    })
+}
+
+fn parse_quilt(data: &str) -> Option<JarMetadata> {
+    let json: QuiltModJson = serde_json::from_str(data).ok()?;
+    let loader = json.quilt_loader;
+
+    let mut minecraft_range = None;
+    let mut loader_range = None;
+    let mut dependencies = Vec::new();
+
+    for dep in loader.depends {
+        let (id, versions, optional) = match dep {
+            QuiltDependency::Id(id) => (id, None, false),
+            QuiltDependency::Detailed {
+                id,
+                versions,
+                optional,
+            } => (id, versions.map(VersionPredicate::into_range), optional),
+        };
+        match id.as_str() {
+            "minecraft" => minecraft_range = versions,
+            "quilt_loader" => loader_range = versions,
+            JAVA_DEPENDENCY => {}
+            _ => dependencies.push(DeclaredDependency {
+                mod_id: id,
+                version_range: versions,
+                dependency_type: if optional {
+                    DependencyType::Optional
+                } else {
+                    DependencyType::Required
+                },
+            }),
+        }
+    }
+    for dep in loader.breaks {
+        let (id, versions) = match dep {
+            QuiltDependency::Id(id) => (id, None),
+            QuiltDependency::Detailed { id, versions, .. } => {
+                (id, versions.map(VersionPredicate::into_range))
+            }
+        };
+        dependencies.push(DeclaredDependency {
+            mod_id: id,
+            version_range: versions,
+            dependency_type: DependencyType::Incompatible,
+        });
+    }
+
+    let provides = loader
+        .provides
+        .into_iter()
+        .map(|p| match p {
+            QuiltDependency::Id(id) | QuiltDependency::Detailed { id, .. } => id,
+        })
+        .collect();
+
+    let (name, authors) = match loader.metadata {
+        Some(meta) => (meta.name, meta.contributors.into_keys().collect()),
+        None => (None, Vec::new()),
+    };
+
+    Some(JarMetadata {
+        format: MetadataFormat::Quilt,
+        name: name.unwrap_or_else(|| loader.id.clone()),
+        mod_id: loader.id,
+        version: loader.version,
+        authors,
+        minecraft_range,
+        loader_range,
+        dependencies,
+        provides,
+        environment: parse_environment(json.minecraft.and_then(|m| m.environment).as_deref()),
+    })
+}
+
+fn parse_mods_toml<R: Read + Seek>(
+    data: &str,
+    format: MetadataFormat,
+    archive: &mut zip::ZipArchive<R>,
+) -> Option<JarMetadata> {
+    let mut toml: ForgeModsToml = toml::from_str(data).ok()?;
+    // Multi-mod jars list their primary mod first; the rest ship alongside it
+    let mut mods = toml.mods.into_iter();
+    let entry = mods.next()?;
+    let provides = mods.map(|m| m.mod_id).collect();
+    let declared_deps = toml.dependencies.remove(&entry.mod_id).unwrap_or_default();
+
+    // NeoForge 1.20.1-1.20.4 still used mods.toml; its loader dependency gives it away
+    let format = if declared_deps.iter().any(|d| d.mod_id == "neoforge") {
+        MetadataFormat::NeoForge
+    } else {
+        format
+    };
+    let loader_id = match format {
+        MetadataFormat::NeoForge => "neoforge",
+        _ => "forge",
+    };
+
+    // Forge substitutes `${file.jarVersion}` with the manifest's Implementation-Version
+    let version = match entry.version {
+        Some(v) if v == "${file.jarVersion}" => read_entry(archive, "META-INF/MANIFEST.MF")
+            .and_then(|m| manifest_attribute(&m, "Implementation-Version"))
+            .unwrap_or_default(),
+        Some(v) => v,
+        None => String::new(),
+    };
+
+    let authors = match entry.authors {
+        Some(ForgeAuthors::Joined(joined)) => joined
+            .split(',')
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect(),
+        Some(ForgeAuthors::List(list)) => list,
+        None => Vec::new(),
+    };
+
+    let mut environment = if toml.client_side_only {
+        ModEnvironment::Client
+    } else {
+        ModEnvironment::Both
+    };
+    let mut minecraft_range = None;
+    let mut loader_range = None;
+    let mut dependencies = Vec::new();
+
+    for dep in declared_deps {
+        if dep.mod_id == "minecraft" {
+            if environment == ModEnvironment::Both {
+                environment = parse_environment(dep.side.as_deref());
+            }
+            minecraft_range = dep.version_range;
+            continue;
+        }
+        if dep.mod_id == loader_id {
+            loader_range = dep.version_range;
+            continue;
+        }
+
+        let dependency_type = match (dep.kind.as_deref(), dep.mandatory) {
+            (Some("required"), _) => DependencyType::Required,
+            (Some("optional"), _) => DependencyType::Optional,
+            (Some("incompatible"), _) => DependencyType::Incompatible,
+            // "discouraged" only warns in NeoForge; not worth surfacing
+            (Some(_), _) => continue,
+            (None, Some(false)) => DependencyType::Optional,
+            (None, _) => DependencyType::Required,
+        };
+        dependencies.push(DeclaredDependency {
+            mod_id: dep.mod_id,
+            version_range: dep.version_range,
+            dependency_type,
+        });
+    }
+
+    Some(JarMetadata {
+        format,
+        name: entry.display_name.unwrap_or_else(|| entry.mod_id.clone()),
+        mod_id: entry.mod_id,
+        version,
+        authors,
+        minecraft_range,
+        loader_range,
+        dependencies,
+        provides,
+        environment,
+    })
+}
+
+// --- Helpers ---
+
+fn declared(
+    mod_id: String,
+    predicate: VersionPredicate,
+    dependency_type: DependencyType,
+) -> DeclaredDependency {
+    DeclaredDependency {
+        mod_id,
+        version_range: Some(predicate.into_range()),
+        dependency_type,
+    }
+}
+
+/// Map Fabric/Quilt (`*`, `client`, `server`) and Forge (`BOTH`, `CLIENT`,
+/// `SERVER`) side names.
+fn parse_environment(raw: Option<&str>) -> ModEnvironment {
+    match raw.map(str::to_lowercase).as_deref() {
+        Some("client") => ModEnvironment::Client,
+        Some("server") => ModEnvironment::Server,
+        _ => ModEnvironment::Both,
+    }
+}
+
+fn manifest_attribute(manifest: &str, key: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        (k.trim() == key).then(|| v.trim().to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn jar_bytes<C: AsRef<[u8]>>(entries: &[(&str, C)]) -> AppResult<Vec<u8>> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in entries {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .map_err(|e| AppError::Custom(format!("zip start_file failed: {e}")))?;
+            zip.write_all(content.as_ref())?;
+        }
+        let cursor = zip
+            .finish()
+            .map_err(|e| AppError::Custom(format!("zip finish failed: {e}")))?;
+        Ok(cursor.into_inner())
+    }
+
+    fn write_jar<C: AsRef<[u8]>>(label: &str, entries: &[(&str, C)]) -> AppResult<PathBuf> {
+        let dir = std::env::temp_dir().join(format!(
+            "minesync_test_jar_{label}_{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{label}.jar"));
+        std::fs::write(&path, jar_bytes(entries)?)?;
+        Ok(path)
+    }
+
+    fn read(path: &Path, preferred: Option<&ModLoader>) -> AppResult<JarMetadata> {
+        let meta = read_jar_metadata(path, preferred)?;
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+        meta.ok_or_else(|| AppError::Custom("no metadata found".to_string()))
+    }
+
+    #[test]
+    fn reads_fabric_mod_json() -> AppResult<()> {
+        let jar = write_jar(
+            "fabric",
+            &[(
+                FABRIC_DESCRIPTOR,
+                r#"{
+                    "schemaVersion": 1,
+                    "id": "sodium",
+                    "version": "0.5.8",
+                    "name": "Sodium",
+                    "authors": ["JellySquid", {"name": "IMS", "contact": {}}],
+                    "environment": "client",
+                    "depends": {
+                        "minecraft": ["1.20", "1.20.1"],
+                        "fabricloader": ">=0.12.0",
+                        "java": ">=17",
+                        "fabric-api": "*"
+                    },
+                    "breaks": { "optifabric": "*" }
+                }"#,
+            )],
+        )?;
+
+        let meta = read(&jar, None)?;
+        assert_eq!(meta.format, MetadataFormat::Fabric);
+        assert_eq!(meta.mod_id, "sodium");
+        assert_eq!(meta.name, "Sodium");
+        assert_eq!(meta.version, "0.5.8");
+        assert_eq!(meta.authors, vec!["JellySquid", "IMS"]);
+        assert_eq!(meta.minecraft_range.as_deref(), Some("1.20 || 1.20.1"));
+        assert_eq!(meta.loader_range.as_deref(), Some(">=0.12.0"));
+        assert_eq!(meta.environment, ModEnvironment::Client);
+        assert_eq!(
+            meta.dependencies,
+            vec![
+                DeclaredDependency {
+                    mod_id: "fabric-api".to_string(),
+                    version_range: Some("*".to_string()),
+                    dependency_type: DependencyType::Required,
+                },
+                DeclaredDependency {
+                    mod_id: "optifabric".to_string(),
+                    version_range: Some("*".to_string()),
+                    dependency_type: DependencyType::Incompatible,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reads_quilt_mod_json() -> AppResult<()> {
+        let jar = write_jar(
+            "quilt",
+            &[(
+                QUILT_DESCRIPTOR,
+                r#"{
+                    "schema_version": 1,
+                    "quilt_loader": {
+                        "id": "qsl_demo",
+                        "version": "1.2.3",
+                        "metadata": { "name": "QSL Demo", "contributors": { "Alice": "Owner" } },
+                        "depends": [
+                            { "id": "minecraft", "versions": ">=1.20.1" },
+                            { "id": "quilt_loader", "versions": ">=0.19" },
+                            "qsl",
+                            { "id": "modmenu", "optional": true }
+                        ],
+                        "breaks": ["sodium"]
+                    },
+                    "minecraft": { "environment": "*" }
+                }"#,
+            )],
+        )?;
+
+        let meta = read(&jar, None)?;
+        assert_eq!(meta.format, MetadataFormat::Quilt);
+        assert_eq!(meta.name, "QSL Demo");
+        assert_eq!(meta.authors, vec!["Alice"]);
+        assert_eq!(meta.minecraft_range.as_deref(), Some(">=1.20.1"));
+        assert_eq!(meta.loader_range.as_deref(), Some(">=0.19"));
+        assert_eq!(meta.environment, ModEnvironment::Both);
+        let kinds: Vec<(&str, &DependencyType)> = meta
+            .dependencies
+            .iter()
+            .map(|d| (d.mod_id.as_str(), &d.dependency_type))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("qsl", &DependencyType::Required),
+                ("modmenu", &DependencyType::Optional),
+                ("sodium", &DependencyType::Incompatible),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reads_forge_mods_toml_with_manifest_version() -> AppResult<()> {
+        let jar = write_jar(
+            "forge",
+            &[
+                (
+                    FORGE_DESCRIPTOR,
+                    r#"
+modLoader = "javafml"
+loaderVersion = "[47,)"
+
+[[mods]]
+modId = "jei"
+version = "${file.jarVersion}"
+displayName = "Just Enough Items"
+authors = "mezz, Ranger"
+
+[[dependencies.jei]]
+modId = "forge"
+mandatory = true
+versionRange = "[47.1.0,)"
+side = "BOTH"
+
+[[dependencies.jei]]
+modId = "minecraft"
+mandatory = true
+versionRange = "[1.20.1,1.20.2)"
+side = "BOTH"
+
+[[dependencies.jei]]
+modId = "rei"
+mandatory = false
+versionRange = "*"
+side = "BOTH"
+"#,
+                ),
+                (
+                    "META-INF/MANIFEST.MF",
+                    "Manifest-Version: 1.0\r\nImplementation-Version: 15.2.0.27\r\n",
+                ),
+            ],
+        )?;
+
+        let meta = read(&jar, None)?;
+        assert_eq!(meta.format, MetadataFormat::Forge);
+        assert_eq!(meta.mod_id, "jei");
+        assert_eq!(meta.version, "15.2.0.27");
+        assert_eq!(meta.authors, vec!["mezz", "Ranger"]);
+        assert_eq!(meta.minecraft_range.as_deref(), Some("[1.20.1,1.20.2)"));
+        assert_eq!(meta.loader_range.as_deref(), Some("[47.1.0,)"));
+        assert_eq!(meta.dependencies.len(), 1);
+        assert_eq!(
+            meta.dependencies[0].dependency_type,
+            DependencyType::Optional
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reads_neoforge_mods_toml() -> AppResult<()> {
+        let jar = write_jar(
+            "neoforge",
+            &[(
+                NEOFORGE_DESCRIPTOR,
+                r#"
+modLoader = "javafml"
+loaderVersion = "[1,)"
+
+[[mods]]
+modId = "zoom"
+version = "2.0.0"
+
+[[dependencies.zoom]]
+modId = "neoforge"
+type = "required"
+versionRange = "[21.0,)"
+
+[[dependencies.zoom]]
+modId = "minecraft"
+type = "required"
+versionRange = "[1.21,1.22)"
+side = "CLIENT"
+
+[[dependencies.zoom]]
+modId = "optifine"
+type = "incompatible"
+"#,
+            )],
+        )?;
+
+        let meta = read(&jar, None)?;
+        assert_eq!(meta.format, MetadataFormat::NeoForge);
+        assert_eq!(meta.name, "zoom");
+        assert_eq!(meta.loader_range.as_deref(), Some("[21.0,)"));
+        assert_eq!(meta.environment, ModEnvironment::Client);
+        assert_eq!(meta.dependencies.len(), 1);
+        assert_eq!(
+            meta.dependencies[0].dependency_type,
+            DependencyType::Incompatible
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn multi_loader_jar_prefers_instance_loader() -> AppResult<()> {
+        let jar = write_jar(
+            "multi",
+            &[
+                (FABRIC_DESCRIPTOR, r#"{"id": "multi", "version": "1.0"}"#),
+                (
+                    FORGE_DESCRIPTOR,
+                    "[[mods]]\nmodId = \"multi\"\nversion = \"1.0\"\n",
+                ),
+            ],
+        )?;
+
+        let meta = read(&jar, Some(&ModLoader::Forge))?;
+        assert_eq!(meta.format, MetadataFormat::Forge);
+        Ok(())
+    }
+
+    #[test]
+    fn provides_include_aliases_and_bundled_jars() -> AppResult<()> {
+        let nested = jar_bytes(&[(
+            FABRIC_DESCRIPTOR,
+            r#"{"id": "fabric-api-base", "version": "0.4.31", "provides": ["fabric-base"]}"#,
+        )])?;
+        let jar = write_jar(
+            "bundle",
+            &[
+                (
+                    FABRIC_DESCRIPTOR,
+                    r#"{"id": "fabric-api", "version": "0.92.0", "provides": ["fabric"]}"#
+                        .as_bytes(),
+                ),
+                (
+                    "META-INF/jars/fabric-api-base-0.4.31.jar",
+                    nested.as_slice(),
+                ),
+            ],
+        )?;
+
+        let meta = read(&jar, None)?;
+        assert_eq!(
+            meta.provides,
+            vec!["fabric", "fabric-api-base", "fabric-base"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn jar_without_descriptor_has_no_metadata() -> AppResult<()> {
+        let jar = write_jar("library", &[("com/example/Lib.class", "cafebabe")])?;
+        let meta = read_jar_metadata(&jar, None)?;
+        assert!(meta.is_none());
+        if let Some(dir) = jar.parent() {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+        Ok(())
+    }
+}
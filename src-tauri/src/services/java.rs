@@ -8,6 +8,7 @@ use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 
 use crate::errors::{AppError, AppResult};
+use crate::models::endpoints::Endpoints;
 use crate::models::java::{JavaInstallResult, JavaRuntimeStatus};
 
 const REQUIRED_JAVA_MAJOR: u32 = 21;
@@ -16,15 +17,17 @@ const RUNTIME_VENDOR: &str = "temurin";
 pub struct JavaService {
     app_dir: PathBuf,
     client: reqwest::Client,
+    adoptium_url: String,
     status: Arc<Mutex<JavaRuntimeStatus>>,
     install_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl JavaService {
-    pub fn new(app_dir: PathBuf) -> Self {
+    pub fn new(app_dir: PathBuf, endpoints: &Endpoints) -> Self {
         Self {
             app_dir,
             client: reqwest::Client::new(),
+            adoptium_url: endpoints.adoptium_api.clone(),
             status: Arc::new(Mutex::new(JavaRuntimeStatus::Missing)),
             install_lock: Arc::new(tokio::sync::Mutex::new(())),
         }
@@ -222,7 +225,8 @@ impl JavaService {
         let os = platform_os()?;
         let arch = platform_arch()?;
         Ok(format!(
-            "https://api.adoptium.net/v3/binary/latest/{REQUIRED_JAVA_MAJOR}/ga/{os}/{arch}/jdk/hotspot/normal/eclipse"
+            "{}/binary/latest/{REQUIRED_JAVA_MAJOR}/ga/{os}/{arch}/jdk/hotspot/normal/eclipse",
+            self.adoptium_url
         ))
     }
 
@@ -516,7 +520,7 @@ mod tests {
     fn make_service() -> JavaService {
         let app_dir = std::env::temp_dir().join(format!("minesync-java-test-{}", uuid::Uuid::new_v4()));
         std::fs::create_dir_all(&app_dir).expect("create temp app dir");
-        JavaService::new(app_dir)
+        JavaService::new(app_dir, &Endpoints::default())
     }
 
     #[test]
@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+use crate::errors::{AppError, AppResult};
+use crate::models::install::{InstallProgress, InstallStage};
+use crate::services::download::{DownloadProgress, DownloadService};
+
+/// Finished jobs kept so their final state can still be read.
+const MAX_FINISHED_JOBS: usize = 20;
+
+// --- Public types ---
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    ModInstall,
+    ModpackInstall,
+    MinecraftDownload,
+}
+
+impl JobKind {
+    pub fn is_install(self) -> bool {
+        matches!(self, Self::ModInstall | Self::ModpackInstall)
+    }
+}
+
+/// Snapshot of one job for the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobInfo {
+    pub id: String,
+    pub kind: JobKind,
+    pub progress: InstallProgress,
+    pub downloads: DownloadProgress,
+    pub cancelled: bool,
+    pub finished: bool,
+    pub started_at: DateTime<Utc>,
+}
+
+// --- CancelToken ---
+
+/// Shared flag telling a job's work to stop, which running tasks can await.
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Resolves once the token is cancelled.
+    pub async fn cancelled(&self) {
+        // Registered before the check so a concurrent cancel is not missed
+        let notified = self.notify.notified();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+
+    /// Run `future` unless the token is cancelled first.
+    pub async fn run<T>(&self, future: impl Future<Output = AppResult<T>>) -> AppResult<T> {
+        if self.is_cancelled() {
+            return Err(AppError::Cancelled);
+        }
+        tokio::select! {
+            result = future => result,
+            () = self.cancelled() => Err(AppError::Cancelled),
+        }
+    }
+}
+
+// --- JobRegistry ---
+
+struct JobEntry {
+    kind: JobKind,
+    progress: InstallProgress,
+    downloads: Arc<Mutex<DownloadProgress>>,
+    cancel: CancelToken,
+    finished: bool,
+    started_at: DateTime<Utc>,
+}
+
+/// Every install and download job, running or recently finished.
+#[derive(Clone, Default)]
+pub struct JobRegistry {
+    jobs: Arc<Mutex<HashMap<String, JobEntry>>>,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a job. Callers may pick the id so they can poll it before
+    /// the command that runs the job returns.
+    pub fn start(&self, job_id: Option<String>, kind: JobKind) -> AppResult<JobHandle> {
+        let id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let mut jobs = self.lock_jobs()?;
+        if jobs.get(&id).is_some_and(|job| !job.finished) {
+            return Err(AppError::Custom(format!("Job {id} is already running")));
+        }
+
+        let downloads = Arc::new(Mutex::new(DownloadProgress::idle()));
+        let cancel = CancelToken::new();
+        jobs.insert(
+            id.clone(),
+            JobEntry {
+                kind,
+                progress: InstallProgress::new(InstallStage::FetchingInfo, 0.0),
+                downloads: Arc::clone(&downloads),
+                cancel: cancel.clone(),
+                finished: false,
+                started_at: Utc::now(),
+            },
+        );
+
+        Ok(JobHandle {
+            id,
+            registry: self.clone(),
+            downloads,
+            cancel,
+        })
+    }
+
+    pub fn get(&self, job_id: &str) -> AppResult<Option<JobInfo>> {
+        let jobs = self.lock_jobs()?;
+        Ok(jobs.get(job_id).map(|job| job_info(job_id, job)))
+    }
+
+    /// All known jobs, oldest first.
+    pub fn list(&self) -> AppResult<Vec<JobInfo>> {
+        let jobs = self.lock_jobs()?;
+        let mut list: Vec<JobInfo> = jobs.iter().map(|(id, job)| job_info(id, job)).collect();
+        list.sort_by_key(|job| job.started_at);
+        Ok(list)
+    }
+
+    /// Most recently started job matching `filter`.
+    pub fn latest(&self, filter: impl Fn(JobKind) -> bool) -> AppResult<Option<JobInfo>> {
+        Ok(self.list()?.into_iter().rfind(|job| filter(job.kind)))
+    }
+
+    /// Stop a running job whose kind matches `filter`.
+    pub fn cancel(&self, job_id: &str, filter: impl Fn(JobKind) -> bool) -> AppResult<()> {
+        let jobs = self.lock_jobs()?;
+        let job = jobs
+            .get(job_id)
+            .filter(|job| filter(job.kind))
+            .ok_or_else(|| AppError::Custom(format!("Job not found: {job_id}")))?;
+        if !job.finished {
+            job.cancel.cancel();
+        }
+        Ok(())
+    }
+
+    fn update(&self, job_id: &str, f: impl FnOnce(&mut JobEntry)) -> AppResult<()> {
+        let mut jobs = self.lock_jobs()?;
+        if let Some(job) = jobs.get_mut(job_id) {
+            f(job);
+        }
+        Ok(())
+    }
+
+    /// Mark a job finished and forget the oldest finished ones.
+    fn finish(&self, job_id: &str) -> AppResult<()> {
+        let mut jobs = self.lock_jobs()?;
+        if let Some(job) = jobs.get_mut(job_id) {
+            job.finished = true;
+        }
+
+        let mut finished: Vec<(String, DateTime<Utc>)> = jobs
+            .iter()
+            .filter(|(_, job)| job.finished)
+            .map(|(id, job)| (id.clone(), job.started_at))
+            .collect();
+        if finished.len() > MAX_FINISHED_JOBS {
+            finished.sort_by_key(|(_, started_at)| *started_at);
+            for (id, _) in finished.iter().take(finished.len() - MAX_FINISHED_JOBS) {
+                jobs.remove(id);
+            }
+        }
+        Ok(())
+    }
+
+    fn lock_jobs(&self) -> AppResult<MutexGuard<'_, HashMap<String, JobEntry>>> {
+        self.jobs
+            .lock()
+            .map_err(|e| AppError::Custom(format!("Job registry lock poisoned: {e}")))
+    }
+}
+
+fn job_info(id: &str, job: &JobEntry) -> JobInfo {
+    let downloads = match job.downloads.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => e.into_inner().clone(),
+    };
+    JobInfo {
+        id: id.to_string(),
+        kind: job.kind,
+        progress: job.progress.clone(),
+        downloads,
+        cancelled: job.cancel.is_cancelled(),
+        finished: job.finished,
+        started_at: job.started_at,
+    }
+}
+
+// --- JobHandle ---
+
+/// Held by the code running a job; the job is marked finished when dropped.
+pub struct JobHandle {
+    id: String,
+    registry: JobRegistry,
+    downloads: Arc<Mutex<DownloadProgress>>,
+    cancel: CancelToken,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Run one step of the job, stopping early if the job is cancelled.
+    pub async fn run<T>(&self, future: impl Future<Output = AppResult<T>>) -> AppResult<T> {
+        self.cancel.run(future).await
+    }
+
+    /// The job's cancellation, for blocking work that checks it between steps.
+    pub fn cancel_token(&self) -> CancelToken {
+        self.cancel.clone()
+    }
+
+    /// A download service reporting into this job's progress and stopped by
+    /// its cancellation, sharing the global download budget with every other job.
+    pub fn download_service(&self, download_service: &DownloadService) -> DownloadService {
+        download_service.for_job(Arc::clone(&self.downloads), self.cancel.clone())
+    }
+
+    /// Move to the next stage, keeping the instance and modpack metadata.
+    /// Fails once the job was cancelled, so work stops at stage boundaries.
+    pub fn set_progress(&self, stage: InstallStage, percent: f32) -> AppResult<()> {
+        if self.cancel.is_cancelled() {
+            return Err(AppError::Cancelled);
+        }
+        self.set_stage(stage, percent)
+    }
+
+    pub fn update_progress(&self, f: impl FnOnce(&mut InstallProgress)) -> AppResult<()> {
+        self.registry.update(&self.id, |job| f(&mut job.progress))
+    }
+
+    pub fn complete(&self) {
+        let _ = self.set_stage(InstallStage::Completed, 100.0);
+    }
+
+    pub fn fail(&self, error: &AppError) {
+        let stage = match error {
+            AppError::Cancelled => InstallStage::Cancelled,
+            _ => InstallStage::Failed {
+                message: error.to_string(),
+            },
+        };
+        let _ = self.set_stage(stage, 100.0);
+    }
+
+    fn set_stage(&self, stage: InstallStage, percent: f32) -> AppResult<()> {
+        self.update_progress(|progress| {
+            progress.stage = stage;
+            progress.overall_percent = percent;
+        })
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        if let Err(e) = self.registry.finish(&self.id) {
+            log::warn!("Failed to finish job {}: {e}", self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jobs_track_progress_independently() -> AppResult<()> {
+        let registry = JobRegistry::new();
+        let pack = registry.start(Some("pack".to_string()), JobKind::ModpackInstall)?;
+        let mod_job = registry.start(None, JobKind::ModInstall)?;
+
+        pack.update_progress(|p| p.modpack_name = Some("Pack".to_string()))?;
+        pack.set_progress(InstallStage::CopyingOverrides, 85.0)?;
+        mod_job.set_progress(InstallStage::ResolvingMods, 20.0)?;
+
+        let pack_info = registry.get("pack")?.expect("pack job");
+        assert!(matches!(
+            pack_info.progress.stage,
+            InstallStage::CopyingOverrides
+        ));
+        assert_eq!(pack_info.progress.modpack_name.as_deref(), Some("Pack"));
+        let mod_info = registry.get(mod_job.id())?.expect("mod job");
+        assert!(matches!(
+            mod_info.progress.stage,
+            InstallStage::ResolvingMods
+        ));
+        assert!(mod_info.progress.modpack_name.is_none());
+
+        assert!(registry
+            .start(Some("pack".to_string()), JobKind::ModInstall)
+            .is_err());
+        drop(pack);
+        assert!(registry.get("pack")?.expect("finished job").finished);
+        assert!(registry
+            .start(Some("pack".to_string()), JobKind::ModInstall)
+            .is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn cancelled_job_stops_at_next_stage() -> AppResult<()> {
+        let registry = JobRegistry::new();
+        let job = registry.start(None, JobKind::ModpackInstall)?;
+
+        assert!(registry
+            .cancel(job.id(), |kind| kind == JobKind::MinecraftDownload)
+            .is_err());
+        registry.cancel(job.id(), JobKind::is_install)?;
+        let result = job.set_progress(InstallStage::DownloadingMinecraft, 22.0);
+        assert!(matches!(result, Err(AppError::Cancelled)));
+        job.fail(&AppError::Cancelled);
+
+        let info = registry.get(job.id())?.expect("job");
+        assert!(info.cancelled);
+        assert!(matches!(info.progress.stage, InstallStage::Cancelled));
+        Ok(())
+    }
+
+    #[test]
+    fn finished_jobs_are_pruned() -> AppResult<()> {
+        let registry = JobRegistry::new();
+        let running = registry.start(None, JobKind::MinecraftDownload)?;
+        for _ in 0..MAX_FINISHED_JOBS + 5 {
+            registry.start(None, JobKind::ModInstall)?;
+        }
+
+        let jobs = registry.list()?;
+        assert_eq!(jobs.len(), MAX_FINISHED_JOBS + 1);
+        assert!(jobs.iter().any(|job| job.id == running.id()));
+        Ok(())
+    }
+}
@@ -0,0 +1,650 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::errors::{AppError, AppResult};
+use crate::models::instance::{MinecraftInstance, ModLoader};
+use crate::models::launcher_import::{
+    ImportNote, LauncherImportReport, LauncherKind, SkippedImport,
+};
+use crate::models::mod_platform::ReleaseChannel;
+use crate::services::database::DatabaseService;
+use crate::services::install::copy_dir_recursive;
+use crate::services::mod_platform::UnifiedModClient;
+use crate::services::mod_scan;
+
+/// Game folders copied into the new instance.
+const IMPORTED_FOLDERS: &[&str] = &["mods", "config", "resourcepacks", "shaderpacks"];
+
+/// A Minecraft setup found in another launcher's files.
+#[derive(Debug)]
+struct ForeignInstance {
+    kind: LauncherKind,
+    name: String,
+    minecraft_version: String,
+    loader: ModLoader,
+    loader_version: Option<String>,
+    /// Folder holding `mods/`, `config/`, etc. `None` when the folder is
+    /// shared with other setups, as its mods may not belong to this one.
+    game_dir: Option<PathBuf>,
+}
+
+/// Import instances from Prism/MultiMC, ATLauncher or the official launcher.
+///
+/// `path` may be a single instance folder, a folder of instances (e.g. Prism's
+/// `instances/`), a `.minecraft` folder or its `launcher_profiles.json`. Game
+/// folders are copied, then mods are registered through a rescan so they get
+/// identified by hash. Instances that fail are reported, not fatal, and so
+/// are instances imported without their files.
+pub async fn import_launcher_instances(
+    db: &DatabaseService,
+    mod_client: &UnifiedModClient,
+    base_dir: &Path,
+    path: &Path,
+) -> AppResult<LauncherImportReport> {
+    let (found, mut skipped) = discover(path)?;
+    if found.is_empty() && skipped.is_empty() {
+        return Err(AppError::Custom(format!(
+            "No Prism, MultiMC, ATLauncher or Minecraft Launcher instances found in {}",
+            path.display()
+        )));
+    }
+
+    let mut imported = Vec::new();
+    let mut notes = Vec::new();
+    for foreign in found {
+        match import_one(db, mod_client, base_dir, &foreign).await {
+            Ok(instance) => {
+                if foreign.game_dir.is_none() {
+                    notes.push(ImportNote {
+                        name: instance.name.clone(),
+                        message: "Profile uses the shared .minecraft folder, so its mods, \
+                                  configs and packs were not copied"
+                            .to_string(),
+                    });
+                }
+                imported.push(instance);
+            }
+            Err(e) => {
+                log::warn!("[IMPORT] Failed to import {}: {e}", foreign.name);
+                skipped.push(SkippedImport {
+                    name: foreign.name,
+                    reason: e.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(LauncherImportReport {
+        imported,
+        skipped,
+        notes,
+    })
+}
+
+async fn import_one(
+    db: &DatabaseService,
+    mod_client: &UnifiedModClient,
+    base_dir: &Path,
+    foreign: &ForeignInstance,
+) -> AppResult<MinecraftInstance> {
+    let instance_id = uuid::Uuid::new_v4().to_string();
+    let instance_path = base_dir.join("instances").join(&instance_id);
+    let now = Utc::now();
+    let instance = MinecraftInstance {
+        id: instance_id,
+        name: foreign.name.clone(),
+        minecraft_version: foreign.minecraft_version.clone(),
+        loader: foreign.loader.clone(),
+        loader_version: foreign.loader_version.clone(),
+        instance_path: instance_path.to_string_lossy().to_string(),
+        icon_path: None,
+        icon_url: None,
+        description: Some(format!("Imported from {}", foreign.kind)),
+        last_played_at: None,
+        total_play_time: 0,
+        is_active: true,
+        min_release_channel: ReleaseChannel::default(),
+        created_at: now,
+        updated_at: now,
+    };
+
+    let copied: AppResult<()> = async {
+        tokio::fs::create_dir_all(instance_path.join("mods")).await?;
+        if let Some(game_dir) = &foreign.game_dir {
+            for folder in IMPORTED_FOLDERS {
+                let src = game_dir.join(folder);
+                if src.is_dir() {
+                    copy_dir_recursive(&src, &instance_path.join(folder)).await?;
+                }
+            }
+        }
+        db.create_instance(&instance)
+    }
+    .await;
+    if let Err(e) = copied {
+        let _ = tokio::fs::remove_dir_all(&instance_path).await;
+        return Err(e);
+    }
+
+    if let Err(e) = mod_scan::rescan_instance_mods(db, mod_client, &instance.id).await {
+        log::warn!("[IMPORT] Failed to register mods of {}: {e}", instance.name);
+    }
+    log::info!(
+        "[IMPORT] Imported {} from {} ({} {})",
+        instance.name,
+        foreign.kind,
+        instance.minecraft_version,
+        instance.loader
+    );
+    Ok(instance)
+}
+
+// --- Discovery ---
+
+/// Everything importable at `path`, plus what was recognised but unreadable.
+fn discover(path: &Path) -> AppResult<(Vec<ForeignInstance>, Vec<SkippedImport>)> {
+    let profiles = if path.is_dir() {
+        path.join("launcher_profiles.json")
+    } else {
+        path.to_path_buf()
+    };
+    if profiles
+        .file_name()
+        .is_some_and(|n| n == "launcher_profiles.json")
+        && profiles.is_file()
+    {
+        return parse_official_profiles(&profiles);
+    }
+
+    if !path.is_dir() {
+        return Err(AppError::Custom(format!(
+            "Not a launcher folder or launcher_profiles.json: {}",
+            path.display()
+        )));
+    }
+
+    let mut found = Vec::new();
+    let mut skipped = Vec::new();
+    let mut record = |dir: &Path, result: AppResult<ForeignInstance>| match result {
+        Ok(foreign) => found.push(foreign),
+        Err(e) => skipped.push(SkippedImport {
+            name: dir_name(dir),
+            reason: e.to_string(),
+        }),
+    };
+
+    if let Some(result) = detect_instance_dir(path) {
+        record(path, result);
+    } else {
+        // A folder of instances, e.g. Prism's or ATLauncher's `instances/`
+        let mut dirs: Vec<PathBuf> = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_dir())
+            .collect();
+        dirs.sort();
+        for dir in dirs {
+            if let Some(result) = detect_instance_dir(&dir) {
+                record(&dir, result);
+            }
+        }
+    }
+
+    Ok((found, skipped))
+}
+
+/// Parse `dir` if it holds a Prism/MultiMC or ATLauncher instance.
+fn detect_instance_dir(dir: &Path) -> Option<AppResult<ForeignInstance>> {
+    if dir.join("mmc-pack.json").is_file() {
+        return Some(parse_prism_instance(dir));
+    }
+    if dir.join("instance.json").is_file() {
+        return Some(parse_atlauncher_instance(dir));
+    }
+    None
+}
+
+fn dir_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| dir.display().to_string())
+}
+
+// --- Prism Launcher / MultiMC ---
+
+#[derive(Debug, Deserialize)]
+struct MmcPack {
+    components: Vec<MmcComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MmcComponent {
+    uid: String,
+    version: Option<String>,
+    cached_version: Option<String>,
+}
+
+fn parse_prism_instance(dir: &Path) -> AppResult<ForeignInstance> {
+    let pack: MmcPack = serde_json::from_str(&std::fs::read_to_string(dir.join("mmc-pack.json"))?)?;
+    let versions: HashMap<&str, &str> = pack
+        .components
+        .iter()
+        .filter_map(|c| {
+            let version = c.version.as_deref().or(c.cached_version.as_deref())?;
+            Some((c.uid.as_str(), version))
+        })
+        .collect();
+
+    let minecraft_version = versions
+        .get("net.minecraft")
+        .ok_or_else(|| AppError::Custom("mmc-pack.json has no Minecraft component".to_string()))?
+        .to_string();
+    let (loader, loader_version) = [
+        ("net.fabricmc.fabric-loader", ModLoader::Fabric),
+        ("org.quiltmc.quilt-loader", ModLoader::Quilt),
+        ("net.neoforged", ModLoader::NeoForge),
+        ("net.minecraftforge", ModLoader::Forge),
+    ]
+    .into_iter()
+    .find_map(|(uid, loader)| Some((loader, Some(versions.get(uid)?.to_string()))))
+    .unwrap_or((ModLoader::Vanilla, None));
+
+    let cfg = std::fs::read_to_string(dir.join("instance.cfg")).unwrap_or_default();
+    let name = cfg_value(&cfg, "name").unwrap_or_else(|| dir_name(dir));
+    let game_dir = [".minecraft", "minecraft"]
+        .iter()
+        .map(|d| dir.join(d))
+        .find(|d| d.is_dir())
+        .unwrap_or_else(|| dir.join(".minecraft"));
+
+    Ok(ForeignInstance {
+        kind: LauncherKind::Prism,
+        name,
+        minecraft_version,
+        loader,
+        loader_version,
+        game_dir: Some(game_dir),
+    })
+}
+
+/// Value of `key` in an INI-style `instance.cfg`.
+fn cfg_value(cfg: &str, key: &str) -> Option<String> {
+    cfg.lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+// --- ATLauncher ---
+
+#[derive(Debug, Deserialize)]
+struct AtlInstance {
+    /// Minecraft version.
+    id: String,
+    launcher: AtlLauncherInfo,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AtlLauncherInfo {
+    name: Option<String>,
+    loader_version: Option<AtlLoaderVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AtlLoaderVersion {
+    version: String,
+    #[serde(rename = "type")]
+    loader_type: String,
+}
+
+fn parse_atlauncher_instance(dir: &Path) -> AppResult<ForeignInstance> {
+    let atl: AtlInstance =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("instance.json"))?)?;
+
+    let (loader, loader_version) = match atl.launcher.loader_version {
+        Some(lv) => {
+            let loader = lv
+                .loader_type
+                .to_ascii_lowercase()
+                .parse::<ModLoader>()
+                .map_err(|_| {
+                    AppError::Custom(format!("Unsupported ATLauncher loader: {}", lv.loader_type))
+                })?;
+            // Forge versions are sometimes stored as "<mc>-<forge>"
+            let version = lv
+                .version
+                .strip_prefix(&format!("{}-", atl.id))
+                .map(str::to_string)
+                .unwrap_or(lv.version);
+            (loader, Some(version))
+        }
+        None => (ModLoader::Vanilla, None),
+    };
+
+    Ok(ForeignInstance {
+        kind: LauncherKind::AtLauncher,
+        name: atl.launcher.name.unwrap_or_else(|| dir_name(dir)),
+        minecraft_version: atl.id,
+        loader,
+        loader_version,
+        game_dir: Some(dir.to_path_buf()),
+    })
+}
+
+// --- Official launcher ---
+
+#[derive(Debug, Deserialize)]
+struct LauncherProfiles {
+    profiles: HashMap<String, LauncherProfile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LauncherProfile {
+    name: Option<String>,
+    #[serde(rename = "type")]
+    profile_type: Option<String>,
+    last_version_id: Option<String>,
+    game_dir: Option<String>,
+}
+
+fn parse_official_profiles(
+    profiles_path: &Path,
+) -> AppResult<(Vec<ForeignInstance>, Vec<SkippedImport>)> {
+    let data: LauncherProfiles = serde_json::from_str(&std::fs::read_to_string(profiles_path)?)?;
+
+    let mut profiles: Vec<(String, LauncherProfile)> = data.profiles.into_iter().collect();
+    profiles.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut found = Vec::new();
+    let mut skipped = Vec::new();
+    for (key, profile) in profiles {
+        let name = profile
+            .name
+            .filter(|n| !n.is_empty())
+            .or_else(|| profile.last_version_id.clone())
+            .unwrap_or(key);
+        let version_id = match (profile.profile_type.as_deref(), profile.last_version_id) {
+            (Some("latest-release" | "latest-snapshot"), _) | (_, None) => {
+                skipped.push(SkippedImport {
+                    name,
+                    reason: "Profile follows the latest release instead of a fixed version"
+                        .to_string(),
+                });
+                continue;
+            }
+            (_, Some(id)) => id,
+        };
+
+        let Some((minecraft_version, loader, loader_version)) = parse_version_id(&version_id)
+        else {
+            skipped.push(SkippedImport {
+                name,
+                reason: format!(
+                    "Unrecognised version {version_id}; only vanilla, Fabric, Quilt, Forge \
+                     and NeoForge installs can be imported"
+                ),
+            });
+            continue;
+        };
+        // Without its own gameDir a profile plays from `.minecraft`, whose
+        // mods are shared by every such profile
+        found.push(ForeignInstance {
+            kind: LauncherKind::Official,
+            name,
+            minecraft_version,
+            loader,
+            loader_version,
+            game_dir: profile.game_dir.map(PathBuf::from),
+        });
+    }
+
+    Ok((found, skipped))
+}
+
+/// Split a launcher version id into Minecraft version, loader and loader
+/// version, following the ids the loader installers write:
+/// `fabric-loader-0.15.0-1.20.1`, `quilt-loader-0.21.0-1.20.1`,
+/// `1.20.1-forge-47.2.0`, `neoforge-20.4.80`. `None` for anything else,
+/// such as old Forge or OptiFine installs and hand-made versions.
+fn parse_version_id(id: &str) -> Option<(String, ModLoader, Option<String>)> {
+    for (prefix, loader) in [
+        ("fabric-loader-", ModLoader::Fabric),
+        ("quilt-loader-", ModLoader::Quilt),
+    ] {
+        if let Some(rest) = id.strip_prefix(prefix) {
+            let (lv, mc) = rest.split_once('-')?;
+            return is_minecraft_version(mc)
+                .then(|| (mc.to_string(), loader, Some(lv.to_string())));
+        }
+    }
+    if let Some((mc, lv)) = id.split_once("-forge-") {
+        return is_minecraft_version(mc)
+            .then(|| (mc.to_string(), ModLoader::Forge, Some(lv.to_string())));
+    }
+    if let Some(lv) = id.strip_prefix("neoforge-") {
+        // NeoForge 20.4.x targets Minecraft 1.20.4, 21.0.x targets 1.21
+        let mut parts = lv.split('.');
+        let (major, minor) = (parts.next()?, parts.next()?);
+        if !is_number(major) || !is_number(minor) {
+            return None;
+        }
+        let mc = match minor {
+            "0" => format!("1.{major}"),
+            _ => format!("1.{major}.{minor}"),
+        };
+        return Some((mc, ModLoader::NeoForge, Some(lv.to_string())));
+    }
+    is_minecraft_version(id).then(|| (id.to_string(), ModLoader::Vanilla, None))
+}
+
+/// A vanilla release (`1.20.1`), pre-release or release candidate
+/// (`1.21-pre1`, `1.20.1-rc1`) or snapshot (`23w45a`).
+fn is_minecraft_version(version: &str) -> bool {
+    let (release, suffix) = match version.split_once('-') {
+        Some((release, suffix)) => (release, Some(suffix)),
+        None => (version, None),
+    };
+    let is_release = release.starts_with("1.") && release.split('.').all(is_number);
+    let suffix_ok = suffix.map_or(true, |s| {
+        ["pre", "rc"]
+            .iter()
+            .any(|tag| s.strip_prefix(tag).is_some_and(is_number))
+    });
+    if is_release && suffix_ok {
+        return true;
+    }
+
+    let bytes = version.as_bytes();
+    bytes.len() == 6
+        && bytes[..2].iter().all(u8::is_ascii_digit)
+        && bytes[2] == b'w'
+        && bytes[3..5].iter().all(u8::is_ascii_digit)
+        && bytes[5].is_ascii_lowercase()
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::endpoints::Endpoints;
+    use crate::services::http_cache::HttpCache;
+    use crate::test_support::{http_response, stub_server, write_fabric_jar};
+
+    #[test]
+    fn parses_launcher_version_ids() {
+        let cases = [
+            ("1.20.1", ("1.20.1", ModLoader::Vanilla, None)),
+            ("23w45a", ("23w45a", ModLoader::Vanilla, None)),
+            ("1.21-pre1", ("1.21-pre1", ModLoader::Vanilla, None)),
+            (
+                "fabric-loader-0.15.0-1.20.1",
+                ("1.20.1", ModLoader::Fabric, Some("0.15.0")),
+            ),
+            (
+                "quilt-loader-0.21.0-1.20.1",
+                ("1.20.1", ModLoader::Quilt, Some("0.21.0")),
+            ),
+            (
+                "1.20.1-forge-47.2.0",
+                ("1.20.1", ModLoader::Forge, Some("47.2.0")),
+            ),
+            (
+                "neoforge-20.4.80",
+                ("1.20.4", ModLoader::NeoForge, Some("20.4.80")),
+            ),
+            (
+                "neoforge-21.0.143",
+                ("1.21", ModLoader::NeoForge, Some("21.0.143")),
+            ),
+        ];
+        for (id, (mc, loader, lv)) in cases {
+            assert_eq!(
+                parse_version_id(id),
+                Some((mc.to_string(), loader, lv.map(str::to_string))),
+                "{id}"
+            );
+        }
+
+        for id in [
+            "1.12.2-forge1.12.2-14.23.5.2859",
+            "1.20.1-OptiFine_HD_U_I6",
+            "My Modpack",
+            "fabric-loader-0.15.0-1.20.1-OptiFine",
+        ] {
+            assert_eq!(parse_version_id(id), None, "{id}");
+        }
+    }
+
+    #[test]
+    fn discovers_prism_and_atlauncher_instances_in_a_folder() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_import_{}", uuid::Uuid::new_v4()));
+
+        let prism = root.join("Prism Pack");
+        std::fs::create_dir_all(prism.join(".minecraft").join("mods"))?;
+        std::fs::write(
+            prism.join("instance.cfg"),
+            "[General]\nInstanceType=OneSix\nname=Fabulously Optimized\n",
+        )?;
+        std::fs::write(
+            prism.join("mmc-pack.json"),
+            r#"{"components":[
+                {"uid":"net.minecraft","version":"1.20.1"},
+                {"uid":"net.fabricmc.intermediary","version":"1.20.1"},
+                {"uid":"net.fabricmc.fabric-loader","version":"0.15.0"}
+            ],"formatVersion":1}"#,
+        )?;
+
+        let atl = root.join("AllTheMods");
+        std::fs::create_dir_all(&atl)?;
+        std::fs::write(
+            atl.join("instance.json"),
+            r#"{"id":"1.20.1","launcher":{"name":"All the Mods 9",
+                "loaderVersion":{"version":"1.20.1-47.2.0","type":"Forge"}}}"#,
+        )?;
+
+        let broken = root.join("Broken");
+        std::fs::create_dir_all(&broken)?;
+        std::fs::write(broken.join("mmc-pack.json"), r#"{"components":[]}"#)?;
+
+        std::fs::create_dir_all(root.join("not-an-instance"))?;
+
+        let (found, skipped) = discover(&root)?;
+        assert_eq!(found.len(), 2);
+
+        let atl = &found[0];
+        assert_eq!(atl.kind, LauncherKind::AtLauncher);
+        assert_eq!(atl.name, "All the Mods 9");
+        assert_eq!(atl.loader, ModLoader::Forge);
+        assert_eq!(atl.loader_version.as_deref(), Some("47.2.0"));
+
+        let prism_found = &found[1];
+        assert_eq!(prism_found.kind, LauncherKind::Prism);
+        assert_eq!(prism_found.name, "Fabulously Optimized");
+        assert_eq!(prism_found.minecraft_version, "1.20.1");
+        assert_eq!(prism_found.loader, ModLoader::Fabric);
+        assert_eq!(prism_found.loader_version.as_deref(), Some("0.15.0"));
+        assert_eq!(prism_found.game_dir, Some(prism.join(".minecraft")));
+
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].name, "Broken");
+
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn imports_official_profiles_without_sharing_their_mods() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_import_{}", uuid::Uuid::new_v4()));
+        let minecraft = root.join(".minecraft");
+        let own_dir = root.join("pack");
+        std::fs::create_dir_all(minecraft.join("mods"))?;
+        std::fs::create_dir_all(own_dir.join("mods"))?;
+        std::fs::create_dir_all(own_dir.join("config"))?;
+        write_fabric_jar(&minecraft.join("mods").join("shared.jar"), "shared", "1.0")?;
+        write_fabric_jar(&own_dir.join("mods").join("sodium.jar"), "sodium", "0.5.8")?;
+        std::fs::write(own_dir.join("config").join("sodium.json"), "{}")?;
+        std::fs::write(
+            minecraft.join("launcher_profiles.json"),
+            serde_json::json!({"profiles": {
+                "a": {"name": "Own Pack", "type": "custom",
+                      "lastVersionId": "fabric-loader-0.15.0-1.20.1",
+                      "gameDir": own_dir.to_string_lossy()},
+                "b": {"name": "Shared", "type": "custom",
+                      "lastVersionId": "fabric-loader-0.15.0-1.20.1"},
+                "c": {"name": "OptiFine", "type": "custom",
+                      "lastVersionId": "1.20.1-OptiFine_HD_U_I6"},
+            }})
+            .to_string(),
+        )?;
+
+        // No hash is known to the platforms, so mods stay local
+        let server = stub_server(usize::MAX, |_| Some(http_response("200 OK", b"{}")));
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let mod_client = UnifiedModClient::new(
+            None,
+            &Endpoints::all_at(&server.base),
+            HttpCache::new(root.join("http")),
+        );
+        let report =
+            import_launcher_instances(&db, &mod_client, &root.join("app"), &minecraft).await?;
+
+        let names: Vec<&str> = report.imported.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["Own Pack", "Shared"]);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].name, "OptiFine");
+        assert_eq!(report.notes.len(), 1);
+        assert_eq!(report.notes[0].name, "Shared");
+
+        let own = &report.imported[0];
+        let own_path = Path::new(&own.instance_path);
+        assert!(own_path.join("mods").join("sodium.jar").is_file());
+        assert!(own_path.join("config").join("sodium.json").is_file());
+        let stored = db.get_instance(&own.id)?.expect("instance row");
+        assert_eq!(stored.loader, ModLoader::Fabric);
+        assert_eq!(stored.loader_version.as_deref(), Some("0.15.0"));
+        let mods = db.list_instance_mods(&own.id)?;
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods[0].file_name, "sodium.jar");
+        assert_eq!(mods[0].version, "0.5.8");
+
+        let shared = &report.imported[1];
+        let shared_mods = Path::new(&shared.instance_path).join("mods");
+        assert_eq!(std::fs::read_dir(shared_mods)?.count(), 0);
+        assert!(db.list_instance_mods(&shared.id)?.is_empty());
+
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+}
@@ -1,12 +1,13 @@
 use serde::Deserialize;
 
 use crate::errors::{AppError, AppResult};
+use crate::models::endpoints::Endpoints;
 use crate::models::loader::{LoaderLibrary, LoaderProfile, LoaderVersionEntry};
 
-const META_URL: &str = "https://meta.fabricmc.net/v2";
-
 pub struct FabricInstaller {
     client: reqwest::Client,
+    meta_url: String,
+    maven_url: String,
 }
 
 // --- Fabric Meta API response types ---
@@ -47,18 +48,22 @@ struct FabricArguments {
 }
 
 impl FabricInstaller {
-    pub fn new() -> Self {
+    pub fn new(endpoints: &Endpoints) -> Self {
         let client = reqwest::Client::builder()
             .user_agent("MineSync/1.0.0")
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            meta_url: endpoints.fabric_meta.clone(),
+            maven_url: endpoints.fabric_maven.clone(),
+        }
     }
 
     /// List all Fabric loader versions available for a Minecraft version.
     pub async fn list_versions(&self, game_version: &str) -> AppResult<Vec<LoaderVersionEntry>> {
-        let url = format!("{META_URL}/versions/loader/{game_version}");
+        let url = format!("{}/versions/loader/{game_version}", self.meta_url);
         let response = self.client.get(&url).send().await?;
 
         if !response.status().is_success() {
@@ -91,8 +96,10 @@ impl FabricInstaller {
         game_version: &str,
         loader_version: &str,
     ) -> AppResult<LoaderProfile> {
-        let url =
-            format!("{META_URL}/versions/loader/{game_version}/{loader_version}/profile/json");
+        let url = format!(
+            "{}/versions/loader/{game_version}/{loader_version}/profile/json",
+            self.meta_url
+        );
 
         let response = self.client.get(&url).send().await?;
 
@@ -104,21 +111,22 @@ impl FabricInstaller {
         }
 
         let profile: FabricProfileJson = response.json().await?;
-        Ok(fabric_profile_to_loader_profile(profile))
+        Ok(fabric_profile_to_loader_profile(profile, &self.maven_url))
     }
 }
 
 // --- Converters ---
 
-fn fabric_profile_to_loader_profile(profile: FabricProfileJson) -> LoaderProfile {
+fn fabric_profile_to_loader_profile(
+    profile: FabricProfileJson,
+    default_maven: &str,
+) -> LoaderProfile {
     let libraries = profile
         .libraries
         .into_iter()
         .map(|lib| {
             let path = maven_name_to_path(&lib.name);
-            let base_url = lib
-                .url
-                .unwrap_or_else(|| "https://maven.fabricmc.net/".to_string());
+            let base_url = lib.url.unwrap_or_else(|| default_maven.to_string());
             let url = format!("{}{}", base_url.trim_end_matches('/'), &format!("/{path}"));
 
             LoaderLibrary {
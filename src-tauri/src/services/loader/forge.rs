@@ -4,14 +4,13 @@ use std::path::{Path, PathBuf};
 use serde::Deserialize;
 
 use crate::errors::{AppError, AppResult};
+use crate::models::endpoints::Endpoints;
 use crate::models::loader::{LoaderLibrary, LoaderProfile, LoaderVersionEntry};
 
-const MAVEN_URL: &str = "https://maven.minecraftforge.net";
-const PROMOTIONS_URL: &str =
-    "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
-
 pub struct ForgeInstaller {
     client: reqwest::Client,
+    maven_url: String,
+    promotions_url: String,
 }
 
 // --- Forge promotions API ---
@@ -56,20 +55,24 @@ struct ForgeArguments {
 }
 
 impl ForgeInstaller {
-    pub fn new() -> Self {
+    pub fn new(endpoints: &Endpoints) -> Self {
         let client = reqwest::Client::builder()
             .user_agent("MineSync/1.0.0")
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            maven_url: endpoints.forge_maven.clone(),
+            promotions_url: endpoints.forge_promotions.clone(),
+        }
     }
 
     /// List Forge versions available for a Minecraft version.
     ///
     /// Uses the promotions API to find recommended/latest Forge versions.
     pub async fn list_versions(&self, game_version: &str) -> AppResult<Vec<LoaderVersionEntry>> {
-        let response = self.client.get(PROMOTIONS_URL).send().await?;
+        let response = self.client.get(&self.promotions_url).send().await?;
 
         if !response.status().is_success() {
             return Err(AppError::Custom(format!(
@@ -120,7 +123,8 @@ impl ForgeInstaller {
     ) -> AppResult<LoaderProfile> {
         let forge_id = format!("{game_version}-{loader_version}");
         let installer_url = format!(
-            "{MAVEN_URL}/net/minecraftforge/forge/{forge_id}/forge-{forge_id}-installer.jar"
+            "{}/net/minecraftforge/forge/{forge_id}/forge-{forge_id}-installer.jar",
+            self.maven_url
         );
 
         let response = self.client.get(&installer_url).send().await?;
@@ -153,7 +157,7 @@ impl ForgeInstaller {
             ))
         })?;
 
-        Ok(forge_profile_to_loader_profile(profile))
+        Ok(forge_profile_to_loader_profile(profile, &self.maven_url))
     }
 
     /// Get the path to a previously downloaded installer JAR.
@@ -183,7 +187,7 @@ fn extract_version_json_from_jar(jar_bytes: &[u8]) -> AppResult<String> {
     Ok(contents)
 }
 
-fn forge_profile_to_loader_profile(profile: ForgeVersionJson) -> LoaderProfile {
+fn forge_profile_to_loader_profile(profile: ForgeVersionJson, maven_url: &str) -> LoaderProfile {
     let libraries = profile
         .libraries
         .into_iter()
@@ -198,7 +202,7 @@ fn forge_profile_to_loader_profile(profile: ForgeVersionJson) -> LoaderProfile {
                 },
                 None => {
                     let path = maven_name_to_path(&lib.name);
-                    let url = format!("{MAVEN_URL}/{path}");
+                    let url = format!("{maven_url}/{path}");
                     (url, path, None, 0)
                 }
             };
@@ -6,9 +6,10 @@ pub mod quilt;
 use std::path::PathBuf;
 
 use crate::errors::{AppError, AppResult};
+use crate::models::endpoints::Endpoints;
 use crate::models::instance::ModLoader;
 use crate::models::loader::{LoaderProfile, LoaderVersionEntry};
-use crate::services::download::{DownloadService, DownloadTask};
+use crate::services::download::{DownloadService, DownloadTask, FileHashes};
 
 use self::fabric::FabricInstaller;
 use self::forge::ForgeInstaller;
@@ -26,12 +27,12 @@ pub struct LoaderService {
 }
 
 impl LoaderService {
-    pub fn new(base_dir: PathBuf) -> Self {
+    pub fn new(base_dir: PathBuf, endpoints: &Endpoints) -> Self {
         Self {
-            fabric: FabricInstaller::new(),
-            quilt: QuiltInstaller::new(),
-            forge: ForgeInstaller::new(),
-            neoforge: NeoForgeInstaller::new(),
+            fabric: FabricInstaller::new(endpoints),
+            quilt: QuiltInstaller::new(endpoints),
+            forge: ForgeInstaller::new(endpoints),
+            neoforge: NeoForgeInstaller::new(endpoints),
             base_dir,
         }
     }
@@ -105,10 +106,14 @@ impl LoaderService {
                     return None;
                 }
                 Some(DownloadTask {
-                    url: lib.url.clone(),
+                    urls: vec![lib.url.clone()],
                     dest,
-                    sha1: lib.sha1.clone(),
+                    hashes: FileHashes {
+                        sha1: lib.sha1.clone(),
+                        ..FileHashes::default()
+                    },
                     size: lib.size,
+                    shared: false,
                 })
             })
             .collect();
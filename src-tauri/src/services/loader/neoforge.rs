@@ -4,14 +4,13 @@ use std::path::{Path, PathBuf};
 use serde::Deserialize;
 
 use crate::errors::{AppError, AppResult};
+use crate::models::endpoints::Endpoints;
 use crate::models::loader::{LoaderLibrary, LoaderProfile, LoaderVersionEntry};
 
-const MAVEN_URL: &str = "https://maven.neoforged.net";
-const VERSIONS_API: &str =
-    "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
-
 pub struct NeoForgeInstaller {
     client: reqwest::Client,
+    maven_url: String,
+    versions_url: String,
 }
 
 // --- NeoForge Maven API response ---
@@ -58,13 +57,17 @@ struct NeoForgeArguments {
 }
 
 impl NeoForgeInstaller {
-    pub fn new() -> Self {
+    pub fn new(endpoints: &Endpoints) -> Self {
         let client = reqwest::Client::builder()
             .user_agent("MineSync/1.0.0")
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            maven_url: endpoints.neoforge_maven.clone(),
+            versions_url: endpoints.neoforge_versions.clone(),
+        }
     }
 
     /// List all NeoForge versions, filtered for a specific MC version.
@@ -72,7 +75,7 @@ impl NeoForgeInstaller {
     /// NeoForge version scheme: MC versions map to NeoForge major.minor:
     /// MC 1.21.5 → NeoForge 21.5.x, MC 1.20.1 → NeoForge 20.1.x
     pub async fn list_versions(&self, game_version: &str) -> AppResult<Vec<LoaderVersionEntry>> {
-        let response = self.client.get(VERSIONS_API).send().await?;
+        let response = self.client.get(&self.versions_url).send().await?;
 
         if !response.status().is_success() {
             return Err(AppError::Custom(format!(
@@ -117,7 +120,8 @@ impl NeoForgeInstaller {
     ) -> AppResult<LoaderProfile> {
         // Download installer JAR
         let installer_url = format!(
-            "{MAVEN_URL}/releases/net/neoforged/neoforge/{loader_version}/neoforge-{loader_version}-installer.jar"
+            "{}/releases/net/neoforged/neoforge/{loader_version}/neoforge-{loader_version}-installer.jar",
+            self.maven_url
         );
 
         let response = self.client.get(&installer_url).send().await?;
@@ -157,6 +161,7 @@ impl NeoForgeInstaller {
 
         Ok(neoforge_profile_to_loader_profile(
             profile,
+            &self.maven_url,
             game_version,
             loader_version,
             &loader_dir,
@@ -208,6 +213,7 @@ fn extract_version_json_from_jar(jar_bytes: &[u8]) -> AppResult<String> {
 
 fn neoforge_profile_to_loader_profile(
     profile: NeoForgeVersionJson,
+    maven_url: &str,
     _game_version: &str,
     _loader_version: &str,
     _loader_dir: &Path,
@@ -226,7 +232,7 @@ fn neoforge_profile_to_loader_profile(
                 },
                 None => {
                     let path = maven_name_to_path(&lib.name);
-                    let url = format!("{MAVEN_URL}/releases/{path}");
+                    let url = format!("{maven_url}/releases/{path}");
                     (url, path, None, 0)
                 }
             };
@@ -1,12 +1,13 @@
 use serde::Deserialize;
 
 use crate::errors::{AppError, AppResult};
+use crate::models::endpoints::Endpoints;
 use crate::models::loader::{LoaderLibrary, LoaderProfile, LoaderVersionEntry};
 
-const META_URL: &str = "https://meta.quiltmc.org/v3";
-
 pub struct QuiltInstaller {
     client: reqwest::Client,
+    meta_url: String,
+    maven_url: String,
 }
 
 // --- Quilt Meta API response types ---
@@ -43,18 +44,22 @@ struct QuiltArguments {
 }
 
 impl QuiltInstaller {
-    pub fn new() -> Self {
+    pub fn new(endpoints: &Endpoints) -> Self {
         let client = reqwest::Client::builder()
             .user_agent("MineSync/1.0.0")
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            meta_url: endpoints.quilt_meta.clone(),
+            maven_url: endpoints.quilt_maven.clone(),
+        }
     }
 
     /// List all Quilt loader versions available for a Minecraft version.
     pub async fn list_versions(&self, game_version: &str) -> AppResult<Vec<LoaderVersionEntry>> {
-        let url = format!("{META_URL}/versions/loader/{game_version}");
+        let url = format!("{}/versions/loader/{game_version}", self.meta_url);
         let response = self.client.get(&url).send().await?;
 
         if !response.status().is_success() {
@@ -87,8 +92,10 @@ impl QuiltInstaller {
         game_version: &str,
         loader_version: &str,
     ) -> AppResult<LoaderProfile> {
-        let url =
-            format!("{META_URL}/versions/loader/{game_version}/{loader_version}/profile/json");
+        let url = format!(
+            "{}/versions/loader/{game_version}/{loader_version}/profile/json",
+            self.meta_url
+        );
 
         let response = self.client.get(&url).send().await?;
 
@@ -100,21 +107,22 @@ impl QuiltInstaller {
         }
 
         let profile: QuiltProfileJson = response.json().await?;
-        Ok(quilt_profile_to_loader_profile(profile))
+        Ok(quilt_profile_to_loader_profile(profile, &self.maven_url))
     }
 }
 
 // --- Converters ---
 
-fn quilt_profile_to_loader_profile(profile: QuiltProfileJson) -> LoaderProfile {
+fn quilt_profile_to_loader_profile(
+    profile: QuiltProfileJson,
+    default_maven: &str,
+) -> LoaderProfile {
     let libraries = profile
         .libraries
         .into_iter()
         .map(|lib| {
             let path = maven_name_to_path(&lib.name);
-            let base_url = lib
-                .url
-                .unwrap_or_else(|| "https://maven.quiltmc.org/repository/release/".to_string());
+            let base_url = lib.url.unwrap_or_else(|| default_maven.to_string());
             let url = format!("{}{}", base_url.trim_end_matches('/'), &format!("/{path}"));
 
             LoaderLibrary {
@@ -5,10 +5,9 @@ use std::sync::Mutex;
 use serde::{Deserialize, Serialize};
 
 use crate::errors::{AppError, AppResult};
-use crate::services::download::DownloadTask;
-
-const MANIFEST_URL: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
-const ASSETS_BASE_URL: &str = "https://resources.download.minecraft.net";
+use crate::models::endpoints::Endpoints;
+use crate::services::download::{DownloadTask, FileHashes};
+use crate::services::http_cache::{HttpCache, IMMUTABLE_TTL, MANIFEST_TTL};
 
 // --- Mojang API response types ---
 
@@ -124,15 +123,21 @@ struct AssetObject {
 
 pub struct MinecraftService {
     client: reqwest::Client,
+    cache: HttpCache,
     base_dir: PathBuf,
+    manifest_url: String,
+    assets_url: String,
     manifest_cache: Mutex<Option<Vec<VersionEntry>>>,
 }
 
 impl MinecraftService {
-    pub fn new(base_dir: PathBuf) -> Self {
+    pub fn new(base_dir: PathBuf, endpoints: &Endpoints, cache: HttpCache) -> Self {
         Self {
             client: reqwest::Client::new(),
+            cache,
             base_dir,
+            manifest_url: endpoints.mojang_version_manifest.clone(),
+            assets_url: endpoints.mojang_assets.clone(),
             manifest_cache: Mutex::new(None),
         }
     }
@@ -143,7 +148,10 @@ impl MinecraftService {
 
     /// Fetch the version manifest from Mojang and cache it
     pub async fn fetch_version_manifest(&self) -> AppResult<Vec<VersionEntry>> {
-        let response = self.client.get(MANIFEST_URL).send().await?;
+        let response = self
+            .cache
+            .send(self.client.get(&self.manifest_url), MANIFEST_TTL)
+            .await?;
 
         if !response.status().is_success() {
             return Err(AppError::Custom(format!(
@@ -152,7 +160,7 @@ impl MinecraftService {
             )));
         }
 
-        let manifest: VersionManifest = response.json().await?;
+        let manifest: VersionManifest = response.json()?;
 
         // Cache for later URL lookups (short lock, after all awaits)
         {
@@ -167,8 +175,11 @@ impl MinecraftService {
     pub async fn fetch_version_detail(&self, version_id: &str) -> AppResult<VersionDetail> {
         let url = self.get_version_url(version_id)?;
 
-        let response = self.client.get(&url).send().await?;
-        let body = response.text().await?;
+        let response = self
+            .cache
+            .send(self.client.get(&url), IMMUTABLE_TTL)
+            .await?;
+        let body = response.text();
 
         // Save raw JSON to versions/{id}/{id}.json
         let version_dir = self.base_dir.join("versions").join(version_id);
@@ -186,10 +197,11 @@ impl MinecraftService {
         // Client JAR
         let version_dir = self.base_dir.join("versions").join(&detail.id);
         tasks.push(DownloadTask {
-            url: detail.downloads.client.url.clone(),
+            urls: vec![detail.downloads.client.url.clone()],
             dest: version_dir.join(format!("{}.jar", detail.id)),
-            sha1: Some(detail.downloads.client.sha1.clone()),
+            hashes: FileHashes::sha1(&detail.downloads.client.sha1),
             size: detail.downloads.client.size,
+            shared: false,
         });
 
         // Libraries (filtered by OS rules)
@@ -235,10 +247,11 @@ impl MinecraftService {
             if let Some(ref artifact) = downloads.artifact {
                 if let Some(ref path) = artifact.path {
                     tasks.push(DownloadTask {
-                        url: artifact.url.clone(),
+                        urls: vec![artifact.url.clone()],
                         dest: lib_dir.join(path),
-                        sha1: Some(artifact.sha1.clone()),
+                        hashes: FileHashes::sha1(&artifact.sha1),
                         size: artifact.size,
+                        shared: false,
                     });
                 }
             }
@@ -269,10 +282,11 @@ impl MinecraftService {
                 if let Some(ref path) = artifact.path {
                     let dest = self.base_dir.join("libraries").join(path);
                     tasks.push(DownloadTask {
-                        url: artifact.url.clone(),
+                        urls: vec![artifact.url.clone()],
                         dest,
-                        sha1: Some(artifact.sha1.clone()),
+                        hashes: FileHashes::sha1(&artifact.sha1),
                         size: artifact.size,
+                        shared: false,
                     });
                 }
             }
@@ -287,8 +301,11 @@ impl MinecraftService {
         let assets_dir = self.base_dir.join("assets");
 
         // Fetch and save asset index
-        let response = self.client.get(&asset_info.url).send().await?;
-        let body = response.text().await?;
+        let response = self
+            .cache
+            .send(self.client.get(&asset_info.url), IMMUTABLE_TTL)
+            .await?;
+        let body = response.text();
 
         let index_dir = assets_dir.join("indexes");
         tokio::fs::create_dir_all(&index_dir).await?;
@@ -300,10 +317,11 @@ impl MinecraftService {
         for obj in index.objects.values() {
             let prefix = &obj.hash[..2];
             tasks.push(DownloadTask {
-                url: format!("{ASSETS_BASE_URL}/{prefix}/{}", obj.hash),
+                urls: vec![format!("{}/{prefix}/{}", self.assets_url, obj.hash)],
                 dest: objects_dir.join(prefix).join(&obj.hash),
-                sha1: Some(obj.hash.clone()),
+                hashes: FileHashes::sha1(&obj.hash),
                 size: obj.size,
+                shared: false,
             });
         }
 
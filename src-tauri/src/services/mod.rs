@@ -1,11 +1,23 @@
 pub mod auth;
 pub mod database;
+pub mod dependency_resolver;
 pub mod download;
+pub mod export;
+pub mod http_cache;
 pub mod install;
+pub mod instance_check;
+pub mod jar_metadata;
 pub mod java;
+pub mod jobs;
 pub mod launch;
+pub mod launcher_import;
 pub mod loader;
 pub mod minecraft;
 pub mod mod_platform;
+pub mod mod_scan;
+pub mod mod_update;
 pub mod p2p;
+pub mod rate_limit;
+pub mod store;
 pub mod sync_protocol;
+pub mod version_range;
@@ -3,23 +3,34 @@ use std::collections::HashMap;
 use serde::Deserialize;
 
 use crate::errors::{AppError, AppResult};
+use crate::models::endpoints::Endpoints;
 use crate::models::mod_info::ModSource;
 use crate::models::mod_platform::{
     ContentType, DependencyType, ModDependency, ModDetails, ModSearchResult, ModVersionFile,
-    ModVersionInfo, SearchFilters, SearchResponse, SearchSort,
+    ModVersionInfo, ReleaseChannel, SearchFilters, SearchResponse, SearchSort,
 };
+use crate::services::http_cache::{HttpCache, PROJECT_TTL, SEARCH_TTL, VERSIONS_TTL};
+use crate::services::rate_limit::RateLimiter;
 
-const BASE_URL: &str = "https://api.curseforge.com";
 const MINECRAFT_GAME_ID: u32 = 432;
 const CLASS_ID_MODS: u32 = 6;
 const CLASS_ID_MODPACKS: u32 = 4471;
-
-// CurseForge download URL fallback when API returns null
-const CDN_BASE: &str = "https://edge.forgecdn.net/files";
+const CLASS_ID_RESOURCE_PACKS: u32 = 12;
+const CLASS_ID_SHADERS: u32 = 6552;
+const CLASS_ID_DATA_PACKS: u32 = 6945;
+const CLASS_ID_PLUGINS: u32 = 5;
+/// Most values CurseForge accepts in the `gameVersions` search parameter.
+const MAX_SEARCH_GAME_VERSIONS: usize = 4;
+/// Most values CurseForge accepts in the `modLoaderTypes` search parameter.
+const MAX_SEARCH_LOADER_TYPES: usize = 5;
 
 pub struct CurseForgeClient {
     client: reqwest::Client,
+    cache: HttpCache,
+    limiter: RateLimiter,
     api_key: String,
+    base_url: String,
+    cdn_url: String,
 }
 
 // --- CurseForge API response types ---
@@ -49,6 +60,11 @@ struct CfFilesResponse {
     data: Vec<CfFile>,
 }
 
+#[derive(Deserialize)]
+struct CfFileResponse {
+    data: CfFile,
+}
+
 #[derive(Deserialize)]
 struct CfMod {
     id: u32,
@@ -104,6 +120,8 @@ struct CfModLinks {
 #[derive(Deserialize)]
 struct CfFile {
     id: u32,
+    #[serde(rename = "fileFingerprint", default)]
+    file_fingerprint: u32,
     #[serde(rename = "displayName")]
     display_name: String,
     #[serde(rename = "fileName")]
@@ -118,6 +136,26 @@ struct CfFile {
     dependencies: Option<Vec<CfDependency>>,
     #[serde(rename = "fileDate")]
     file_date: String,
+    /// 1 = release, 2 = beta, 3 = alpha
+    #[serde(rename = "releaseType", default)]
+    release_type: u32,
+}
+
+#[derive(Deserialize)]
+struct CfFingerprintResponse {
+    data: CfFingerprintMatches,
+}
+
+#[derive(Deserialize)]
+struct CfFingerprintMatches {
+    #[serde(rename = "exactMatches")]
+    exact_matches: Vec<CfFingerprintMatch>,
+}
+
+#[derive(Deserialize)]
+struct CfFingerprintMatch {
+    id: u32,
+    file: CfFile,
 }
 
 #[derive(Deserialize)]
@@ -135,30 +173,38 @@ struct CfDependency {
 }
 
 impl CurseForgeClient {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(api_key: String, endpoints: &Endpoints, cache: HttpCache) -> Self {
         let client = reqwest::Client::builder()
             .user_agent("MineSync/1.0.0")
             .build()
             .unwrap_or_default();
 
-        Self { client, api_key }
+        Self {
+            client,
+            cache,
+            limiter: RateLimiter::new("CurseForge"),
+            api_key,
+            base_url: endpoints.curseforge_api.clone(),
+            cdn_url: endpoints.curseforge_cdn.clone(),
+        }
     }
 
     pub async fn search_mods(&self, filters: &SearchFilters) -> AppResult<SearchResponse> {
+        // CurseForge has no relevance score; popularity is its closest order
         let sort_field = match filters.sort {
-            SearchSort::Relevance => 1,
+            SearchSort::Relevance => 2,
             SearchSort::Downloads => 6,
             SearchSort::Updated => 3,
-            SearchSort::Newest => 3,
-        };
-        let sort_order = match filters.sort {
-            SearchSort::Newest => "asc",
-            _ => "desc",
+            SearchSort::Newest => 11,
         };
 
         let class_id = match filters.content_type {
             ContentType::Mod => CLASS_ID_MODS,
             ContentType::Modpack => CLASS_ID_MODPACKS,
+            ContentType::ResourcePack => CLASS_ID_RESOURCE_PACKS,
+            ContentType::Shader => CLASS_ID_SHADERS,
+            ContentType::DataPack => CLASS_ID_DATA_PACKS,
+            ContentType::Plugin => CLASS_ID_PLUGINS,
         };
 
         let mut params: Vec<(&str, String)> = vec![
@@ -166,26 +212,42 @@ impl CurseForgeClient {
             ("classId", class_id.to_string()),
             ("searchFilter", filters.query.clone()),
             ("sortField", sort_field.to_string()),
-            ("sortOrder", sort_order.to_string()),
+            ("sortOrder", "desc".to_string()),
             ("index", filters.offset.to_string()),
             ("pageSize", filters.limit.min(50).to_string()),
         ];
 
-        if let Some(ref gv) = filters.game_version {
-            params.push(("gameVersion", gv.clone()));
+        // Single values use the scalar parameters, several the list ones,
+        // capped to what the API accepts
+        let game_versions = capped(
+            &filters.game_versions,
+            MAX_SEARCH_GAME_VERSIONS,
+            "game versions",
+        );
+        match game_versions {
+            [] => {}
+            [gv] => params.push(("gameVersion", gv.clone())),
+            many => params.push(("gameVersions", serde_json::to_string(many)?)),
         }
-        if let Some(ref loader) = filters.loader {
-            if let Some(loader_type) = loader_to_cf_type(loader) {
-                params.push(("modLoaderType", loader_type.to_string()));
-            }
+        let loader_types: Vec<u32> = filters
+            .loaders
+            .iter()
+            .filter_map(|l| loader_to_cf_type(l))
+            .collect();
+        match capped(&loader_types, MAX_SEARCH_LOADER_TYPES, "loaders") {
+            [] => {}
+            [t] => params.push(("modLoaderType", t.to_string())),
+            many => params.push(("modLoaderTypes", serde_json::to_string(many)?)),
         }
 
-        let response = self
+        let request = self
             .client
-            .get(format!("{BASE_URL}/v1/mods/search"))
+            .get(format!("{}/v1/mods/search", self.base_url))
             .header("x-api-key", &self.api_key)
-            .query(&params)
-            .send()
+            .query(&params);
+        let response = self
+            .cache
+            .send_limited(request, SEARCH_TTL, &self.limiter)
             .await?;
 
         if !response.status().is_success() {
@@ -195,7 +257,7 @@ impl CurseForgeClient {
             )));
         }
 
-        let cf_response: CfSearchResponse = response.json().await?;
+        let cf_response: CfSearchResponse = response.json()?;
 
         let hits = cf_response
             .data
@@ -212,11 +274,13 @@ impl CurseForgeClient {
     }
 
     pub async fn get_mod(&self, project_id: &str) -> AppResult<ModDetails> {
-        let response = self
+        let request = self
             .client
-            .get(format!("{BASE_URL}/v1/mods/{project_id}"))
-            .header("x-api-key", &self.api_key)
-            .send()
+            .get(format!("{}/v1/mods/{project_id}", self.base_url))
+            .header("x-api-key", &self.api_key);
+        let response = self
+            .cache
+            .send_limited(request, PROJECT_TTL, &self.limiter)
             .await?;
 
         if !response.status().is_success() {
@@ -226,7 +290,7 @@ impl CurseForgeClient {
             )));
         }
 
-        let cf_response: CfModResponse = response.json().await?;
+        let cf_response: CfModResponse = response.json()?;
         Ok(cf_mod_to_details(cf_response.data))
     }
 
@@ -247,12 +311,14 @@ impl CurseForgeClient {
             }
         }
 
-        let response = self
+        let request = self
             .client
-            .get(format!("{BASE_URL}/v1/mods/{project_id}/files"))
+            .get(format!("{}/v1/mods/{project_id}/files", self.base_url))
             .header("x-api-key", &self.api_key)
-            .query(&params)
-            .send()
+            .query(&params);
+        let response = self
+            .cache
+            .send_limited(request, VERSIONS_TTL, &self.limiter)
             .await?;
 
         if !response.status().is_success() {
@@ -262,16 +328,45 @@ impl CurseForgeClient {
             )));
         }
 
-        let cf_response: CfFilesResponse = response.json().await?;
+        let cf_response: CfFilesResponse = response.json()?;
 
         let versions = cf_response
             .data
             .into_iter()
-            .map(|f| cf_file_to_version(f, project_id))
+            .map(|f| cf_file_to_version(f, project_id, &self.cdn_url))
             .collect();
 
         Ok(versions)
     }
+
+    /// Fetch one file of a mod by id, whatever its game version or loader.
+    pub async fn get_version(&self, project_id: &str, file_id: &str) -> AppResult<ModVersionInfo> {
+        let request = self
+            .client
+            .get(format!(
+                "{}/v1/mods/{project_id}/files/{file_id}",
+                self.base_url
+            ))
+            .header("x-api-key", &self.api_key);
+        let response = self
+            .cache
+            .send_limited(request, PROJECT_TTL, &self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Custom(format!(
+                "CurseForge get_version failed for {project_id}/{file_id}: HTTP {}",
+                response.status()
+            )));
+        }
+
+        let cf_response: CfFileResponse = response.json()?;
+        Ok(cf_file_to_version(
+            cf_response.data,
+            project_id,
+            &self.cdn_url,
+        ))
+    }
 }
 
 // --- Batch file resolution ---
@@ -281,9 +376,11 @@ pub struct CfResolvedFile {
     pub project_id: u32,
     pub file_id: u32,
     pub file_name: String,
-    pub download_url: String,
+    /// The API's download URL first, then the CDN path built from the file id.
+    pub download_urls: Vec<String>,
     pub file_size: u64,
     pub sha1: Option<String>,
+    pub md5: Option<String>,
 }
 
 impl CurseForgeClient {
@@ -295,13 +392,12 @@ impl CurseForgeClient {
 
         for chunk in file_ids.chunks(BATCH_SIZE) {
             let body = serde_json::json!({ "fileIds": chunk });
-            let response = self
+            let request = self
                 .client
-                .post(format!("{BASE_URL}/v1/mods/files"))
+                .post(format!("{}/v1/mods/files", self.base_url))
                 .header("x-api-key", &self.api_key)
-                .json(&body)
-                .send()
-                .await?;
+                .json(&body);
+            let response = self.limiter.send(request).await?;
 
             if !response.status().is_success() {
                 return Err(AppError::Custom(format!(
@@ -313,17 +409,25 @@ impl CurseForgeClient {
             let batch: CfFilesResponse = response.json().await?;
 
             for f in batch.data {
-                let url = f
-                    .download_url
-                    .unwrap_or_else(|| build_cf_download_url(f.id, &f.file_name));
-                let sha1 = f.hashes.iter().find(|h| h.algo == 1).map(|h| h.value.clone());
+                let cdn_url = build_cf_download_url(&self.cdn_url, f.id, &f.file_name);
+                let mut download_urls: Vec<String> = f.download_url.into_iter().collect();
+                if !download_urls.contains(&cdn_url) {
+                    download_urls.push(cdn_url);
+                }
+                let hash = |algo: u32| {
+                    f.hashes
+                        .iter()
+                        .find(|h| h.algo == algo)
+                        .map(|h| h.value.clone())
+                };
                 all_files.push(CfResolvedFile {
                     project_id: 0, // Filled by caller from manifest mapping
                     file_id: f.id,
+                    sha1: hash(1),
+                    md5: hash(2),
                     file_name: f.file_name,
-                    download_url: url,
+                    download_urls,
                     file_size: f.file_length,
-                    sha1,
                 });
             }
         }
@@ -332,6 +436,107 @@ impl CurseForgeClient {
     }
 }
 
+// --- Fingerprint matching ---
+
+impl CurseForgeClient {
+    /// Look up files by their CurseForge fingerprint.
+    /// CurseForge API: POST /v1/fingerprints { fingerprints: [...] }
+    ///
+    /// Returns a map keyed by fingerprint; only exact matches are included.
+    pub async fn match_fingerprints(
+        &self,
+        fingerprints: &[u32],
+    ) -> AppResult<HashMap<u32, ModVersionInfo>> {
+        if fingerprints.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let body = serde_json::json!({ "fingerprints": fingerprints });
+        let request = self
+            .client
+            .post(format!("{}/v1/fingerprints", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .json(&body);
+        let response = self.limiter.send(request).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Custom(format!(
+                "CurseForge fingerprint match failed: HTTP {}",
+                response.status()
+            )));
+        }
+
+        let cf_response: CfFingerprintResponse = response.json().await?;
+
+        Ok(cf_response
+            .data
+            .exact_matches
+            .into_iter()
+            .map(|m| {
+                let fingerprint = m.file.file_fingerprint;
+                (
+                    fingerprint,
+                    cf_file_to_version(m.file, &m.id.to_string(), &self.cdn_url),
+                )
+            })
+            .collect())
+    }
+}
+
+/// The first `max` of `values`, warning when some are dropped.
+fn capped<'a, T>(values: &'a [T], max: usize, what: &str) -> &'a [T] {
+    if values.len() > max {
+        log::warn!(
+            "CurseForge search takes at most {max} {what}, ignoring {} of them",
+            values.len() - max
+        );
+    }
+    &values[..values.len().min(max)]
+}
+
+/// Compute the CurseForge fingerprint of a file's contents.
+///
+/// CurseForge hashes files with 32-bit MurmurHash2 (seed 1) after stripping
+/// every tab, line feed, carriage return and space byte.
+pub fn cf_fingerprint(data: &[u8]) -> u32 {
+    let normalized: Vec<u8> = data
+        .iter()
+        .copied()
+        .filter(|b| !matches!(b, b'\t' | b'\n' | b'\r' | b' '))
+        .collect();
+    murmur2(&normalized, 1)
+}
+
+fn murmur2(data: &[u8], seed: u32) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+    const R: u32 = 24;
+
+    let mut h = seed ^ data.len() as u32;
+
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, b) in tail.iter().enumerate() {
+            h ^= (*b as u32) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
 // --- Converters ---
 
 fn cf_mod_to_search_result(m: CfMod) -> ModSearchResult {
@@ -397,7 +602,7 @@ fn cf_mod_to_details(m: CfMod) -> ModDetails {
     }
 }
 
-fn cf_file_to_version(f: CfFile, project_id: &str) -> ModVersionInfo {
+fn cf_file_to_version(f: CfFile, project_id: &str, cdn_url: &str) -> ModVersionInfo {
     let mut hashes = HashMap::new();
     for h in &f.hashes {
         let algo = match h.algo {
@@ -410,7 +615,7 @@ fn cf_file_to_version(f: CfFile, project_id: &str) -> ModVersionInfo {
 
     let download_url = f
         .download_url
-        .unwrap_or_else(|| build_cf_download_url(f.id, &f.file_name));
+        .unwrap_or_else(|| build_cf_download_url(cdn_url, f.id, &f.file_name));
 
     let file = ModVersionFile {
         url: download_url,
@@ -444,6 +649,8 @@ fn cf_file_to_version(f: CfFile, project_id: &str) -> ModVersionInfo {
             Some(ModDependency {
                 project_id: d.mod_id.to_string(),
                 dependency_type,
+                version_id: None,
+                file_name: None,
             })
         })
         .collect();
@@ -459,6 +666,11 @@ fn cf_file_to_version(f: CfFile, project_id: &str) -> ModVersionInfo {
         dependencies,
         date_published: f.file_date,
         source: ModSource::CurseForge,
+        channel: match f.release_type {
+            2 => ReleaseChannel::Beta,
+            3 => ReleaseChannel::Alpha,
+            _ => ReleaseChannel::Release,
+        },
     }
 }
 
@@ -521,8 +733,88 @@ fn cf_type_to_loader(t: u32) -> String {
 }
 
 /// Build the download URL when CurseForge returns null
-pub fn build_cf_download_url(file_id: u32, file_name: &str) -> String {
+pub fn build_cf_download_url(cdn_url: &str, file_id: u32, file_name: &str) -> String {
     let segment1 = file_id / 1000;
     let segment2 = file_id % 1000;
-    format!("{CDN_BASE}/{segment1}/{segment2}/{file_name}")
+    format!("{cdn_url}/{segment1}/{segment2}/{file_name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{canned_server, http_response};
+
+    #[test]
+    fn murmur2_matches_reference_vectors() {
+        // Published vectors for MurmurHash2 with seed 3242157231
+        const SEED: u32 = 3_242_157_231;
+        let vectors: [(&str, u32); 7] = [
+            ("", 3_632_506_080),
+            ("a", 455_683_869),
+            ("ab", 2_448_092_234),
+            ("abc", 2_066_295_634),
+            ("abcd", 2_588_571_162),
+            ("abcde", 2_988_696_942),
+            ("abcdefghijklmnop", 2_350_868_870),
+        ];
+        for (input, expected) in vectors {
+            assert_eq!(murmur2(input.as_bytes(), SEED), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_whitespace() {
+        assert_eq!(cf_fingerprint(b"a b\tc\r\nd "), murmur2(b"abcd", 1));
+        assert_eq!(cf_fingerprint(b" \n"), cf_fingerprint(b""));
+        assert_ne!(cf_fingerprint(b"abcd"), cf_fingerprint(b"abce"));
+    }
+
+    #[test]
+    fn fingerprint_matches_curseforge_for_blank_files() {
+        // CurseForge reports 1540447798 for every empty or whitespace-only
+        // file; any other seed, or whitespace left in, gives another value
+        assert_eq!(cf_fingerprint(b""), 1_540_447_798);
+        assert_eq!(cf_fingerprint(b"\r\n\t  \n"), 1_540_447_798);
+        assert_ne!(murmur2(b"\r\n\t  \n", 1), 1_540_447_798);
+        assert_ne!(murmur2(b"", 0), 1_540_447_798);
+    }
+
+    #[tokio::test]
+    async fn search_caps_list_filters_to_the_api_limits() -> AppResult<()> {
+        let server = canned_server(vec![http_response(
+            "200 OK",
+            br#"{"data":[],"pagination":{"totalCount":0,"index":0,"pageSize":20}}"#,
+        )]);
+        let cache = HttpCache::new(
+            std::env::temp_dir().join(format!("minesync_http_{}", uuid::Uuid::new_v4())),
+        );
+        let client =
+            CurseForgeClient::new("key".to_string(), &Endpoints::all_at(&server.base), cache);
+        let game_versions: Vec<String> = (16..22).map(|minor| format!("1.{minor}")).collect();
+
+        client
+            .search_mods(&SearchFilters {
+                query: String::new(),
+                game_versions,
+                loaders: vec!["forge".to_string(), "fabric".to_string()],
+                categories: Vec::new(),
+                environment: None,
+                sort: SearchSort::Relevance,
+                offset: 0,
+                limit: 20,
+                content_type: ContentType::Mod,
+            })
+            .await?;
+
+        let request = server.requests()[0].clone();
+        // ["1.16","1.17","1.18","1.19"], without 1.20 and 1.21
+        assert!(
+            request
+                .contains("gameVersions=%5B%221.16%22%2C%221.17%22%2C%221.18%22%2C%221.19%22%5D&"),
+            "{request}"
+        );
+        assert!(request.contains("modLoaderTypes="), "{request}");
+        Ok(())
+    }
 }
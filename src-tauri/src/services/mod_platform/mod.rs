@@ -1,13 +1,17 @@
 pub mod curseforge;
 pub mod modrinth;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::errors::AppResult;
+use crate::models::endpoints::Endpoints;
+use crate::models::instance::{MinecraftInstance, ModLoader};
 use crate::models::mod_info::ModSource;
 use crate::models::mod_platform::{
-    DependencyType, ModDetails, ModSearchResult, ModVersionInfo, SearchFilters, SearchResponse,
+    DependencyType, ModDetails, ModSearchResult, ModVersionInfo, ReleaseChannel, SearchFilters,
+    SearchResponse, SearchSort, VersionIncompatibility,
 };
+use crate::services::http_cache::HttpCache;
 
 use self::curseforge::CurseForgeClient;
 use self::modrinth::ModrinthClient;
@@ -88,9 +92,15 @@ impl UnifiedModClient {
     /// Create a new unified client.
     ///
     /// CurseForge requires an API key; if `None` is passed, only Modrinth is queried.
-    pub fn new(curseforge_api_key: Option<String>) -> Self {
-        let curseforge = curseforge_api_key.map(CurseForgeClient::new);
-        let modrinth = ModrinthClient::new();
+    /// Both platforms share `cache` for metadata requests.
+    pub fn new(
+        curseforge_api_key: Option<String>,
+        endpoints: &Endpoints,
+        cache: HttpCache,
+    ) -> Self {
+        let curseforge =
+            curseforge_api_key.map(|key| CurseForgeClient::new(key, endpoints, cache.clone()));
+        let modrinth = ModrinthClient::new(endpoints, cache);
         Self {
             curseforge,
             modrinth,
@@ -98,11 +108,19 @@ impl UnifiedModClient {
     }
 
     /// Search both platforms in parallel, merge and deduplicate results by slug.
+    ///
+    /// Each platform is paged with the same offset and limit, so a page holds
+    /// the same rank window from both and paging never skips or repeats a
+    /// platform's results. The merged page follows `filters.sort`.
+    ///
+    /// CurseForge cannot filter by Modrinth category or environment, so only
+    /// Modrinth is searched when either is set rather than mixing in
+    /// unfiltered hits.
     pub async fn search_mods(&self, filters: &SearchFilters) -> AppResult<SearchResponse> {
         let mr_future = self.modrinth.search_mods(filters);
 
         match &self.curseforge {
-            Some(cf) => {
+            Some(cf) if curseforge_supports(filters) => {
                 let cf_future = cf.search_mods(filters);
                 let (mr_result, cf_result) = tokio::join!(mr_future, cf_future);
 
@@ -124,12 +142,9 @@ impl UnifiedModClient {
                 );
 
                 let total_hits = mr.total_hits + cf.total_hits;
-                let merged = deduplicate_results(mr.hits, cf.hits);
+                let merged = merge_results(mr.hits, cf.hits, &filters.sort);
 
-                log::info!(
-                    "[UnifiedModClient] After dedup: {} results",
-                    merged.len()
-                );
+                log::info!("[UnifiedModClient] After dedup: {} results", merged.len());
 
                 Ok(SearchResponse {
                     hits: merged,
@@ -138,7 +153,7 @@ impl UnifiedModClient {
                     limit: filters.limit,
                 })
             }
-            None => self.modrinth.search_mods(filters).await,
+            _ => mr_future.await,
         }
     }
 
@@ -184,6 +199,27 @@ impl UnifiedModClient {
         }
     }
 
+    /// Get one specific version (Modrinth version id / CurseForge file id).
+    pub async fn get_version(
+        &self,
+        source: &ModSource,
+        project_id: &str,
+        version_id: &str,
+    ) -> AppResult<ModVersionInfo> {
+        match source {
+            ModSource::CurseForge => {
+                let cf = self.curseforge.as_ref().ok_or_else(|| {
+                    crate::errors::AppError::Custom("CurseForge API key not configured".to_string())
+                })?;
+                cf.get_version(project_id, version_id).await
+            }
+            ModSource::Modrinth => self.modrinth.get_version(version_id).await,
+            ModSource::Local => Err(crate::errors::AppError::Custom(
+                "Cannot fetch versions for local mods".to_string(),
+            )),
+        }
+    }
+
     /// Resolve CurseForge file IDs in batch via the CF API.
     pub async fn get_cf_files_batch(
         &self,
@@ -195,14 +231,76 @@ impl UnifiedModClient {
         cf.get_files_batch(file_ids).await
     }
 
+    /// Identify files on Modrinth by their SHA-512 hashes.
+    pub async fn identify_modrinth_files(
+        &self,
+        sha512_hashes: &[String],
+    ) -> AppResult<HashMap<String, ModVersionInfo>> {
+        self.modrinth
+            .get_versions_by_hashes(sha512_hashes, "sha512")
+            .await
+    }
+
+    /// Modrinth projects by id, fetched in bulk.
+    pub async fn get_modrinth_projects(
+        &self,
+        project_ids: &[String],
+    ) -> AppResult<Vec<ModDetails>> {
+        self.modrinth.get_projects(project_ids).await
+    }
+
+    /// Modrinth versions by id, fetched in bulk.
+    pub async fn get_modrinth_versions(
+        &self,
+        version_ids: &[String],
+    ) -> AppResult<Vec<ModVersionInfo>> {
+        self.modrinth.get_versions_by_ids(version_ids).await
+    }
+
+    /// Latest Modrinth version per installed file (SHA-1), for the given
+    /// Minecraft version and loader.
+    pub async fn latest_modrinth_versions(
+        &self,
+        sha1_hashes: &[String],
+        game_version: &str,
+        loader: &str,
+    ) -> AppResult<HashMap<String, ModVersionInfo>> {
+        self.modrinth
+            .get_latest_versions_by_hashes(
+                sha1_hashes,
+                "sha1",
+                &[loader.to_string()],
+                &[game_version.to_string()],
+            )
+            .await
+    }
+
+    /// Identify files on CurseForge by their fingerprints.
+    ///
+    /// Without an API key nothing can be matched, so an empty map is returned.
+    pub async fn identify_curseforge_files(
+        &self,
+        fingerprints: &[u32],
+    ) -> AppResult<HashMap<u32, ModVersionInfo>> {
+        match &self.curseforge {
+            Some(cf) => cf.match_fingerprints(fingerprints).await,
+            None => Ok(HashMap::new()),
+        }
+    }
+
     /// Resolve all required dependencies for a given version, recursively.
     ///
+    /// Each dependency resolves to its newest version on `min_channel` or a
+    /// more stable one; if it has none, the newest version of any channel is
+    /// used rather than leaving the dependency out.
+    ///
     /// Returns a flat list of all transitive required dependencies.
     pub async fn resolve_dependencies(
         &self,
         version: &ModVersionInfo,
         game_version: Option<&str>,
         loader: Option<&str>,
+        min_channel: ReleaseChannel,
     ) -> AppResult<Vec<ModVersionInfo>> {
         let mut resolved: Vec<ModVersionInfo> = Vec::new();
         let mut visited: HashSet<String> = HashSet::new();
@@ -226,14 +324,15 @@ impl UnifiedModClient {
                 .await
             {
                 Ok(v) => v,
+                // An incomplete list would look like success; let the caller retry
+                Err(e @ crate::errors::AppError::RateLimited(_)) => return Err(e),
                 Err(e) => {
                     log::warn!("Failed to resolve dependency {project_id}: {e}");
                     continue;
                 }
             };
 
-            // Pick the first (latest) compatible version
-            if let Some(best) = versions.into_iter().next() {
+            if let Some(best) = pick_version(versions, min_channel) {
                 // Enqueue transitive dependencies
                 for dep in &best.dependencies {
                     if matches!(dep.dependency_type, DependencyType::Required)
@@ -252,6 +351,78 @@ impl UnifiedModClient {
 
 // --- Helpers ---
 
+/// Newest version (platforms list newest first) acceptable for `min_channel`,
+/// falling back to the newest overall.
+pub fn pick_version(
+    versions: Vec<ModVersionInfo>,
+    min_channel: ReleaseChannel,
+) -> Option<ModVersionInfo> {
+    match versions
+        .iter()
+        .position(|v| v.channel.satisfies(min_channel))
+    {
+        Some(index) => versions.into_iter().nth(index),
+        None => {
+            let newest = versions.into_iter().next()?;
+            log::warn!(
+                "No {min_channel} version of {}, using {} ({})",
+                newest.project_id,
+                newest.version_number,
+                newest.channel
+            );
+            Some(newest)
+        }
+    }
+}
+
+/// Platform loader tags whose mods load on `loader`, matching the descriptor
+/// formats of [`formats_read_by`](crate::services::jar_metadata::formats_read_by).
+pub fn loaders_accepted_by(loader: &ModLoader) -> &'static [&'static str] {
+    match loader {
+        ModLoader::Fabric => &["fabric"],
+        ModLoader::Quilt => &["quilt", "fabric"],
+        ModLoader::Forge => &["forge"],
+        ModLoader::NeoForge => &["neoforge", "forge"],
+        ModLoader::Vanilla => &[],
+    }
+}
+
+/// Check a version against an instance's Minecraft version and loader. An
+/// empty list on the version means the platform did not say, and is trusted.
+pub fn find_incompatibility(
+    version: &ModVersionInfo,
+    instance: &MinecraftInstance,
+) -> Option<VersionIncompatibility> {
+    let game_version_supported = version.game_versions.is_empty()
+        || version.game_versions.contains(&instance.minecraft_version);
+    let accepted = loaders_accepted_by(&instance.loader);
+    let loader_supported = instance.loader != ModLoader::Vanilla
+        && (version.loaders.is_empty()
+            || version
+                .loaders
+                .iter()
+                .any(|l| accepted.contains(&l.to_lowercase().as_str())));
+
+    if game_version_supported && loader_supported {
+        return None;
+    }
+    Some(VersionIncompatibility {
+        version_id: version.id.clone(),
+        version_name: version.name.clone(),
+        minecraft_version: instance.minecraft_version.clone(),
+        loader: instance.loader.clone(),
+        game_versions: version.game_versions.clone(),
+        loaders: version.loaders.clone(),
+        game_version_supported,
+        loader_supported,
+    })
+}
+
+/// Whether CurseForge can apply every filter of a search.
+fn curseforge_supports(filters: &SearchFilters) -> bool {
+    filters.categories.is_empty() && filters.environment.is_none()
+}
+
 fn empty_response(filters: &SearchFilters) -> SearchResponse {
     SearchResponse {
         hits: Vec::new(),
@@ -261,34 +432,213 @@ fn empty_response(filters: &SearchFilters) -> SearchResponse {
     }
 }
 
-/// Deduplicate results from two platforms by slug.
+/// Merge two platforms' result pages, dropping CurseForge hits whose slug
+/// Modrinth already returned.
 ///
-/// If the same mod exists on both platforms, prefer the Modrinth version
-/// (it has better metadata — body, loaders, etc.).
-fn deduplicate_results(
+/// Relevance scores are not comparable across platforms, so for relevance
+/// the pages are interleaved rank by rank; other sorts compare the field.
+/// Ties keep the interleaved order, making the merge deterministic.
+fn merge_results(
     modrinth: Vec<ModSearchResult>,
     curseforge: Vec<ModSearchResult>,
+    sort: &SearchSort,
 ) -> Vec<ModSearchResult> {
-    let mut seen_slugs: HashSet<String> = HashSet::new();
-    let mut merged: Vec<ModSearchResult> = Vec::new();
-
-    // Modrinth results first (preferred)
-    for hit in modrinth {
-        let slug = hit.slug.to_lowercase();
-        seen_slugs.insert(slug);
-        merged.push(hit);
+    let modrinth_slugs: HashSet<String> = modrinth.iter().map(|h| h.slug.to_lowercase()).collect();
+    let curseforge: Vec<ModSearchResult> = curseforge
+        .into_iter()
+        .filter(|h| !modrinth_slugs.contains(&h.slug.to_lowercase()))
+        .collect();
+
+    let mut merged = Vec::with_capacity(modrinth.len() + curseforge.len());
+    let mut mr = modrinth.into_iter();
+    let mut cf = curseforge.into_iter();
+    loop {
+        match (mr.next(), cf.next()) {
+            (None, None) => break,
+            (a, b) => merged.extend(a.into_iter().chain(b)),
+        }
     }
 
-    // CurseForge results, skip duplicates
-    for hit in curseforge {
-        let slug = hit.slug.to_lowercase();
-        if !seen_slugs.contains(&slug) {
-            seen_slugs.insert(slug);
-            merged.push(hit);
+    match sort {
+        SearchSort::Relevance => {}
+        SearchSort::Downloads => merged.sort_by(|a, b| b.downloads.cmp(&a.downloads)),
+        SearchSort::Updated => {
+            merged.sort_by_key(|h| std::cmp::Reverse(parse_date(&h.date_updated)))
+        }
+        SearchSort::Newest => {
+            merged.sort_by_key(|h| std::cmp::Reverse(parse_date(&h.date_created)))
         }
     }
-
-    // Sort merged results by downloads descending
-    merged.sort_by(|a, b| b.downloads.cmp(&a.downloads));
     merged
 }
+
+/// Platforms format timestamps differently; unparseable ones sort last.
+fn parse_date(date: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    chrono::DateTime::parse_from_rfc3339(date).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    use crate::models::mod_info::ModEnvironment;
+    use crate::models::mod_platform::ContentType;
+    use crate::test_support::{test_instance, test_version};
+
+    fn version(id: &str, channel: ReleaseChannel) -> ModVersionInfo {
+        ModVersionInfo {
+            game_versions: vec!["1.21.1".to_string()],
+            channel,
+            ..test_version(id, "P7dR8mSH")
+        }
+    }
+
+    #[test]
+    fn pick_version_honors_min_channel_with_fallback() {
+        let versions = || {
+            vec![
+                version("0.3.0-alpha", ReleaseChannel::Alpha),
+                version("0.2.0-beta", ReleaseChannel::Beta),
+                version("0.1.0", ReleaseChannel::Release),
+            ]
+        };
+
+        let pick = |min| pick_version(versions(), min).map(|v| v.id);
+        assert_eq!(pick(ReleaseChannel::Release).as_deref(), Some("0.1.0"));
+        assert_eq!(pick(ReleaseChannel::Beta).as_deref(), Some("0.2.0-beta"));
+        assert_eq!(pick(ReleaseChannel::Alpha).as_deref(), Some("0.3.0-alpha"));
+
+        let alpha_only = vec![version("0.3.0-alpha", ReleaseChannel::Alpha)];
+        assert_eq!(
+            pick_version(alpha_only, ReleaseChannel::Release).map(|v| v.id),
+            Some("0.3.0-alpha".to_string())
+        );
+        assert!(pick_version(Vec::new(), ReleaseChannel::Release).is_none());
+    }
+
+    #[test]
+    fn curseforge_is_skipped_for_filters_it_cannot_apply() {
+        let filters = SearchFilters {
+            query: "map".to_string(),
+            game_versions: vec!["1.20.1".to_string()],
+            loaders: vec!["fabric".to_string()],
+            categories: Vec::new(),
+            environment: None,
+            sort: SearchSort::Relevance,
+            offset: 0,
+            limit: 20,
+            content_type: ContentType::Mod,
+        };
+        assert!(curseforge_supports(&filters));
+
+        let categories = SearchFilters {
+            categories: vec!["utility".to_string()],
+            ..filters.clone()
+        };
+        assert!(!curseforge_supports(&categories));
+
+        let environment = SearchFilters {
+            environment: Some(ModEnvironment::Client),
+            ..filters
+        };
+        assert!(!curseforge_supports(&environment));
+    }
+
+    #[test]
+    fn compatibility_checks_game_version_and_loader() {
+        let mut instance = MinecraftInstance {
+            minecraft_version: "1.21.1".to_string(),
+            loader: ModLoader::Quilt,
+            loader_version: None,
+            ..test_instance(Path::new(""))
+        };
+        let fabric = version("1.0.0", ReleaseChannel::Release);
+
+        // Quilt loads Fabric mods
+        assert!(find_incompatibility(&fabric, &instance).is_none());
+
+        instance.loader = ModLoader::Forge;
+        instance.minecraft_version = "1.19.2".to_string();
+        let err = find_incompatibility(&fabric, &instance).expect("incompatible");
+        assert!(!err.loader_supported);
+        assert!(!err.game_version_supported);
+
+        // NeoForge loads Forge mods, as it reads their descriptors
+        instance.loader = ModLoader::NeoForge;
+        instance.minecraft_version = "1.20.1".to_string();
+        let forge = ModVersionInfo {
+            loaders: vec!["forge".to_string()],
+            game_versions: vec!["1.20.1".to_string()],
+            ..fabric.clone()
+        };
+        assert!(find_incompatibility(&forge, &instance).is_none());
+
+        // Unknown targets are trusted
+        let mut untagged = fabric.clone();
+        untagged.game_versions.clear();
+        untagged.loaders.clear();
+        assert!(find_incompatibility(&untagged, &instance).is_none());
+    }
+
+    fn hit(slug: &str, source: ModSource, downloads: u64, created: &str) -> ModSearchResult {
+        ModSearchResult {
+            id: slug.to_string(),
+            slug: slug.to_string(),
+            name: slug.to_string(),
+            description: String::new(),
+            author: String::new(),
+            downloads,
+            icon_url: None,
+            source,
+            game_versions: Vec::new(),
+            loaders: Vec::new(),
+            date_updated: created.to_string(),
+            date_created: created.to_string(),
+        }
+    }
+
+    #[test]
+    fn merge_keeps_relevance_rank_and_sorts_other_orders() {
+        let mr = || {
+            vec![
+                hit("sodium", ModSource::Modrinth, 10, "2021-01-01T00:00:00Z"),
+                hit(
+                    "lithium",
+                    ModSource::Modrinth,
+                    500,
+                    "2023-06-01T00:00:00.123456Z",
+                ),
+            ]
+        };
+        let cf = || {
+            vec![
+                hit("jei", ModSource::CurseForge, 900, "2019-01-01T00:00:00.5Z"),
+                hit(
+                    "Sodium",
+                    ModSource::CurseForge,
+                    1000,
+                    "2021-01-01T00:00:00Z",
+                ),
+                hit("create", ModSource::CurseForge, 50, "2024-01-01T00:00:00Z"),
+            ]
+        };
+        let slugs = |hits: Vec<ModSearchResult>| -> Vec<String> {
+            hits.into_iter().map(|h| h.slug).collect()
+        };
+
+        // Interleaved by rank, the CurseForge duplicate dropped
+        assert_eq!(
+            slugs(merge_results(mr(), cf(), &SearchSort::Relevance)),
+            vec!["sodium", "jei", "lithium", "create"]
+        );
+        assert_eq!(
+            slugs(merge_results(mr(), cf(), &SearchSort::Downloads)),
+            vec!["jei", "lithium", "create", "sodium"]
+        );
+        assert_eq!(
+            slugs(merge_results(mr(), cf(), &SearchSort::Newest)),
+            vec!["create", "lithium", "sodium", "jei"]
+        );
+    }
+}
@@ -3,17 +3,25 @@ use std::collections::HashMap;
 use serde::Deserialize;
 
 use crate::errors::{AppError, AppResult};
-use crate::models::mod_info::ModSource;
+use crate::models::endpoints::Endpoints;
+use crate::models::mod_info::{ModEnvironment, ModSource};
 use crate::models::mod_platform::{
     ContentType, DependencyType, ModDependency, ModDetails, ModSearchResult, ModVersionFile,
-    ModVersionInfo, SearchFilters, SearchResponse, SearchSort,
+    ModVersionInfo, ReleaseChannel, SearchFilters, SearchResponse, SearchSort,
 };
+use crate::services::http_cache::{HttpCache, PROJECT_TTL, SEARCH_TTL, VERSIONS_TTL};
+use crate::services::rate_limit::RateLimiter;
 
-const BASE_URL: &str = "https://api.modrinth.com/v2";
 const USER_AGENT: &str = "MineSync/1.0.0 (contact@minesync.dev)";
 
+/// Ids per bulk `?ids=` request, keeping URLs well under server limits.
+const BULK_IDS_PER_REQUEST: usize = 100;
+
 pub struct ModrinthClient {
     client: reqwest::Client,
+    cache: HttpCache,
+    limiter: RateLimiter,
+    base_url: String,
 }
 
 // --- Modrinth API response types ---
@@ -71,6 +79,8 @@ struct MrVersion {
     files: Vec<MrFile>,
     dependencies: Vec<MrDependency>,
     date_published: String,
+    #[serde(default)]
+    version_type: String,
 }
 
 #[derive(Deserialize)]
@@ -91,11 +101,15 @@ struct MrHashes {
 #[derive(Deserialize)]
 struct MrDependency {
     project_id: Option<String>,
+    version_id: Option<String>,
+    file_name: Option<String>,
     dependency_type: String,
 }
 
 #[derive(Deserialize)]
 struct MrTeamMember {
+    #[serde(default)]
+    team_id: String,
     user: MrUser,
     role: String,
 }
@@ -106,13 +120,18 @@ struct MrUser {
 }
 
 impl ModrinthClient {
-    pub fn new() -> Self {
+    pub fn new(endpoints: &Endpoints, cache: HttpCache) -> Self {
         let client = reqwest::Client::builder()
             .user_agent(USER_AGENT)
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            cache,
+            limiter: RateLimiter::new("Modrinth"),
+            base_url: endpoints.modrinth_api.clone(),
+        }
     }
 
     pub async fn search_mods(&self, filters: &SearchFilters) -> AppResult<SearchResponse> {
@@ -123,12 +142,7 @@ impl ModrinthClient {
             SearchSort::Newest => "newest",
         };
 
-        let facets = build_facets(
-            &filters.content_type,
-            filters.game_version.as_deref(),
-            filters.loader.as_deref(),
-            filters.category.as_deref(),
-        );
+        let facets = build_facets(filters);
 
         let mut params: Vec<(&str, String)> = vec![
             ("query", filters.query.clone()),
@@ -141,11 +155,13 @@ impl ModrinthClient {
             params.push(("facets", facets));
         }
 
-        let response = self
+        let request = self
             .client
-            .get(format!("{BASE_URL}/search"))
-            .query(&params)
-            .send()
+            .get(format!("{}/search", self.base_url))
+            .query(&params);
+        let response = self
+            .cache
+            .send_limited(request, SEARCH_TTL, &self.limiter)
             .await?;
 
         if !response.status().is_success() {
@@ -155,7 +171,7 @@ impl ModrinthClient {
             )));
         }
 
-        let mr_response: MrSearchResponse = response.json().await?;
+        let mr_response: MrSearchResponse = response.json()?;
 
         let hits = mr_response
             .hits
@@ -172,10 +188,12 @@ impl ModrinthClient {
     }
 
     pub async fn get_mod(&self, project_id: &str) -> AppResult<ModDetails> {
-        let response = self
+        let request = self
             .client
-            .get(format!("{BASE_URL}/project/{project_id}"))
-            .send()
+            .get(format!("{}/project/{project_id}", self.base_url));
+        let response = self
+            .cache
+            .send_limited(request, PROJECT_TTL, &self.limiter)
             .await?;
 
         if !response.status().is_success() {
@@ -185,7 +203,7 @@ impl ModrinthClient {
             )));
         }
 
-        let project: MrProject = response.json().await?;
+        let project: MrProject = response.json()?;
 
         // Fetch team members to get the author name
         let author = self.fetch_author(&project.team).await.unwrap_or_default();
@@ -208,11 +226,13 @@ impl ModrinthClient {
             params.push(("loaders", format!("[\"{l}\"]")));
         }
 
-        let response = self
+        let request = self
             .client
-            .get(format!("{BASE_URL}/project/{project_id}/version"))
-            .query(&params)
-            .send()
+            .get(format!("{}/project/{project_id}/version", self.base_url))
+            .query(&params);
+        let response = self
+            .cache
+            .send_limited(request, VERSIONS_TTL, &self.limiter)
             .await?;
 
         if !response.status().is_success() {
@@ -222,36 +242,215 @@ impl ModrinthClient {
             )));
         }
 
-        let versions: Vec<MrVersion> = response.json().await?;
+        let versions: Vec<MrVersion> = response.json()?;
 
         let result = versions.into_iter().map(mr_version_to_info).collect();
 
         Ok(result)
     }
 
-    async fn fetch_author(&self, team_id: &str) -> AppResult<String> {
+    /// Fetch one version by id, whatever its game version or loader.
+    pub async fn get_version(&self, version_id: &str) -> AppResult<ModVersionInfo> {
+        let request = self
+            .client
+            .get(format!("{}/version/{version_id}", self.base_url));
         let response = self
+            .cache
+            .send_limited(request, PROJECT_TTL, &self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Custom(format!(
+                "Modrinth get_version failed for {version_id}: HTTP {}",
+                response.status()
+            )));
+        }
+
+        let version: MrVersion = response.json()?;
+        Ok(mr_version_to_info(version))
+    }
+
+    /// Fetch many projects at once, in no particular order; unknown ids are
+    /// absent.
+    /// Modrinth API: GET /projects?ids=[...] and GET /teams?ids=[...]
+    pub async fn get_projects(&self, project_ids: &[String]) -> AppResult<Vec<ModDetails>> {
+        let mut projects: Vec<MrProject> = Vec::new();
+        for chunk in project_ids.chunks(BULK_IDS_PER_REQUEST) {
+            projects.extend(self.get_bulk::<MrProject>("projects", chunk).await?);
+        }
+
+        // Authors come from one bulk team lookup instead of one per project
+        let team_ids: Vec<String> = projects.iter().map(|p| p.team.clone()).collect();
+        let mut authors: HashMap<String, String> = HashMap::new();
+        for chunk in team_ids.chunks(BULK_IDS_PER_REQUEST) {
+            match self.get_bulk::<Vec<MrTeamMember>>("teams", chunk).await {
+                Ok(teams) => {
+                    for members in teams {
+                        if let Some(team_id) = members.first().map(|m| m.team_id.clone()) {
+                            authors.insert(team_id, pick_author(&members));
+                        }
+                    }
+                }
+                Err(e) => log::warn!("Modrinth bulk team lookup failed: {e}"),
+            }
+        }
+
+        Ok(projects
+            .into_iter()
+            .map(|p| {
+                let author = authors.get(&p.team).cloned().unwrap_or_default();
+                mr_project_to_details(p, author)
+            })
+            .collect())
+    }
+
+    /// Fetch many versions at once; unknown ids are absent.
+    /// Modrinth API: GET /versions?ids=[...]
+    pub async fn get_versions_by_ids(
+        &self,
+        version_ids: &[String],
+    ) -> AppResult<Vec<ModVersionInfo>> {
+        let mut versions = Vec::new();
+        for chunk in version_ids.chunks(BULK_IDS_PER_REQUEST) {
+            let found = self.get_bulk::<MrVersion>("versions", chunk).await?;
+            versions.extend(found.into_iter().map(mr_version_to_info));
+        }
+        Ok(versions)
+    }
+
+    /// GET `/{endpoint}?ids=[...]` for one chunk of ids.
+    async fn get_bulk<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        ids: &[String],
+    ) -> AppResult<Vec<T>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let request = self
             .client
-            .get(format!("{BASE_URL}/team/{team_id}/members"))
-            .send()
+            .get(format!("{}/{endpoint}", self.base_url))
+            .query(&[("ids", serde_json::to_string(ids)?)]);
+        let response = self
+            .cache
+            .send_limited(request, PROJECT_TTL, &self.limiter)
             .await?;
 
         if !response.status().is_success() {
-            return Ok(String::new());
+            return Err(AppError::Custom(format!(
+                "Modrinth {endpoint} lookup failed: HTTP {}",
+                response.status()
+            )));
         }
 
-        let members: Vec<MrTeamMember> = response.json().await?;
+        response.json()
+    }
 
-        // The owner is the primary author
-        let author = members
-            .iter()
-            .find(|m| m.role == "Owner")
-            .or_else(|| members.first())
-            .map(|m| m.user.username.clone())
-            .unwrap_or_default();
+    /// Identify files by hash in a single request.
+    /// Modrinth API: POST /version_files { hashes: [...], algorithm }
+    ///
+    /// Returns a map keyed by the submitted hash; unknown hashes are absent.
+    pub async fn get_versions_by_hashes(
+        &self,
+        hashes: &[String],
+        algorithm: &str,
+    ) -> AppResult<HashMap<String, ModVersionInfo>> {
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let body = serde_json::json!({ "hashes": hashes, "algorithm": algorithm });
+        let request = self
+            .client
+            .post(format!("{}/version_files", self.base_url))
+            .json(&body);
+        let response = self.limiter.send(request).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Custom(format!(
+                "Modrinth version_files failed: HTTP {}",
+                response.status()
+            )));
+        }
+
+        let versions: HashMap<String, MrVersion> = response.json().await?;
 
-        Ok(author)
+        Ok(versions
+            .into_iter()
+            .map(|(hash, v)| (hash, mr_version_to_info(v)))
+            .collect())
     }
+
+    /// Latest compatible version for each installed file, in a single request.
+    /// Modrinth API: POST /version_files/update { hashes, algorithm, loaders, game_versions }
+    ///
+    /// Returns a map keyed by the submitted hash; hashes without a compatible
+    /// version are absent. Modrinth ignores release channels here.
+    pub async fn get_latest_versions_by_hashes(
+        &self,
+        hashes: &[String],
+        algorithm: &str,
+        loaders: &[String],
+        game_versions: &[String],
+    ) -> AppResult<HashMap<String, ModVersionInfo>> {
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let body = serde_json::json!({
+            "hashes": hashes,
+            "algorithm": algorithm,
+            "loaders": loaders,
+            "game_versions": game_versions,
+        });
+        let request = self
+            .client
+            .post(format!("{}/version_files/update", self.base_url))
+            .json(&body);
+        let response = self.limiter.send(request).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Custom(format!(
+                "Modrinth version_files/update failed: HTTP {}",
+                response.status()
+            )));
+        }
+
+        let versions: HashMap<String, MrVersion> = response.json().await?;
+
+        Ok(versions
+            .into_iter()
+            .map(|(hash, v)| (hash, mr_version_to_info(v)))
+            .collect())
+    }
+
+    async fn fetch_author(&self, team_id: &str) -> AppResult<String> {
+        let request = self
+            .client
+            .get(format!("{}/team/{team_id}/members", self.base_url));
+        let response = self
+            .cache
+            .send_limited(request, PROJECT_TTL, &self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            return Ok(String::new());
+        }
+
+        let members: Vec<MrTeamMember> = response.json()?;
+        Ok(pick_author(&members))
+    }
+}
+
+/// The owner is the primary author.
+fn pick_author(members: &[MrTeamMember]) -> String {
+    members
+        .iter()
+        .find(|m| m.role == "Owner")
+        .or_else(|| members.first())
+        .map(|m| m.user.username.clone())
+        .unwrap_or_default()
 }
 
 // --- Converters ---
@@ -334,6 +533,8 @@ fn mr_version_to_info(v: MrVersion) -> ModVersionInfo {
             Some(ModDependency {
                 project_id,
                 dependency_type,
+                version_id: d.version_id,
+                file_name: d.file_name,
             })
         })
         .collect();
@@ -349,6 +550,11 @@ fn mr_version_to_info(v: MrVersion) -> ModVersionInfo {
         dependencies,
         date_published: v.date_published,
         source: ModSource::Modrinth,
+        channel: match v.version_type.as_str() {
+            "beta" => ReleaseChannel::Beta,
+            "alpha" => ReleaseChannel::Alpha,
+            _ => ReleaseChannel::Release,
+        },
     }
 }
 
@@ -357,30 +563,39 @@ fn mr_version_to_info(v: MrVersion) -> ModVersionInfo {
 /// Build Modrinth facets filter string
 ///
 /// Facets use AND between groups, OR within groups:
-/// `[["project_type:mod"],["versions:1.20.1"],["categories:fabric"]]`
-fn build_facets(
-    content_type: &ContentType,
-    game_version: Option<&str>,
-    loader: Option<&str>,
-    category: Option<&str>,
-) -> String {
-    let project_type = match content_type {
+/// `[["project_type:mod"],["versions:1.20.1","versions:1.21"],["categories:fabric"]]`
+fn build_facets(filters: &SearchFilters) -> String {
+    let project_type = match filters.content_type {
         ContentType::Mod => "mod",
         ContentType::Modpack => "modpack",
+        ContentType::ResourcePack => "resourcepack",
+        ContentType::Shader => "shader",
+        ContentType::DataPack => "datapack",
+        ContentType::Plugin => "plugin",
     };
-    let mut groups: Vec<String> = vec![format!("[\"project_type:{project_type}\"]")];
+    let mut groups: Vec<Vec<String>> = vec![vec![format!("project_type:{project_type}")]];
 
-    if let Some(gv) = game_version {
-        groups.push(format!("[\"versions:{gv}\"]"));
-    }
-    if let Some(l) = loader {
-        groups.push(format!("[\"categories:{l}\"]"));
-    }
-    if let Some(cat) = category {
-        groups.push(format!("[\"categories:{cat}\"]"));
+    let any_of = |facet: &str, values: &[String]| -> Vec<String> {
+        values.iter().map(|v| format!("{facet}:{v}")).collect()
+    };
+    groups.push(any_of("versions", &filters.game_versions));
+    // Loaders are categories on Modrinth
+    groups.push(any_of("categories", &filters.loaders));
+    groups.push(any_of("categories", &filters.categories));
+
+    let supported = |side: &str| vec![format!("{side}:required"), format!("{side}:optional")];
+    match filters.environment {
+        Some(ModEnvironment::Client) => groups.push(supported("client_side")),
+        Some(ModEnvironment::Server) => groups.push(supported("server_side")),
+        Some(ModEnvironment::Both) => {
+            groups.push(supported("client_side"));
+            groups.push(supported("server_side"));
+        }
+        None => {}
     }
 
-    format!("[{}]", groups.join(","))
+    groups.retain(|g| !g.is_empty());
+    serde_json::to_string(&groups).unwrap_or_default()
 }
 
 fn extract_loaders_from_categories(categories: &[String]) -> Vec<String> {
@@ -391,3 +606,83 @@ fn extract_loaders_from_categories(categories: &[String]) -> Vec<String> {
         .map(|c| c.to_lowercase())
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{canned_server, http_response, StubServer};
+
+    fn test_cache() -> HttpCache {
+        HttpCache::new(std::env::temp_dir().join(format!("minesync_http_{}", uuid::Uuid::new_v4())))
+    }
+
+    /// Serve one canned JSON response.
+    fn stub_server(body: &str) -> StubServer {
+        canned_server(vec![http_response(
+            "200 OK\r\nContent-Type: application/json",
+            body.as_bytes(),
+        )])
+    }
+
+    #[tokio::test]
+    async fn get_versions_uses_configured_base_url() -> AppResult<()> {
+        let server = stub_server(
+            r#"[{"id":"v1","project_id":"p1","name":"Sodium 0.6","version_number":"0.6.0",
+                "game_versions":["1.21.1"],"loaders":["fabric"],"date_published":"2024-08-01",
+                "version_type":"beta","dependencies":[],
+                "files":[{"url":"https://cdn.example/sodium.jar","filename":"sodium.jar","size":10,
+                          "hashes":{"sha1":"abc","sha512":null},"primary":true}]}]"#,
+        );
+        let client = ModrinthClient::new(&Endpoints::all_at(&server.base), test_cache());
+
+        let versions = client
+            .get_versions("p1", Some("1.21.1"), Some("fabric"))
+            .await?;
+
+        assert!(server.requests()[0].starts_with("GET /modrinth/project/p1/version?"));
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].version_number, "0.6.0");
+        assert_eq!(versions[0].channel, ReleaseChannel::Beta);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_versions_by_ids_sends_one_bulk_request() -> AppResult<()> {
+        let server = stub_server(
+            r#"[{"id":"v1","project_id":"p1","name":"Sodium 0.6","version_number":"0.6.0",
+                "game_versions":["1.21.1"],"loaders":["fabric"],"date_published":"2024-08-01",
+                "dependencies":[],"files":[]}]"#,
+        );
+        let client = ModrinthClient::new(&Endpoints::all_at(&server.base), test_cache());
+
+        let ids = vec!["v1".to_string(), "gone".to_string()];
+        let versions = client.get_versions_by_ids(&ids).await?;
+
+        assert!(server.requests()[0]
+            .starts_with("GET /modrinth/versions?ids=%5B%22v1%22%2C%22gone%22%5D "));
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].channel, ReleaseChannel::Release);
+        Ok(())
+    }
+
+    #[test]
+    fn facet_values_are_ored_and_groups_anded() {
+        let filters = SearchFilters {
+            query: String::new(),
+            game_versions: vec!["1.20.1".to_string(), "1.21".to_string()],
+            loaders: vec!["fabric".to_string()],
+            categories: Vec::new(),
+            environment: Some(ModEnvironment::Server),
+            sort: SearchSort::Relevance,
+            offset: 0,
+            limit: 20,
+            content_type: ContentType::Shader,
+        };
+
+        assert_eq!(
+            build_facets(&filters),
+            r#"[["project_type:shader"],["versions:1.20.1","versions:1.21"],["categories:fabric"],["server_side:required","server_side:optional"]]"#
+        );
+    }
+}
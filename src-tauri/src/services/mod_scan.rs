@@ -0,0 +1,523 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use sha1::Sha1;
+use sha2::{Digest, Sha512};
+
+use crate::errors::{AppError, AppResult};
+use crate::models::instance::ModLoader;
+use crate::models::mod_info::{JarMetadata, ModInfo, ModSource, RescanReport, DISABLED_SUFFIX};
+use crate::models::mod_platform::ModVersionInfo;
+use crate::services::database::DatabaseService;
+use crate::services::jar_metadata::read_jar_metadata;
+use crate::services::mod_platform::curseforge::cf_fingerprint;
+use crate::services::mod_platform::UnifiedModClient;
+
+/// A jar found in `mods/`, hashed and inspected.
+#[derive(Debug)]
+struct ScannedFile {
+    /// File name without the `.disabled` suffix.
+    file_name: String,
+    enabled: bool,
+    sha1: String,
+    sha512: String,
+    fingerprint: u32,
+    metadata: Option<JarMetadata>,
+}
+
+/// Reconcile an instance's `mods/` folder with its `instance_mods` rows.
+///
+/// - jars with no row are registered, identified on Modrinth (SHA-512) then
+///   CurseForge (fingerprint), and fall back to `ModSource::Local`;
+/// - rows without platform ids, or whose file changed, are re-identified;
+/// - rows whose file is gone are flagged with `file_missing`.
+///
+/// Platform lookups are best-effort: when offline, files are still registered
+/// from their embedded metadata.
+pub async fn rescan_instance_mods(
+    db: &DatabaseService,
+    mod_client: &UnifiedModClient,
+    instance_id: &str,
+) -> AppResult<RescanReport> {
+    let instance = db
+        .get_instance(instance_id)?
+        .ok_or_else(|| AppError::Custom(format!("Instance not found: {instance_id}")))?;
+
+    let mods_dir = PathBuf::from(&instance.instance_path).join("mods");
+    let loader = instance.loader.clone();
+    let scanned = tokio::task::spawn_blocking(move || scan_mods_dir(&mods_dir, &loader))
+        .await
+        .map_err(|e| AppError::Custom(format!("Mod scan task panicked: {e}")))??;
+
+    let rows = db.list_instance_mods(instance_id)?;
+    let to_identify: Vec<&ScannedFile> = scanned
+        .iter()
+        .filter(|f| match rows.iter().find(|r| r.file_name == f.file_name) {
+            Some(row) => needs_identification(row, f),
+            None => true,
+        })
+        .collect();
+
+    let identities = identify_files(mod_client, &to_identify).await;
+    let slugs = fetch_modrinth_slugs(mod_client, &identities).await;
+
+    reconcile(db, instance_id, rows, &scanned, &identities, &slugs)
+}
+
+/// Apply a scan to the DB. `identities` is keyed by SHA-1, `slugs` by
+/// project id.
+fn reconcile(
+    db: &DatabaseService,
+    instance_id: &str,
+    rows: Vec<ModInfo>,
+    scanned: &[ScannedFile],
+    identities: &HashMap<String, ModVersionInfo>,
+    slugs: &HashMap<String, String>,
+) -> AppResult<RescanReport> {
+    let mut files_by_name: HashMap<&str, &ScannedFile> = HashMap::new();
+    let mut duplicates: HashSet<&str> = HashSet::new();
+    for f in scanned {
+        if files_by_name.insert(f.file_name.as_str(), f).is_some() {
+            duplicates.insert(f.file_name.as_str());
+        }
+    }
+
+    let mut report = RescanReport {
+        added: Vec::new(),
+        identified: Vec::new(),
+        missing: Vec::new(),
+        duplicates: Vec::new(),
+        scanned_files: scanned.len(),
+    };
+
+    // `foo.jar` next to `foo.jar.disabled`: no way to tell which one a row
+    // means, so both are left alone until the user removes one
+    for name in &duplicates {
+        files_by_name.remove(name);
+        log::warn!("[SCAN] {name} is both enabled and disabled in mods/");
+        report.duplicates.push(name.to_string());
+        report.duplicates.push(format!("{name}{DISABLED_SUFFIX}"));
+    }
+    report.duplicates.sort();
+
+    for mut row in rows.iter().cloned() {
+        if duplicates.contains(row.file_name.as_str()) {
+            continue;
+        }
+        let Some(f) = files_by_name.get(row.file_name.as_str()) else {
+            if !row.file_missing {
+                row.file_missing = true;
+                db.update_mod(&row)?;
+            }
+            report.missing.push(row);
+            continue;
+        };
+
+        let before = row.clone();
+        let replaced = before
+            .file_hash
+            .as_deref()
+            .is_some_and(|hash| hash != f.sha1);
+        row.file_missing = false;
+        row.enabled = f.enabled;
+        row.file_hash = Some(f.sha1.clone());
+        if row.metadata.is_none() || replaced {
+            row.metadata = f.metadata.clone();
+        }
+        let identity = identities.get(f.sha1.as_str());
+        match identity {
+            Some(version) => apply_identity(&mut row, version, slugs),
+            // The platform version on record is no longer the file on disk
+            None if replaced => forget_identity(&mut row, f),
+            None => {}
+        }
+
+        if row != before {
+            db.update_mod(&row)?;
+            if let Some(version) = identity {
+                db.set_mod_platform_dependencies(&row.id, &version.dependencies)?;
+                report.identified.push(row);
+            } else if replaced {
+                db.set_mod_platform_dependencies(&row.id, &[])?;
+            }
+        }
+    }
+
+    let new_files = files_by_name
+        .values()
+        .filter(|f| !rows.iter().any(|r| r.file_name == f.file_name));
+    for f in new_files {
+        let mut mod_info = local_mod_info(instance_id, f);
+        let identity = identities.get(f.sha1.as_str());
+        if let Some(version) = identity {
+            apply_identity(&mut mod_info, version, slugs);
+        }
+        db.add_mod_to_instance(&mod_info)?;
+        if let Some(version) = identity {
+            db.set_mod_platform_dependencies(&mod_info.id, &version.dependencies)?;
+        }
+        report.added.push(mod_info);
+    }
+
+    Ok(report)
+}
+
+// --- Identification ---
+
+/// Look files up on Modrinth first, then CurseForge for whatever is left.
+///
+/// Returns identities keyed by SHA-1.
+async fn identify_files(
+    mod_client: &UnifiedModClient,
+    files: &[&ScannedFile],
+) -> HashMap<String, ModVersionInfo> {
+    let mut identities = HashMap::new();
+    if files.is_empty() {
+        return identities;
+    }
+
+    let sha512s: Vec<String> = files.iter().map(|f| f.sha512.clone()).collect();
+    match mod_client.identify_modrinth_files(&sha512s).await {
+        Ok(mut found) => {
+            for f in files {
+                if let Some(v) = found.remove(&f.sha512) {
+                    identities.insert(f.sha1.clone(), v);
+                }
+            }
+        }
+        Err(e) => log::warn!("[SCAN] Modrinth hash lookup failed: {e}"),
+    }
+
+    let remaining: Vec<&&ScannedFile> = files
+        .iter()
+        .filter(|f| !identities.contains_key(&f.sha1))
+        .collect();
+    if remaining.is_empty() {
+        return identities;
+    }
+
+    let fingerprints: Vec<u32> = remaining.iter().map(|f| f.fingerprint).collect();
+    match mod_client.identify_curseforge_files(&fingerprints).await {
+        Ok(mut found) => {
+            for f in remaining {
+                if let Some(v) = found.remove(&f.fingerprint) {
+                    identities.insert(f.sha1.clone(), v);
+                }
+            }
+        }
+        Err(e) => log::warn!("[SCAN] CurseForge fingerprint lookup failed: {e}"),
+    }
+
+    identities
+}
+
+/// Slugs of the identified Modrinth projects, from one bulk lookup.
+async fn fetch_modrinth_slugs(
+    mod_client: &UnifiedModClient,
+    identities: &HashMap<String, ModVersionInfo>,
+) -> HashMap<String, String> {
+    let mut project_ids: Vec<String> = identities
+        .values()
+        .filter(|v| v.source == ModSource::Modrinth)
+        .map(|v| v.project_id.clone())
+        .collect();
+    project_ids.sort();
+    project_ids.dedup();
+    if project_ids.is_empty() {
+        return HashMap::new();
+    }
+
+    match mod_client.get_modrinth_projects(&project_ids).await {
+        Ok(projects) => projects.into_iter().map(|p| (p.id, p.slug)).collect(),
+        Err(e) => {
+            log::warn!("[SCAN] Modrinth project lookup failed: {e}");
+            HashMap::new()
+        }
+    }
+}
+
+/// A known row is looked up again when it was never linked to a platform or
+/// when the jar was swapped for a different file.
+fn needs_identification(row: &ModInfo, file: &ScannedFile) -> bool {
+    row.source_project_id.is_none() || row.file_hash.as_deref() != Some(file.sha1.as_str())
+}
+
+fn apply_identity(
+    mod_info: &mut ModInfo,
+    version: &ModVersionInfo,
+    slugs: &HashMap<String, String>,
+) {
+    mod_info.source = version.source.clone();
+    if let Some(slug) = slugs.get(&version.project_id) {
+        mod_info.slug = Some(slug.clone());
+    }
+    mod_info.source_project_id = Some(version.project_id.clone());
+    mod_info.source_version_id = Some(version.id.clone());
+    mod_info.version = version.version_number.clone();
+}
+
+/// Turn a row back into a local mod described by its jar.
+fn forget_identity(mod_info: &mut ModInfo, file: &ScannedFile) {
+    let (name, version) = embedded_name_and_version(file);
+    mod_info.name = name;
+    mod_info.version = version;
+    mod_info.source = ModSource::Local;
+    mod_info.slug = None;
+    mod_info.source_project_id = None;
+    mod_info.source_version_id = None;
+}
+
+/// Name and version from the jar's descriptor, else from its file name.
+fn embedded_name_and_version(file: &ScannedFile) -> (String, String) {
+    match &file.metadata {
+        Some(meta) => (meta.name.clone(), meta.version.clone()),
+        None => (
+            file.file_name
+                .strip_suffix(".jar")
+                .unwrap_or(&file.file_name)
+                .to_string(),
+            String::new(),
+        ),
+    }
+}
+
+fn local_mod_info(instance_id: &str, file: &ScannedFile) -> ModInfo {
+    let (name, version) = embedded_name_and_version(file);
+
+    ModInfo {
+        id: uuid::Uuid::new_v4().to_string(),
+        instance_id: instance_id.to_string(),
+        name,
+        slug: None,
+        version,
+        file_name: file.file_name.clone(),
+        file_hash: Some(file.sha1.clone()),
+        source: ModSource::Local,
+        source_project_id: None,
+        source_version_id: None,
+        is_active: true,
+        enabled: file.enabled,
+        file_missing: false,
+        auto_installed: false,
+        metadata: file.metadata.clone(),
+        installed_at: Utc::now(),
+    }
+}
+
+// --- Disk scan ---
+
+fn scan_mods_dir(mods_dir: &Path, loader: &ModLoader) -> AppResult<Vec<ScannedFile>> {
+    let entries = match std::fs::read_dir(mods_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+
+        let disk_name = entry.file_name().to_string_lossy().to_string();
+        let (file_name, enabled) = match disk_name.strip_suffix(DISABLED_SUFFIX) {
+            Some(stripped) => (stripped.to_string(), false),
+            None => (disk_name.clone(), true),
+        };
+        if !file_name.ends_with(".jar") {
+            continue;
+        }
+
+        let path = entry.path();
+        let data = std::fs::read(&path)?;
+        let metadata = read_jar_metadata(&path, Some(loader)).unwrap_or_else(|e| {
+            log::warn!("[SCAN] Unreadable jar {}: {e}", path.display());
+            None
+        });
+
+        files.push(ScannedFile {
+            file_name,
+            enabled,
+            sha1: format!("{:x}", Sha1::digest(&data)),
+            sha512: format!("{:x}", Sha512::digest(&data)),
+            fingerprint: cf_fingerprint(&data),
+            metadata,
+        });
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{test_instance, test_mod, test_version, write_fabric_jar};
+
+    #[test]
+    fn rescan_registers_local_jars_and_flags_missing_rows() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_rescan_{}", uuid::Uuid::new_v4()));
+        let mods_dir = root.join("instance").join("mods");
+        std::fs::create_dir_all(&mods_dir)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+
+        let instance = test_instance(&root.join("instance"));
+        db.create_instance(&instance)?;
+
+        let gone = ModInfo {
+            name: "Gone".to_string(),
+            ..test_mod(&instance.id, "gone.jar")
+        };
+        db.add_mod_to_instance(&gone)?;
+
+        write_fabric_jar(&mods_dir.join("handmade.jar"), "handmade", "2.1.0")?;
+        write_fabric_jar(&mods_dir.join("parked.jar.disabled"), "parked", "0.1")?;
+        std::fs::write(mods_dir.join("notes.txt"), b"not a mod")?;
+
+        let rows = db.list_instance_mods(&instance.id)?;
+        let scanned = scan_mods_dir(&mods_dir, &ModLoader::Fabric)?;
+        let report = reconcile(
+            &db,
+            &instance.id,
+            rows,
+            &scanned,
+            &HashMap::new(),
+            &HashMap::new(),
+        )?;
+
+        assert_eq!(report.scanned_files, 2);
+        assert_eq!(report.added.len(), 2);
+        assert_eq!(report.missing.len(), 1);
+        assert_eq!(report.missing[0].id, gone.id);
+
+        let mods = db.list_instance_mods(&instance.id)?;
+        let handmade = mods
+            .iter()
+            .find(|m| m.file_name == "handmade.jar")
+            .ok_or_else(|| AppError::Custom("handmade.jar not registered".to_string()))?;
+        assert_eq!(handmade.name, "handmade mod");
+        assert_eq!(handmade.version, "2.1.0");
+        assert!(handmade.file_hash.is_some());
+        assert_eq!(
+            handmade.metadata.as_ref().map(|m| m.mod_id.as_str()),
+            Some("handmade")
+        );
+
+        let parked = mods
+            .iter()
+            .find(|m| m.file_name == "parked.jar")
+            .ok_or_else(|| AppError::Custom("parked.jar not registered".to_string()))?;
+        assert!(
+            !parked.enabled,
+            "A .jar.disabled file registers as disabled"
+        );
+
+        assert!(mods.iter().any(|m| m.id == gone.id && m.file_missing));
+
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+
+    #[test]
+    fn replaced_jars_take_the_identity_of_the_new_file() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_rescan_{}", uuid::Uuid::new_v4()));
+        let mods_dir = root.join("instance").join("mods");
+        std::fs::create_dir_all(&mods_dir)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let instance = test_instance(&root.join("instance"));
+        db.create_instance(&instance)?;
+
+        let identified = |file_name: &str| ModInfo {
+            version: "0.5.0".to_string(),
+            file_hash: Some("0".repeat(40)),
+            source: ModSource::Modrinth,
+            slug: Some("old".to_string()),
+            source_project_id: Some("AANobbMI".to_string()),
+            source_version_id: Some("old-version".to_string()),
+            ..test_mod(&instance.id, file_name)
+        };
+        db.add_mod_to_instance(&identified("sodium.jar"))?;
+        db.add_mod_to_instance(&identified("patched.jar"))?;
+        write_fabric_jar(&mods_dir.join("sodium.jar"), "sodium", "0.5.8")?;
+        write_fabric_jar(&mods_dir.join("patched.jar"), "patched", "9.9")?;
+
+        let rows = db.list_instance_mods(&instance.id)?;
+        let scanned = scan_mods_dir(&mods_dir, &ModLoader::Fabric)?;
+        let sodium_sha1 = scanned
+            .iter()
+            .find(|f| f.file_name == "sodium.jar")
+            .map(|f| f.sha1.clone())
+            .expect("sodium.jar scanned");
+        let identities = HashMap::from([(
+            sodium_sha1,
+            ModVersionInfo {
+                version_number: "mc1.20.1-0.5.8".to_string(),
+                ..test_version("new-version", "AANobbMI")
+            },
+        )]);
+        reconcile(
+            &db,
+            &instance.id,
+            rows,
+            &scanned,
+            &identities,
+            &HashMap::new(),
+        )?;
+
+        let mods = db.list_instance_mods(&instance.id)?;
+        let find = |file_name: &str| {
+            mods.iter()
+                .find(|m| m.file_name == file_name)
+                .expect("row kept")
+        };
+        let sodium = find("sodium.jar");
+        assert_eq!(sodium.version, "mc1.20.1-0.5.8");
+        assert_eq!(sodium.source_version_id.as_deref(), Some("new-version"));
+
+        let patched = find("patched.jar");
+        assert_eq!(patched.source, ModSource::Local);
+        assert_eq!(patched.source_project_id, None);
+        assert_eq!(patched.source_version_id, None);
+        assert_eq!(patched.slug, None);
+        assert_eq!(patched.name, "patched mod");
+        assert_eq!(patched.version, "9.9");
+
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+
+    #[test]
+    fn enabled_and_disabled_copies_of_a_jar_are_reported() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_rescan_{}", uuid::Uuid::new_v4()));
+        let mods_dir = root.join("instance").join("mods");
+        std::fs::create_dir_all(&mods_dir)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let instance = test_instance(&root.join("instance"));
+        db.create_instance(&instance)?;
+
+        write_fabric_jar(&mods_dir.join("foo.jar"), "foo", "2.0")?;
+        write_fabric_jar(&mods_dir.join("foo.jar.disabled"), "foo", "1.0")?;
+
+        let scanned = scan_mods_dir(&mods_dir, &ModLoader::Fabric)?;
+        let report = reconcile(
+            &db,
+            &instance.id,
+            Vec::new(),
+            &scanned,
+            &HashMap::new(),
+            &HashMap::new(),
+        )?;
+
+        assert_eq!(report.duplicates, vec!["foo.jar", "foo.jar.disabled"]);
+        assert!(report.added.is_empty());
+        assert!(db.list_instance_mods(&instance.id)?.is_empty());
+
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+}
@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::errors::{AppError, AppResult};
+use crate::models::instance::{MinecraftInstance, ModLoader};
+use crate::models::mod_info::{
+    ModInfo, ModSource, ModUpdate, UpdateCheckReport, UpdateFailure, UpdateResult, DISABLED_SUFFIX,
+};
+use crate::models::mod_platform::{ModVersionFile, ModVersionInfo, ReleaseChannel};
+use crate::services::database::DatabaseService;
+use crate::services::download::{DownloadService, DownloadTask, FileHashes};
+use crate::services::jar_metadata::read_jar_metadata;
+use crate::services::mod_platform::UnifiedModClient;
+
+/// Prefix of the staging folders inside `mods/` for jars downloaded by an
+/// update. Files only leave them through a rename, so a failed update never
+/// leaves a half-written jar where the loader would pick it up. Each
+/// `update_mods` call gets its own folder so concurrent runs never share one.
+const STAGING_PREFIX: &str = ".minesync-update-";
+
+/// Look for newer versions of an instance's platform mods.
+///
+/// Modrinth mods with a known SHA-1 are checked with one bulk request; the
+/// rest (CurseForge, unhashed files, or a bulk answer on a less stable
+/// channel than `min_channel`) fall back to one `get_versions` call each.
+/// Without `min_channel` the instance's own preference applies.
+pub async fn check_mod_updates(
+    db: &DatabaseService,
+    mod_client: &UnifiedModClient,
+    instance_id: &str,
+    min_channel: Option<ReleaseChannel>,
+) -> AppResult<UpdateCheckReport> {
+    let instance = get_instance(db, instance_id)?;
+    let min_channel = min_channel.unwrap_or(instance.min_release_channel);
+    let mods = db.list_instance_mods(instance_id)?;
+    find_updates(mod_client, &instance, mods, min_channel).await
+}
+
+/// Download and install the updates for the selected mods (`instance_mods` ids).
+///
+/// Every jar is downloaded to a staging folder first; a mod is only swapped
+/// once its new jar is fully on disk, and one failure does not stop the rest.
+pub async fn update_mods(
+    db: &DatabaseService,
+    mod_client: &UnifiedModClient,
+    download_service: &DownloadService,
+    instance_id: &str,
+    mod_ids: &[String],
+    min_channel: Option<ReleaseChannel>,
+) -> AppResult<UpdateResult> {
+    let instance = get_instance(db, instance_id)?;
+    let min_channel = min_channel.unwrap_or(instance.min_release_channel);
+    let selected: Vec<ModInfo> = db
+        .list_instance_mods(instance_id)?
+        .into_iter()
+        .filter(|m| mod_ids.contains(&m.id))
+        .collect();
+
+    let report = find_updates(mod_client, &instance, selected, min_channel).await?;
+
+    let mods_dir = PathBuf::from(&instance.instance_path).join("mods");
+    let staging_dir = mods_dir.join(format!("{STAGING_PREFIX}{}", uuid::Uuid::new_v4()));
+    tokio::fs::create_dir_all(&staging_dir).await?;
+
+    let mut result = UpdateResult {
+        updated: Vec::new(),
+        failed: report
+            .skipped
+            .iter()
+            .map(|m| UpdateFailure {
+                mod_id: m.id.clone(),
+                mod_name: m.name.clone(),
+                error: "Update lookup failed".to_string(),
+            })
+            .collect(),
+    };
+
+    let mut staged = Vec::new();
+    let mut tasks = Vec::new();
+    for update in report.updates {
+        let Some(file) = primary_file(&update.latest) else {
+            result.failed.push(failure(&update, "No files in version"));
+            continue;
+        };
+        let dest = staging_dir.join(&file.filename);
+        tasks.push(DownloadTask {
+            urls: vec![file.url.clone()],
+            dest: dest.clone(),
+            hashes: FileHashes::from_map(&file.hashes),
+            size: file.size,
+            shared: true,
+        });
+        staged.push((update, dest));
+    }
+
+    if let Err(e) = download_service.download_all(tasks).await {
+        let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+        return Err(e);
+    }
+
+    for (update, staged_path) in staged {
+        // download_all records failures instead of returning them
+        if !staged_path.exists() {
+            result.failed.push(failure(&update, "Download failed"));
+            continue;
+        }
+        match apply_update(db, &instance, &mods_dir, &update, &staged_path) {
+            Ok(updated) => result.updated.push(updated),
+            Err(e) => {
+                let _ = std::fs::remove_file(&staged_path);
+                result.failed.push(failure(&update, &e.to_string()));
+            }
+        }
+    }
+
+    let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+    Ok(result)
+}
+
+fn get_instance(db: &DatabaseService, instance_id: &str) -> AppResult<MinecraftInstance> {
+    db.get_instance(instance_id)?
+        .ok_or_else(|| AppError::Custom(format!("Instance not found: {instance_id}")))
+}
+
+// --- Lookup ---
+
+async fn find_updates(
+    mod_client: &UnifiedModClient,
+    instance: &MinecraftInstance,
+    mods: Vec<ModInfo>,
+    min_channel: ReleaseChannel,
+) -> AppResult<UpdateCheckReport> {
+    let loader = match instance.loader {
+        ModLoader::Vanilla => None,
+        ref l => Some(l.to_string()),
+    };
+    let game_version = instance.minecraft_version.as_str();
+
+    let mut report = UpdateCheckReport {
+        updates: Vec::new(),
+        up_to_date: 0,
+        skipped: Vec::new(),
+    };
+
+    let candidates: Vec<ModInfo> = mods
+        .into_iter()
+        .filter(|m| !m.file_missing)
+        .filter(|m| m.source != ModSource::Local && m.source_project_id.is_some())
+        .collect();
+
+    // One request covers every hashed Modrinth mod
+    let hashes: Vec<String> = candidates
+        .iter()
+        .filter(|m| m.source == ModSource::Modrinth)
+        .filter_map(|m| m.file_hash.clone())
+        .collect();
+    let bulk = match &loader {
+        Some(l) if !hashes.is_empty() => mod_client
+            .latest_modrinth_versions(&hashes, game_version, l)
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("[UPDATE] Modrinth bulk update check failed: {e}");
+                HashMap::new()
+            }),
+        _ => HashMap::new(),
+    };
+
+    for m in candidates {
+        let from_bulk = m
+            .file_hash
+            .as_ref()
+            .and_then(|h| bulk.get(h))
+            .filter(|v| v.channel.satisfies(min_channel));
+
+        let latest = match from_bulk {
+            Some(v) if is_installed(&m, v) => None,
+            Some(v) => Some(v.clone()),
+            None => {
+                let Some(project_id) = m.source_project_id.as_deref() else {
+                    continue;
+                };
+                match mod_client
+                    .get_versions(&m.source, project_id, Some(game_version), loader.as_deref())
+                    .await
+                {
+                    Ok(versions) => pick_update(&m, versions, min_channel),
+                    Err(e) => {
+                        log::warn!("[UPDATE] Version lookup failed for {}: {e}", m.name);
+                        report.skipped.push(m);
+                        continue;
+                    }
+                }
+            }
+        };
+
+        match latest {
+            Some(latest) => report.updates.push(ModUpdate {
+                mod_id: m.id.clone(),
+                mod_name: m.name.clone(),
+                current_version: m.version.clone(),
+                current_version_id: m.source_version_id.clone(),
+                latest,
+            }),
+            None => report.up_to_date += 1,
+        }
+    }
+
+    Ok(report)
+}
+
+/// Newest version on an acceptable channel that is newer than the installed one.
+///
+/// A version listed after the installed one is older, so a user on a beta is
+/// never "updated" back to an earlier release. When the installed version is
+/// not in the list at all, nothing is known to be newer and no update is
+/// offered.
+fn pick_update(
+    installed: &ModInfo,
+    mut versions: Vec<ModVersionInfo>,
+    min_channel: ReleaseChannel,
+) -> Option<ModVersionInfo> {
+    versions.sort_by(|a, b| b.date_published.cmp(&a.date_published));
+
+    let installed_index = versions.iter().position(|v| is_installed(installed, v))?;
+
+    versions[..installed_index]
+        .iter()
+        .find(|v| v.channel.satisfies(min_channel))
+        .cloned()
+}
+
+fn is_installed(installed: &ModInfo, version: &ModVersionInfo) -> bool {
+    installed.source_version_id.as_deref() == Some(version.id.as_str())
+        || primary_file(version)
+            .and_then(|f| f.hashes.get("sha1"))
+            .is_some_and(|h| installed.file_hash.as_deref() == Some(h.as_str()))
+}
+
+fn primary_file(version: &ModVersionInfo) -> Option<&ModVersionFile> {
+    version
+        .files
+        .iter()
+        .find(|f| f.primary)
+        .or_else(|| version.files.first())
+}
+
+fn failure(update: &ModUpdate, error: &str) -> UpdateFailure {
+    UpdateFailure {
+        mod_id: update.mod_id.clone(),
+        mod_name: update.mod_name.clone(),
+        error: error.to_string(),
+    }
+}
+
+// --- Swap ---
+
+/// Move a staged jar into `mods/`, remove the old one and update the row.
+///
+/// The new jar keeps the old one's enabled/disabled state.
+fn apply_update(
+    db: &DatabaseService,
+    instance: &MinecraftInstance,
+    mods_dir: &Path,
+    update: &ModUpdate,
+    staged_path: &Path,
+) -> AppResult<ModInfo> {
+    let mut mod_info = db
+        .get_mod_by_id(&update.mod_id)?
+        .ok_or_else(|| AppError::Custom(format!("Mod not found: {}", update.mod_id)))?;
+    let file = primary_file(&update.latest)
+        .ok_or_else(|| AppError::Custom("No files in version".to_string()))?;
+
+    let old_path = mods_dir.join(mod_info.disk_file_name());
+    let new_name = if mod_info.enabled {
+        file.filename.clone()
+    } else {
+        format!("{}{DISABLED_SUFFIX}", file.filename)
+    };
+    let new_path = mods_dir.join(&new_name);
+
+    // rename() replaces an existing file atomically, so a same-name update
+    // never leaves a gap; a different name removes the old jar afterwards
+    std::fs::rename(staged_path, &new_path)?;
+    if old_path != new_path {
+        match std::fs::remove_file(&old_path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let metadata = read_jar_metadata(&new_path, Some(&instance.loader)).unwrap_or_else(|e| {
+        log::warn!(
+            "[UPDATE] Failed to read metadata from {}: {e}",
+            new_path.display()
+        );
+        None
+    });
+
+    mod_info.version = match &metadata {
+        Some(meta) if update.latest.version_number.is_empty() => meta.version.clone(),
+        _ => update.latest.version_number.clone(),
+    };
+    mod_info.file_name = file.filename.clone();
+    mod_info.file_hash = file.hashes.get("sha1").cloned();
+    mod_info.source_version_id = Some(update.latest.id.clone());
+    mod_info.file_missing = false;
+    if metadata.is_some() {
+        mod_info.metadata = metadata;
+    }
+
+    db.update_mod(&mod_info)?;
+    db.set_mod_platform_dependencies(&mod_info.id, &update.latest.dependencies)?;
+    Ok(mod_info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{test_instance, test_mod, test_version};
+
+    fn installed(version_id: &str) -> ModInfo {
+        ModInfo {
+            name: "Sodium".to_string(),
+            version: "0.5.0".to_string(),
+            file_hash: Some("old-sha1".to_string()),
+            source: ModSource::Modrinth,
+            source_project_id: Some("AANobbMI".to_string()),
+            source_version_id: Some(version_id.to_string()),
+            ..test_mod("inst", "sodium-0.5.0.jar")
+        }
+    }
+
+    fn version(id: &str, date: &str, channel: ReleaseChannel) -> ModVersionInfo {
+        ModVersionInfo {
+            files: vec![ModVersionFile {
+                url: format!("https://cdn.example/{id}.jar"),
+                filename: format!("sodium-{id}.jar"),
+                size: 3,
+                hashes: HashMap::from([("sha1".to_string(), format!("sha1-{id}"))]),
+                primary: true,
+            }],
+            date_published: date.to_string(),
+            channel,
+            ..test_version(id, "AANobbMI")
+        }
+    }
+
+    #[test]
+    fn pick_update_honours_channel_and_never_downgrades() {
+        let versions = vec![
+            version("0.5.0", "2023-06-01", ReleaseChannel::Release),
+            version("0.6.0-alpha", "2023-09-01", ReleaseChannel::Alpha),
+            version("0.5.8", "2023-08-01", ReleaseChannel::Release),
+            version("0.4.0", "2023-01-01", ReleaseChannel::Release),
+        ];
+
+        let latest = pick_update(
+            &installed("0.5.0"),
+            versions.clone(),
+            ReleaseChannel::Release,
+        );
+        assert_eq!(latest.map(|v| v.id).as_deref(), Some("0.5.8"));
+
+        let latest = pick_update(&installed("0.5.0"), versions.clone(), ReleaseChannel::Alpha);
+        assert_eq!(latest.map(|v| v.id).as_deref(), Some("0.6.0-alpha"));
+
+        let latest = pick_update(
+            &installed("0.6.0-alpha"),
+            versions.clone(),
+            ReleaseChannel::Release,
+        );
+        assert!(latest.is_none(), "An older release is not an update");
+
+        let latest = pick_update(&installed("custom"), versions, ReleaseChannel::Release);
+        assert!(latest.is_none(), "An unlisted install is never replaced");
+    }
+
+    #[test]
+    fn apply_update_swaps_jar_and_keeps_disabled_state() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_update_{}", uuid::Uuid::new_v4()));
+        let mods_dir = root.join("instance").join("mods");
+        let staging = mods_dir.join(format!("{STAGING_PREFIX}test"));
+        std::fs::create_dir_all(&staging)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+
+        let instance = test_instance(&root.join("instance"));
+        db.create_instance(&instance)?;
+
+        let mut old = installed("0.5.0");
+        old.instance_id = instance.id.clone();
+        old.enabled = false;
+        db.add_mod_to_instance(&old)?;
+        std::fs::write(mods_dir.join("sodium-0.5.0.jar.disabled"), b"old")?;
+
+        let latest = version("0.5.8", "2023-08-01", ReleaseChannel::Release);
+        let staged = staging.join("sodium-0.5.8.jar");
+        std::fs::write(&staged, b"new")?;
+        let update = ModUpdate {
+            mod_id: old.id.clone(),
+            mod_name: old.name.clone(),
+            current_version: old.version.clone(),
+            current_version_id: old.source_version_id.clone(),
+            latest,
+        };
+
+        let updated = apply_update(&db, &instance, &mods_dir, &update, &staged)?;
+
+        assert!(!mods_dir.join("sodium-0.5.0.jar.disabled").exists());
+        assert!(mods_dir.join("sodium-0.5.8.jar.disabled").exists());
+        assert!(!staged.exists());
+        assert_eq!(updated.version, "0.5.8");
+
+        let row = db
+            .get_mod_by_id(&old.id)?
+            .ok_or_else(|| AppError::Custom("row vanished".to_string()))?;
+        assert_eq!(row.file_name, "sodium-0.5.8.jar");
+        assert_eq!(row.source_version_id.as_deref(), Some("0.5.8"));
+        assert_eq!(row.file_hash.as_deref(), Some("sha1-0.5.8"));
+        assert!(!row.enabled);
+
+        let _ = std::fs::remove_dir_all(root);
+        Ok(())
+    }
+}
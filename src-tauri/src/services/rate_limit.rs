@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::errors::{AppError, AppResult};
+
+const MAX_RETRIES: u32 = 3;
+const BASE_DELAY: Duration = Duration::from_millis(500);
+/// Longest wait honoured from a server header, so a bogus value cannot hang the app.
+const MAX_WAIT: Duration = Duration::from_secs(60);
+
+/// Paces requests to one platform.
+///
+/// Tracks `X-Ratelimit-Remaining` / `X-Ratelimit-Reset`: once the window is
+/// used up, requests queue until it resets. HTTP 429 and 5xx responses are
+/// retried with jittered exponential backoff, honouring `Retry-After`.
+pub struct RateLimiter {
+    platform: &'static str,
+    max_retries: u32,
+    base_delay: Duration,
+    /// When the current window resets, if it is used up. Held while waiting
+    /// so requests queue in order.
+    blocked_until: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(platform: &'static str) -> Self {
+        Self::with_backoff(platform, MAX_RETRIES, BASE_DELAY)
+    }
+
+    pub fn with_backoff(platform: &'static str, max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            platform,
+            max_retries,
+            base_delay,
+            blocked_until: Mutex::new(None),
+        }
+    }
+
+    pub async fn send(&self, request: reqwest::RequestBuilder) -> AppResult<reqwest::Response> {
+        let (client, request) = request.build_split();
+        self.execute(&client, request?).await
+    }
+
+    /// Send a request, retrying 429 and 5xx responses. A 429 that outlasts
+    /// every retry becomes `AppError::RateLimited`; a 5xx is returned as is.
+    pub async fn execute(
+        &self,
+        client: &reqwest::Client,
+        mut request: reqwest::Request,
+    ) -> AppResult<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            self.wait_for_window().await;
+
+            // Keep a copy for a retry; streaming bodies cannot be replayed
+            let next = if attempt < self.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+            let response = client.execute(request).await?;
+            let status = response.status();
+            let server_wait = self.record_limits(response.headers()).await;
+
+            let retryable = status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+            match next {
+                Some(next) if retryable => {
+                    let delay = match server_wait {
+                        Some(wait) if status == StatusCode::TOO_MANY_REQUESTS => wait,
+                        _ => self.backoff(attempt),
+                    };
+                    log::warn!(
+                        "[{}] HTTP {status}, retrying in {}ms",
+                        self.platform,
+                        delay.as_millis()
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    request = next;
+                }
+                _ if status == StatusCode::TOO_MANY_REQUESTS => {
+                    return Err(AppError::RateLimited(self.platform.to_string()));
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+
+    async fn wait_for_window(&self) {
+        let mut blocked_until = self.blocked_until.lock().await;
+        if let Some(until) = blocked_until.take() {
+            tokio::time::sleep_until(until).await;
+        }
+    }
+
+    /// Remember an exhausted window; returns how long the server asked to wait.
+    async fn record_limits(&self, headers: &HeaderMap) -> Option<Duration> {
+        let wait = server_wait(headers)?;
+        let remaining = header_u64(headers, "x-ratelimit-remaining");
+        let exhausted = remaining == Some(0) || headers.contains_key(RETRY_AFTER);
+        if exhausted {
+            *self.blocked_until.lock().await = Some(Instant::now() + wait);
+        }
+        Some(wait)
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self.base_delay * 2u32.saturating_pow(attempt);
+        let jitter_ms = rand::thread_rng().gen_range(0..=self.base_delay.as_millis() as u64 / 2);
+        exponential + Duration::from_millis(jitter_ms)
+    }
+}
+
+/// `Retry-After` (seconds), else `X-Ratelimit-Reset` (seconds until reset).
+fn server_wait(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = header_u64(headers, RETRY_AFTER.as_str())
+        .or_else(|| header_u64(headers, "x-ratelimit-reset"))?;
+    Some(Duration::from_secs(seconds).min(MAX_WAIT))
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{canned_server, http_response, StubServer};
+
+    /// Answer one connection per status line and extra headers.
+    fn stub_server(statuses: &[&str]) -> StubServer {
+        canned_server(statuses.iter().map(|s| http_response(s, b"{}")).collect())
+    }
+
+    #[tokio::test]
+    async fn retries_429_and_5xx_until_success() -> AppResult<()> {
+        let server = stub_server(&[
+            "429 Too Many Requests\r\nRetry-After: 0",
+            "503 Service Unavailable",
+            "200 OK\r\nX-Ratelimit-Remaining: 299\r\nX-Ratelimit-Reset: 60",
+        ]);
+        let limiter = RateLimiter::with_backoff("Stub", 3, Duration::from_millis(1));
+
+        let response = limiter
+            .send(reqwest::Client::new().get(format!("{}/project/x", server.base)))
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.requests().len(), 3);
+        // Requests were left in the window, so nothing is queued
+        assert!(limiter.blocked_until.lock().await.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn persistent_429_is_a_rate_limit_error() {
+        let server = stub_server(&[
+            "429 Too Many Requests\r\nX-Ratelimit-Remaining: 0\r\nX-Ratelimit-Reset: 0",
+            "429 Too Many Requests\r\nX-Ratelimit-Remaining: 0\r\nX-Ratelimit-Reset: 0",
+        ]);
+        let limiter = RateLimiter::with_backoff("Stub", 1, Duration::from_millis(1));
+
+        let result = limiter
+            .send(reqwest::Client::new().get(format!("{}/search", server.base)))
+            .await;
+
+        assert_eq!(server.requests().len(), 2);
+        assert!(matches!(result, Err(AppError::RateLimited(p)) if p == "Stub"));
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppResult;
+
+/// Suffix of a blob still being added to the store.
+const STAGING_SUFFIX: &str = ".tmp";
+
+/// Staged blobs younger than this may belong to an insert still in progress.
+const STAGING_GRACE: Duration = Duration::from_secs(60 * 60);
+
+/// Result of [`FileStore::gc`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoreGcReport {
+    pub removed_files: usize,
+    /// Size of the removed blobs. Disk space is only reclaimed for blobs no
+    /// instance file is still hardlinked to.
+    pub removed_bytes: u64,
+}
+
+/// Content-addressed store of downloaded files under `store/<sha1>`, shared
+/// by every instance so each file is downloaded and kept on disk once.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    root: PathBuf,
+}
+
+impl FileStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Where the blob for `sha1` lives, or `None` if it is not a SHA1 digest.
+    pub fn blob_path(&self, sha1: &str) -> Option<PathBuf> {
+        let valid = sha1.len() == 40 && sha1.bytes().all(|b| b.is_ascii_hexdigit());
+        valid.then(|| self.root.join(sha1.to_ascii_lowercase()))
+    }
+
+    /// Add `src` to the store under `sha1`, unless a blob is already there.
+    /// The caller is trusted to have verified the contents.
+    pub async fn insert(&self, src: &Path, sha1: &str) -> AppResult<()> {
+        let Some(blob) = self.blob_path(sha1) else {
+            return Ok(());
+        };
+        if tokio::fs::try_exists(&blob).await? {
+            return Ok(());
+        }
+        tokio::fs::create_dir_all(&self.root).await?;
+
+        // Staged under a unique name so concurrent inserts never expose a partial blob
+        let staged = self
+            .root
+            .join(format!("{}{STAGING_SUFFIX}", uuid::Uuid::new_v4()));
+        let result = async {
+            link_or_copy(src, &staged).await?;
+            tokio::fs::rename(&staged, &blob).await?;
+            Ok(())
+        }
+        .await;
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(&staged).await;
+        }
+        result
+    }
+
+    /// Remove every blob whose SHA1 is not in `referenced` (lowercase), along
+    /// with leftovers of interrupted inserts older than [`STAGING_GRACE`].
+    pub async fn gc(&self, referenced: &HashSet<String>) -> AppResult<StoreGcReport> {
+        let mut report = StoreGcReport::default();
+        let mut entries = match tokio::fs::read_dir(&self.root).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(report),
+            Err(e) => return Err(e.into()),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+            let meta = entry.metadata().await?;
+            if !meta.is_file() || referenced.contains(&name) {
+                continue;
+            }
+            if name.ends_with(STAGING_SUFFIX) && !staging_expired(&meta) {
+                continue;
+            }
+            tokio::fs::remove_file(entry.path()).await?;
+            report.removed_files += 1;
+            report.removed_bytes += meta.len();
+        }
+
+        log::info!(
+            "Store GC removed {} files ({} bytes)",
+            report.removed_files,
+            report.removed_bytes
+        );
+        Ok(report)
+    }
+}
+
+/// Whether a staged blob is old enough that no insert is still writing it.
+fn staging_expired(meta: &std::fs::Metadata) -> bool {
+    meta.modified()
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map_or(true, |age| age >= STAGING_GRACE)
+}
+
+/// Hardlink `src` to `dest`, falling back to a copy across filesystems. The
+/// OS turns that copy into a reflink where the filesystem supports it
+/// (APFS, Btrfs, XFS).
+pub async fn link_or_copy(src: &Path, dest: &Path) -> AppResult<()> {
+    if let Err(e) = tokio::fs::hard_link(src, dest).await {
+        log::debug!(
+            "Hardlink {} -> {} failed ({e}), copying",
+            src.display(),
+            dest.display()
+        );
+        tokio::fs::copy(src, dest).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA1_A: &str = "a9993e364706816aba3e25717850c26c9cd0d89d";
+    const SHA1_B: &str = "84983e441c3bd26ebaae4aa1f95129e5e54670f1";
+
+    #[tokio::test]
+    async fn gc_keeps_only_referenced_blobs() -> AppResult<()> {
+        let root = std::env::temp_dir().join(format!("minesync-store-{}", uuid::Uuid::new_v4()));
+        let store = FileStore::new(root.join("store"));
+        std::fs::create_dir_all(&root)?;
+        let src = root.join("mod.jar");
+        std::fs::write(&src, b"abc")?;
+
+        store.insert(&src, SHA1_A).await?;
+        store.insert(&src, &SHA1_B.to_ascii_uppercase()).await?;
+        store.insert(&src, "not-a-hash").await?;
+        let blob_a = store.blob_path(SHA1_A).expect("valid sha1");
+        let blob_b = store.blob_path(SHA1_B).expect("valid sha1");
+        assert_eq!(std::fs::read(&blob_a)?, b"abc");
+        assert!(blob_b.exists());
+        let stale = std::fs::File::create(root.join("store").join("stale.tmp"))?;
+        stale.set_modified(SystemTime::now() - STAGING_GRACE * 2)?;
+        let fresh = root.join("store").join("fresh.tmp");
+        std::fs::write(&fresh, b"in progress")?;
+
+        let referenced = HashSet::from([SHA1_A.to_string()]);
+        let report = store.gc(&referenced).await?;
+        assert_eq!(report.removed_files, 2);
+        assert!(blob_a.exists());
+        assert!(!blob_b.exists());
+        assert!(fresh.exists());
+        // Instance files linked from a removed blob keep their contents
+        assert_eq!(std::fs::read(&src)?, b"abc");
+
+        let _ = std::fs::remove_dir_all(&root);
+        Ok(())
+    }
+}
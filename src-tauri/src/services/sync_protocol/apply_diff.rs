@@ -2,6 +2,7 @@ use crate::errors::AppResult;
 use crate::models::mod_info::{ModInfo, ModSource};
 use crate::models::sync::SyncModEntry;
 use crate::services::database::DatabaseService;
+use crate::services::jar_metadata::read_jar_metadata;
 use crate::services::sync_protocol::manifest_diff::{ManifestDiff, ModUpdate};
 
 /// Result of applying a diff to an instance.
@@ -98,6 +99,10 @@ fn apply_additions(
             source_project_id: entry.source_project_id.clone(),
             source_version_id: entry.source_version_id.clone(),
             is_active: true,
+            enabled: true,
+            file_missing: false,
+            auto_installed: false,
+            metadata: None,
             installed_at: chrono::Utc::now(),
         };
 
@@ -126,6 +131,8 @@ fn apply_updates(
         }
     };
 
+    let instance = db.get_instance(instance_id).ok().flatten();
+
     for update in to_update {
         // Remove old version
         let found = existing_mods.iter().find(|m| m.name == update.mod_name);
@@ -140,6 +147,21 @@ fn apply_updates(
             }
         }
 
+        // Metadata of the new jar once it is on disk, else the old one's
+        let new_jar_metadata = instance.as_ref().and_then(|instance| {
+            let path = std::path::Path::new(&instance.instance_path)
+                .join("mods")
+                .join(&update.remote_file_name);
+            if !path.is_file() {
+                return None;
+            }
+            read_jar_metadata(&path, Some(&instance.loader)).unwrap_or_else(|e| {
+                log::warn!("Unreadable jar {}: {e}", path.display());
+                None
+            })
+        });
+        let metadata = new_jar_metadata.or_else(|| found.and_then(|m| m.metadata.clone()));
+
         // Add new version
         let source = update
             .source
@@ -158,6 +180,10 @@ fn apply_updates(
             source_project_id: update.source_project_id.clone(),
             source_version_id: update.source_version_id.clone(),
             is_active: true,
+            enabled: found.map_or(true, |m| m.enabled),
+            file_missing: false,
+            auto_installed: found.is_some_and(|m| m.auto_installed),
+            metadata,
             installed_at: chrono::Utc::now(),
         };
 
@@ -170,3 +196,123 @@ fn apply_updates(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{test_instance, test_mod, write_fabric_jar};
+
+    #[test]
+    fn updates_keep_the_enabled_state() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_apply_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let instance = test_instance(&root.join("instance"));
+        db.create_instance(&instance)?;
+        db.add_mod_to_instance(&ModInfo {
+            name: "Sodium".to_string(),
+            enabled: false,
+            ..test_mod(&instance.id, "sodium-0.5.0.jar")
+        })?;
+
+        let update = |name: &str| ModUpdate {
+            mod_name: name.to_string(),
+            local_version: "0.5.0".to_string(),
+            remote_version: "0.5.8".to_string(),
+            source: "modrinth".to_string(),
+            source_project_id: None,
+            source_version_id: None,
+            remote_file_name: format!("{name}-0.5.8.jar"),
+            remote_hash: None,
+        };
+        let mut result = ApplyResult {
+            mods_added: Vec::new(),
+            mods_removed: Vec::new(),
+            mods_updated: Vec::new(),
+            errors: Vec::new(),
+        };
+        apply_updates(
+            &db,
+            &instance.id,
+            &[update("Sodium"), update("Lithium")],
+            &mut result,
+        );
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+        let mods = db.list_instance_mods(&instance.id)?;
+        let enabled = |name: &str| mods.iter().find(|m| m.name == name).map(|m| m.enabled);
+        assert_eq!(enabled("Sodium"), Some(false));
+        assert_eq!(enabled("Lithium"), Some(true), "New mods start enabled");
+
+        let _ = std::fs::remove_dir_all(&root);
+        Ok(())
+    }
+
+    #[test]
+    fn updates_keep_or_reread_jar_metadata() -> AppResult<()> {
+        let root =
+            std::env::temp_dir().join(format!("minesync_test_apply_{}", uuid::Uuid::new_v4()));
+        let mods_dir = root.join("instance").join("mods");
+        std::fs::create_dir_all(&mods_dir)?;
+        let db = DatabaseService::new(&root.join("test.db"))?;
+        let instance = test_instance(&root.join("instance"));
+        db.create_instance(&instance)?;
+
+        let old_jar = root.join("old.jar");
+        for name in ["Sodium", "Lithium"] {
+            write_fabric_jar(&old_jar, &name.to_ascii_lowercase(), "0.5.0")?;
+            db.add_mod_to_instance(&ModInfo {
+                name: name.to_string(),
+                metadata: read_jar_metadata(&old_jar, None)?,
+                ..test_mod(&instance.id, &format!("{name}-0.5.0.jar"))
+            })?;
+        }
+        // Only Lithium's new jar is downloaded already
+        write_fabric_jar(&mods_dir.join("Lithium-0.5.8.jar"), "lithium", "0.5.8")?;
+
+        let update = |name: &str| ModUpdate {
+            mod_name: name.to_string(),
+            local_version: "0.5.0".to_string(),
+            remote_version: "0.5.8".to_string(),
+            source: "modrinth".to_string(),
+            source_project_id: None,
+            source_version_id: None,
+            remote_file_name: format!("{name}-0.5.8.jar"),
+            remote_hash: None,
+        };
+        let mut result = ApplyResult {
+            mods_added: Vec::new(),
+            mods_removed: Vec::new(),
+            mods_updated: Vec::new(),
+            errors: Vec::new(),
+        };
+        apply_updates(
+            &db,
+            &instance.id,
+            &[update("Sodium"), update("Lithium")],
+            &mut result,
+        );
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+        let mods = db.list_instance_mods(&instance.id)?;
+        let metadata = |name: &str| {
+            mods.iter()
+                .find(|m| m.name == name)
+                .and_then(|m| m.metadata.as_ref())
+                .map(|meta| (meta.mod_id.clone(), meta.version.clone()))
+        };
+        assert_eq!(
+            metadata("Sodium"),
+            Some(("sodium".to_string(), "0.5.0".to_string()))
+        );
+        assert_eq!(
+            metadata("Lithium"),
+            Some(("lithium".to_string(), "0.5.8".to_string()))
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+        Ok(())
+    }
+}
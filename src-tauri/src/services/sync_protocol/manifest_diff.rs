@@ -123,13 +123,16 @@ fn find_additions(
 }
 
 /// Mods in local but not in remote -> need to remove.
+///
+/// Locally disabled mods are never removed: the host does not share its
+/// disabled mods, so their absence from the remote manifest means nothing.
 fn find_removals(
     local: &HashMap<&str, &SyncModEntry>,
     remote: &HashMap<&str, &SyncModEntry>,
 ) -> Vec<SyncModEntry> {
     local
         .iter()
-        .filter(|(name, _)| !remote.contains_key(*name))
+        .filter(|(name, entry)| entry.enabled && !remote.contains_key(*name))
         .map(|(_, entry)| (*entry).clone())
         .collect()
 }
@@ -200,6 +203,7 @@ mod tests {
             source: "modrinth".to_string(),
             source_project_id: Some(format!("{name}-id")),
             source_version_id: Some(format!("{name}-ver-{version}")),
+            enabled: true,
         }
     }
 
@@ -243,6 +247,19 @@ mod tests {
         assert_eq!(diff.to_remove[0].mod_name, "old-mod");
     }
 
+    #[test]
+    fn disabled_local_mods_are_not_removed() {
+        let mut disabled = make_mod("old-mod", "1.0.0", None);
+        disabled.enabled = false;
+        let local = make_manifest(vec![make_mod("sodium", "0.5.8", None), disabled]);
+        let remote = make_manifest(vec![make_mod("sodium", "0.5.8", None)]);
+
+        let diff = compute_diff(&local, &remote);
+
+        assert!(diff.to_remove.is_empty());
+        assert!(diff.to_add.is_empty());
+    }
+
     #[test]
     fn detects_mods_to_update_by_version() {
         let local = make_manifest(vec![make_mod("sodium", "0.5.7", None)]);
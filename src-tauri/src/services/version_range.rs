@@ -0,0 +1,236 @@
+use std::cmp::Ordering;
+
+use crate::models::mod_info::MetadataFormat;
+
+/// Check `version` against a range declared in a mod descriptor.
+///
+/// Fabric and Quilt use semver-style predicates (`>=0.15`, `~1.20.1`,
+/// `1.20.x`, space-separated for "and", `||` for "or"); Forge and NeoForge
+/// use Maven ranges (`[1.20.1,1.21)`).
+///
+/// Returns `None` when either side cannot be interpreted (snapshots,
+/// `${...}` placeholders, exotic syntax) so callers can skip the check
+/// instead of reporting a false violation.
+pub fn satisfies(range: &str, version: &str, format: &MetadataFormat) -> Option<bool> {
+    let version = Version::parse(version)?;
+    match format {
+        MetadataFormat::Fabric | MetadataFormat::Quilt => satisfies_predicates(range, &version),
+        MetadataFormat::Forge | MetadataFormat::NeoForge => satisfies_maven(range, &version),
+    }
+}
+
+// --- Version parsing ---
+
+#[derive(Debug, Clone, PartialEq)]
+struct Version {
+    core: Vec<u64>,
+    pre: Option<String>,
+}
+
+impl Version {
+    /// Parse `1.20.1`, `0.15.0-beta.2` or `1.0.0+build.5`. Build metadata is ignored.
+    fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let raw = raw.split_once('+').map_or(raw, |(v, _)| v);
+        let (core, pre) = match raw.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (raw, None),
+        };
+        if core.is_empty() {
+            return None;
+        }
+        let core = core
+            .split('.')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { core, pre })
+    }
+
+    fn component(&self, i: usize) -> u64 {
+        self.core.get(i).copied().unwrap_or(0)
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let len = self.core.len().max(other.core.len());
+        for i in 0..len {
+            match self.component(i).cmp(&other.component(i)) {
+                Ordering::Equal => {}
+                ord => return Some(ord),
+            }
+        }
+        // A pre-release sorts before its release
+        Some(match (&self.pre, &other.pre) {
+            (None, None) => Ordering::Equal,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(a), Some(b)) => compare_pre(a, b),
+        })
+    }
+}
+
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    for (x, y) in a.split('.').zip(b.split('.')) {
+        let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    a.split('.').count().cmp(&b.split('.').count())
+}
+
+// --- Fabric / Quilt predicates ---
+
+fn satisfies_predicates(range: &str, version: &Version) -> Option<bool> {
+    let mut any = false;
+    for alternative in range.split("||") {
+        let mut all = true;
+        for predicate in alternative.split_whitespace() {
+            all &= satisfies_predicate(predicate, version)?;
+        }
+        any |= all;
+    }
+    Some(any)
+}
+
+fn satisfies_predicate(predicate: &str, version: &Version) -> Option<bool> {
+    if predicate == "*" {
+        return Some(true);
+    }
+
+    let (op, rest) = [">=", "<=", ">", "<", "=", "~", "^"]
+        .iter()
+        .find_map(|op| predicate.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("=", predicate));
+
+    // `1.20.x` / `1.20.*` match on the leading components only
+    if let Some(prefix) = rest
+        .strip_suffix(".x")
+        .or_else(|| rest.strip_suffix(".X"))
+        .or_else(|| rest.strip_suffix(".*"))
+    {
+        let prefix = Version::parse(prefix)?;
+        let matches = (0..prefix.core.len()).all(|i| prefix.component(i) == version.component(i));
+        return (op == "=").then_some(matches);
+    }
+
+    let target = Version::parse(rest)?;
+    let ord = version.partial_cmp(&target)?;
+    Some(match op {
+        ">=" => ord != Ordering::Less,
+        "<=" => ord != Ordering::Greater,
+        ">" => ord == Ordering::Greater,
+        "<" => ord == Ordering::Less,
+        "=" => ord == Ordering::Equal,
+        // ~1.2.3 := >=1.2.3 <1.3.0
+        "~" => {
+            ord != Ordering::Less
+                && version.component(0) == target.component(0)
+                && version.component(1) == target.component(1)
+        }
+        // ^1.2.3 := >=1.2.3 <2.0.0
+        "^" => ord != Ordering::Less && version.component(0) == target.component(0),
+        _ => return None,
+    })
+}
+
+// --- Maven ranges ---
+
+fn satisfies_maven(range: &str, version: &Version) -> Option<bool> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" {
+        return Some(true);
+    }
+    // A bare version is only a recommendation in Maven; anything goes
+    if !range.starts_with('[') && !range.starts_with('(') {
+        return Some(true);
+    }
+
+    let mut rest = range;
+    let mut any = false;
+    while !rest.is_empty() {
+        let end = rest.find([']', ')'])?;
+        let (interval, tail) = rest.split_at(end + 1);
+        any |= satisfies_interval(interval, version)?;
+        rest = tail.trim_start_matches([',', ' ']);
+    }
+    Some(any)
+}
+
+fn satisfies_interval(interval: &str, version: &Version) -> Option<bool> {
+    let lower_inclusive = interval.starts_with('[');
+    let upper_inclusive = interval.ends_with(']');
+    let inner = &interval[1..interval.len() - 1];
+
+    let Some((lower, upper)) = inner.split_once(',') else {
+        // `[1.0]` pins an exact version
+        let exact = Version::parse(inner)?;
+        return Some(version.partial_cmp(&exact)? == Ordering::Equal);
+    };
+
+    if !lower.trim().is_empty() {
+        let ord = version.partial_cmp(&Version::parse(lower)?)?;
+        if ord == Ordering::Less || (ord == Ordering::Equal && !lower_inclusive) {
+            return Some(false);
+        }
+    }
+    if !upper.trim().is_empty() {
+        let ord = version.partial_cmp(&Version::parse(upper)?)?;
+        if ord == Ordering::Greater || (ord == Ordering::Equal && !upper_inclusive) {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fabric(range: &str, version: &str) -> Option<bool> {
+        satisfies(range, version, &MetadataFormat::Fabric)
+    }
+
+    fn forge(range: &str, version: &str) -> Option<bool> {
+        satisfies(range, version, &MetadataFormat::Forge)
+    }
+
+    #[test]
+    fn fabric_predicates() {
+        assert_eq!(fabric("*", "1.20.1"), Some(true));
+        assert_eq!(fabric(">=0.14.21", "0.15.0"), Some(true));
+        assert_eq!(fabric(">=0.14.21", "0.14.9"), Some(false));
+        assert_eq!(fabric("~1.20", "1.20.4"), Some(true));
+        assert_eq!(fabric("~1.20", "1.21"), Some(false));
+        assert_eq!(fabric("1.20.x", "1.20.6"), Some(true));
+        assert_eq!(fabric("1.20.x", "1.2"), Some(false));
+        assert_eq!(fabric(">=1.19 <1.20", "1.19.4"), Some(true));
+        assert_eq!(fabric(">=1.19 <1.20", "1.20"), Some(false));
+        assert_eq!(fabric("1.20 || 1.20.1", "1.20.1"), Some(true));
+        assert_eq!(fabric(">=1.0.0-beta.2", "1.0.0-beta.10"), Some(true));
+        assert_eq!(fabric(">=1.0.0", "1.0.0-rc.1"), Some(false));
+    }
+
+    #[test]
+    fn maven_ranges() {
+        assert_eq!(forge("[1.20.1,1.20.2)", "1.20.1"), Some(true));
+        assert_eq!(forge("[1.20.1,1.20.2)", "1.20.2"), Some(false));
+        assert_eq!(forge("[47,)", "47.2.0"), Some(true));
+        assert_eq!(forge("(,1.19]", "1.19"), Some(true));
+        assert_eq!(forge("[1.18],[1.20,1.21)", "1.20.4"), Some(true));
+        assert_eq!(forge("[1.18]", "1.18.2"), Some(false));
+        assert_eq!(forge("1.0", "0.1"), Some(true));
+    }
+
+    #[test]
+    fn unparseable_versions_are_unknown() {
+        assert_eq!(fabric(">=1.20", "23w45a"), None);
+        assert_eq!(forge("[1.20,)", "${file.jarVersion}"), None);
+    }
+}
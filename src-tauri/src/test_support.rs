@@ -0,0 +1,178 @@
+//! Fixtures shared by unit tests. Each builder returns a plain value that
+//! tests override with struct update syntax, e.g.
+//! `ModInfo { enabled: false, ..test_mod("inst", "sodium.jar") }`, and a
+//! stub HTTP server for clients under test.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+
+use crate::errors::{AppError, AppResult};
+
+use crate::models::instance::{MinecraftInstance, ModLoader};
+use crate::models::mod_info::{ModInfo, ModSource};
+use crate::models::mod_platform::{ModVersionInfo, ReleaseChannel};
+
+/// A Fabric 1.20.1 instance living in `instance_path`.
+pub fn test_instance(instance_path: &Path) -> MinecraftInstance {
+    let now = Utc::now();
+    MinecraftInstance {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: "Test".to_string(),
+        minecraft_version: "1.20.1".to_string(),
+        loader: ModLoader::Fabric,
+        loader_version: Some("0.15.0".to_string()),
+        instance_path: instance_path.to_string_lossy().to_string(),
+        icon_path: None,
+        icon_url: None,
+        description: None,
+        last_played_at: None,
+        total_play_time: 0,
+        is_active: true,
+        min_release_channel: ReleaseChannel::default(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// An enabled, hand-installed mod row for `file_name`.
+pub fn test_mod(instance_id: &str, file_name: &str) -> ModInfo {
+    ModInfo {
+        id: uuid::Uuid::new_v4().to_string(),
+        instance_id: instance_id.to_string(),
+        name: file_name.trim_end_matches(".jar").to_string(),
+        slug: None,
+        version: "1.0.0".to_string(),
+        file_name: file_name.to_string(),
+        file_hash: None,
+        source: ModSource::Local,
+        source_project_id: None,
+        source_version_id: None,
+        is_active: true,
+        enabled: true,
+        file_missing: false,
+        auto_installed: false,
+        metadata: None,
+        installed_at: Utc::now(),
+    }
+}
+
+/// Write a jar holding only a `fabric.mod.json` for `id` at `version`.
+pub fn write_fabric_jar(path: &Path, id: &str, version: &str) -> AppResult<()> {
+    let file = std::fs::File::create(path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    zip.start_file("fabric.mod.json", zip::write::SimpleFileOptions::default())
+        .map_err(|e| AppError::Custom(format!("zip start_file failed: {e}")))?;
+    write!(
+        zip,
+        r#"{{"id":"{id}","version":"{version}","name":"{id} mod"}}"#
+    )?;
+    zip.finish()
+        .map_err(|e| AppError::Custom(format!("zip finish failed: {e}")))?;
+    Ok(())
+}
+
+/// A Modrinth release of `project_id` for Fabric 1.20.1, without files.
+pub fn test_version(id: &str, project_id: &str) -> ModVersionInfo {
+    ModVersionInfo {
+        id: id.to_string(),
+        project_id: project_id.to_string(),
+        name: id.to_string(),
+        version_number: id.to_string(),
+        game_versions: vec!["1.20.1".to_string()],
+        loaders: vec!["fabric".to_string()],
+        files: Vec::new(),
+        dependencies: Vec::new(),
+        date_published: "2024-08-01".to_string(),
+        source: ModSource::Modrinth,
+        channel: ReleaseChannel::Release,
+    }
+}
+
+/// A local HTTP server running on a background thread, see [`stub_server`].
+pub struct StubServer {
+    pub base: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl StubServer {
+    /// Heads (request line and headers) of the requests received so far.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().expect("stub requests").clone()
+    }
+}
+
+/// Serve `connections` connections, one request each, then stop listening.
+/// `respond` gets the request head and returns the raw response, or `None`
+/// to hold the connection open without ever answering.
+pub fn stub_server<F>(connections: usize, mut respond: F) -> StubServer
+where
+    F: FnMut(&str) -> Option<Vec<u8>> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind stub server");
+    let base = format!("http://{}", listener.local_addr().expect("stub address"));
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&requests);
+
+    std::thread::spawn(move || {
+        let mut hanging = Vec::new();
+        for stream in listener.incoming().take(connections) {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 2 {
+                head.push_str(&line);
+                line.clear();
+            }
+            // Closing with an unread body would reset the connection
+            let body_len = head
+                .lines()
+                .find_map(|l| {
+                    let (name, value) = l.split_once(':')?;
+                    if name.eq_ignore_ascii_case("content-length") {
+                        value.trim().parse::<u64>().ok()
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(0);
+            let _ = std::io::copy(&mut (&mut reader).take(body_len), &mut std::io::sink());
+            // Recorded before answering, so clients see it once they have a response
+            seen.lock().expect("stub requests").push(head.clone());
+
+            let mut stream = reader.into_inner();
+            match respond(&head) {
+                Some(response) => {
+                    let _ = stream.write_all(&response);
+                }
+                None => hanging.push(stream),
+            }
+        }
+    });
+
+    StubServer { base, requests }
+}
+
+/// Answer one connection per response, in order.
+pub fn canned_server(responses: Vec<Vec<u8>>) -> StubServer {
+    let count = responses.len();
+    let mut responses = responses.into_iter();
+    stub_server(count, move |_| responses.next())
+}
+
+/// A complete response; `status` may carry extra header lines.
+pub fn http_response(status: &str, body: &[u8]) -> Vec<u8> {
+    let mut response = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(body);
+    response
+}
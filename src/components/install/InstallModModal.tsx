@@ -1,10 +1,4 @@
-import {
-  type ReactNode,
-  useState,
-  useEffect,
-  useCallback,
-  useMemo,
-} from "react";
+import { type ReactNode, useState, useEffect, useCallback } from "react";
 import { Modal } from "@/components/ui/Modal";
 import { Button } from "@/components/ui/Button";
 import { Badge } from "@/components/ui/Badge";
@@ -14,12 +8,13 @@ import {
   AlertCircle,
   Download,
 } from "@/components/ui/PixelIcon";
-import { listInstances, getModVersions, installMod } from "@/lib/tauri";
+import { listInstances, getCompatibleVersions, installMod } from "@/lib/tauri";
 import type {
   ModSearchResult,
   MinecraftInstance,
   ModVersionInfo,
-  ModInfo,
+  ModInstallResult,
+  IncompatibleVersionError,
 } from "@/lib/types";
 
 interface InstallModModalProps {
@@ -28,6 +23,14 @@ interface InstallModModalProps {
   mod: ModSearchResult;
 }
 
+function isIncompatibleVersionError(
+  err: unknown,
+): err is IncompatibleVersionError {
+  return (
+    typeof err === "object" && err !== null && "incompatible_version" in err
+  );
+}
+
 type Step =
   | "select_instance"
   | "select_version"
@@ -49,9 +52,9 @@ export function InstallModModal({
   const [loadingInstances, setLoadingInstances] = useState(false);
   const [loadingVersions, setLoadingVersions] = useState(false);
   const [installing, setInstalling] = useState(false);
-  const [installedMod, setInstalledMod] = useState<ModInfo | undefined>(
-    undefined,
-  );
+  const [installResult, setInstallResult] = useState<
+    ModInstallResult | undefined
+  >(undefined);
   const [errorMsg, setErrorMsg] = useState<string | undefined>(undefined);
 
   // Reset state when modal closes
@@ -60,7 +63,7 @@ export function InstallModModal({
       setStep("select_instance");
       setSelectedInstance(undefined);
       setVersions([]);
-      setInstalledMod(undefined);
+      setInstallResult(undefined);
       setErrorMsg(undefined);
       return;
     }
@@ -79,21 +82,6 @@ export function InstallModModal({
       });
   }, [open]);
 
-  // Filtered versions based on selected instance's MC version + loader
-  const filteredVersions = useMemo((): ModVersionInfo[] => {
-    if (selectedInstance === undefined) return versions;
-
-    return versions.filter((v) => {
-      const matchesVersion =
-        v.game_versions.length === 0 ||
-        v.game_versions.includes(selectedInstance.minecraft_version);
-      const matchesLoader =
-        v.loaders.length === 0 ||
-        v.loaders.some((l) => l.toLowerCase() === selectedInstance.loader);
-      return matchesVersion && matchesLoader;
-    });
-  }, [versions, selectedInstance]);
-
   const selectInstance = useCallback(
     async (instance: MinecraftInstance): Promise<void> => {
       setSelectedInstance(instance);
@@ -102,11 +90,10 @@ export function InstallModModal({
       setErrorMsg(undefined);
 
       try {
-        const v = await getModVersions({
+        const v = await getCompatibleVersions({
+          instanceId: instance.id,
           source: searchResult.source,
           projectId: searchResult.id,
-          gameVersion: instance.minecraft_version,
-          loader: instance.loader !== "vanilla" ? instance.loader : undefined,
         });
         setVersions(v);
       } catch (err: unknown) {
@@ -132,11 +119,22 @@ export function InstallModModal({
           source: searchResult.source,
           projectId: searchResult.id,
           versionId: version.id,
+          installDependencies: true,
         });
-        setInstalledMod(result);
+        setInstallResult(result);
         setStep("done");
       } catch (err: unknown) {
-        setErrorMsg(err instanceof Error ? err.message : String(err));
+        if (isIncompatibleVersionError(err)) {
+          const { minecraft_version, loader, game_version_supported } =
+            err.incompatible_version;
+          setErrorMsg(
+            game_version_supported
+              ? `${err.message}. This version is not built for ${loader}.`
+              : `${err.message}. This version does not run on Minecraft ${minecraft_version}.`,
+          );
+        } else {
+          setErrorMsg(err instanceof Error ? err.message : String(err));
+        }
         setStep("error");
       } finally {
         setInstalling(false);
@@ -267,10 +265,10 @@ export function InstallModModal({
               </div>
             )}
 
-            {!loadingVersions && filteredVersions.length > 0 && (
+            {!loadingVersions && versions.length > 0 && (
               <div className="max-h-64 overflow-y-auto">
                 <div className="flex flex-col gap-0.5">
-                  {filteredVersions.slice(0, 20).map((v) => (
+                  {versions.slice(0, 20).map((v) => (
                     <button
                       key={v.id}
                       onClick={() => {
@@ -310,7 +308,7 @@ export function InstallModModal({
               </div>
             )}
 
-            {!loadingVersions && filteredVersions.length === 0 && (
+            {!loadingVersions && versions.length === 0 && (
               <p
                 className="py-4 text-center text-sm"
                 style={{ color: "var(--color-notion-text-secondary)" }}
@@ -356,14 +354,33 @@ export function InstallModModal({
             >
               Mod installed successfully!
             </p>
-            {installedMod !== undefined && (
+            {installResult !== undefined && (
               <p
                 className="text-xs"
                 style={{ color: "var(--color-notion-text-tertiary)" }}
               >
-                {installedMod.file_name}
+                {installResult.installed.file_name}
               </p>
             )}
+            {installResult !== undefined &&
+              installResult.unresolved.length > 0 && (
+                <div
+                  className="w-full rounded-md p-2 text-xs"
+                  style={{
+                    background: "var(--color-accent-yellow-bg)",
+                    color: "var(--color-accent-yellow)",
+                  }}
+                >
+                  <p className="font-medium">
+                    Missing required dependencies — the mod may not load:
+                  </p>
+                  {installResult.unresolved.map((dep) => (
+                    <p key={`${dep.required_by}:${dep.project_id}`}>
+                      {dep.project_id}: {dep.reason}
+                    </p>
+                  ))}
+                </div>
+              )}
             <Button size="sm" onClick={onClose}>
               Done
             </Button>
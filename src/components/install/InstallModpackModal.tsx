@@ -21,6 +21,7 @@ import {
   getModVersions,
   installModpack,
   getInstallProgress,
+  cancelInstall,
 } from "@/lib/tauri";
 import type {
   ModSearchResult,
@@ -65,6 +66,8 @@ function stageLabel(stage: InstallStage): string {
       return "Registering mods…";
     case "completed":
       return "Installation complete!";
+    case "cancelled":
+      return "Installation cancelled";
     case "failed":
       return `Failed: ${stage.message}`;
   }
@@ -84,6 +87,7 @@ export function InstallModpackModal({
   );
   const [errorMsg, setErrorMsg] = useState<string | undefined>(undefined);
   const pollRef = useRef<ReturnType<typeof setInterval> | undefined>(undefined);
+  const jobIdRef = useRef<string | undefined>(undefined);
 
   // Fetch versions when modal opens
   useEffect(() => {
@@ -125,9 +129,11 @@ export function InstallModpackModal({
       setStep("installing");
       setErrorMsg(undefined);
 
-      // Start polling progress
+      // Start polling progress of this install's job
+      const jobId = crypto.randomUUID();
+      jobIdRef.current = jobId;
       pollRef.current = setInterval(() => {
-        getInstallProgress()
+        getInstallProgress(jobId)
           .then((p) => {
             setProgress(p);
           })
@@ -144,6 +150,7 @@ export function InstallModpackModal({
           modpackName: modpack.name,
           modpackIconUrl: modpack.icon_url,
           modpackDescription: modpack.description,
+          jobId,
         });
         setStep("done");
         onInstalled(installedInstance);
@@ -171,6 +178,13 @@ export function InstallModpackModal({
     onClose();
   }, [onClose]);
 
+  const handleCancel = useCallback((): void => {
+    if (jobIdRef.current === undefined) return;
+    cancelInstall(jobIdRef.current).catch((err: unknown) => {
+      setErrorMsg(err instanceof Error ? err.message : String(err));
+    });
+  }, []);
+
   return (
     <Modal
       open={open}
@@ -374,14 +388,19 @@ export function InstallModpackModal({
             >
               You can close this dialog — installation continues in background.
             </p>
-            <Button
-              size="sm"
-              variant="ghost"
-              icon={<X size={14} />}
-              onClick={handleClose}
-            >
-              Close
-            </Button>
+            <div className="flex gap-2">
+              <Button size="sm" variant="secondary" onClick={handleCancel}>
+                Cancel install
+              </Button>
+              <Button
+                size="sm"
+                variant="ghost"
+                icon={<X size={14} />}
+                onClick={handleClose}
+              >
+                Close
+              </Button>
+            </div>
           </div>
         )}
 
@@ -8,7 +8,11 @@ import {
   launchInstance,
   listMcVersions,
 } from "@/lib/tauri";
-import type { DownloadProgress, GameStatus } from "@/lib/types";
+import type {
+  DownloadProgress,
+  GameStatus,
+  ModProblemsError,
+} from "@/lib/types";
 
 const STATUS_POLL_INTERVAL_MS = 2000;
 const DOWNLOAD_POLL_INTERVAL_MS = 1000;
@@ -26,9 +30,16 @@ function sleep(ms: number): Promise<void> {
 
 function toErrorMessage(err: unknown): string {
   if (err instanceof Error) return err.message;
+  if (typeof err === "object" && err !== null && "message" in err) {
+    return String(err.message);
+  }
   return String(err);
 }
 
+function isModProblemsError(err: unknown): err is ModProblemsError {
+  return typeof err === "object" && err !== null && "mod_problems" in err;
+}
+
 function mapLaunchError(message: string): string {
   const lower = message.toLowerCase();
 
@@ -60,6 +71,9 @@ function mapLaunchError(message: string): string {
   if (lower.includes("no active account")) {
     return "Aucun compte actif. Connecte-toi avant de lancer une instance.";
   }
+  if (lower.startsWith("instance has mod problems: ")) {
+    return `Problèmes de mods détectés: ${message.slice("instance has mod problems: ".length)}`;
+  }
 
   return message;
 }
@@ -136,11 +150,15 @@ export function getCrashInfo(
 
 interface UseGameStatusResult {
   status: GameStatus;
-  launch: (instanceId: string) => Promise<void>;
+  launch: (instanceId: string, force?: boolean) => Promise<void>;
+  /** Launch the instance the mod check refused, ignoring its problems. */
+  launchAnyway: () => Promise<void>;
   kill: () => Promise<void>;
   isRunning: boolean;
   isPreparing: boolean;
   launchError: string | undefined;
+  /** Instance whose launch was refused by the mod check. */
+  blockedInstanceId: string | undefined;
   downloadProgress: DownloadProgress | undefined;
   isDownloadingBeforeLaunch: boolean;
   activeInstanceId: string | undefined;
@@ -149,6 +167,9 @@ interface UseGameStatusResult {
 export function useGameStatus(): UseGameStatusResult {
   const [status, setStatus] = useState<GameStatus>(IDLE_STATUS);
   const [launchError, setLaunchError] = useState<string | undefined>(undefined);
+  const [blockedInstanceId, setBlockedInstanceId] = useState<
+    string | undefined
+  >(undefined);
   const [downloadProgress, setDownloadProgress] = useState<
     DownloadProgress | undefined
   >(undefined);
@@ -240,8 +261,9 @@ export function useGameStatus(): UseGameStatusResult {
   }, [refreshDownloadState]);
 
   const launch = useCallback(
-    async (instanceId: string): Promise<void> => {
+    async (instanceId: string, force = false): Promise<void> => {
       setLaunchError(undefined);
+      setBlockedInstanceId(undefined);
       setTrackedActiveInstanceId(instanceId);
       setStatus("preparing");
       setPendingDownload(true);
@@ -255,12 +277,12 @@ export function useGameStatus(): UseGameStatusResult {
         // Required by backend before fetching version detail for download.
         await listMcVersions();
 
-        await downloadVersion(instance.minecraft_version);
+        const jobId = await downloadVersion(instance.minecraft_version);
 
         const startedAt = Date.now();
 
         while (true) {
-          const progress = await getDownloadProgress();
+          const progress = await getDownloadProgress(jobId);
           setDownloadProgress(progress);
 
           if (isDownloadFailed(progress)) {
@@ -271,6 +293,9 @@ export function useGameStatus(): UseGameStatusResult {
           if (progress.state === "completed") {
             break;
           }
+          if (progress.state === "cancelled") {
+            throw new Error("Le téléchargement Minecraft a été annulé.");
+          }
           if (Date.now() - startedAt > DOWNLOAD_TIMEOUT_MS) {
             throw new Error("Le téléchargement Minecraft a dépassé le délai.");
           }
@@ -281,11 +306,14 @@ export function useGameStatus(): UseGameStatusResult {
         setPendingDownload(false);
         setDownloadProgress(undefined);
 
-        await launchInstance(instanceId);
+        await launchInstance(instanceId, undefined, force);
         await refreshStatus();
       } catch (err: unknown) {
         setPendingDownload(false);
         setDownloadProgress(undefined);
+        if (isModProblemsError(err)) {
+          setBlockedInstanceId(instanceId);
+        }
 
         const message = mapLaunchError(toErrorMessage(err));
         setLaunchError(message);
@@ -295,6 +323,11 @@ export function useGameStatus(): UseGameStatusResult {
     [refreshStatus, setPendingDownload, setTrackedActiveInstanceId],
   );
 
+  const launchAnyway = useCallback(async (): Promise<void> => {
+    if (blockedInstanceId === undefined) return;
+    await launch(blockedInstanceId, true);
+  }, [blockedInstanceId, launch]);
+
   const kill = useCallback(async (): Promise<void> => {
     setLaunchError(undefined);
     try {
@@ -316,10 +349,12 @@ export function useGameStatus(): UseGameStatusResult {
   return {
     status,
     launch,
+    launchAnyway,
     kill,
     isRunning,
     isPreparing,
     launchError,
+    blockedInstanceId,
     downloadProgress,
     isDownloadingBeforeLaunch,
     activeInstanceId,
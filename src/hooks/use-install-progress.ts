@@ -1,12 +1,12 @@
 import { useState, useEffect, useRef } from "react";
-import { getInstallProgress } from "@/lib/tauri";
+import { listJobs } from "@/lib/tauri";
 import type { InstallProgress } from "@/lib/types";
 
 const POLL_INTERVAL_MS = 1000;
 
 function isActiveStage(progress: InstallProgress): boolean {
   const { type } = progress.stage;
-  return type !== "completed" && type !== "failed";
+  return type !== "completed" && type !== "cancelled" && type !== "failed";
 }
 
 interface UseInstallProgressResult {
@@ -25,13 +25,14 @@ export function useInstallProgress(): UseInstallProgressResult {
 
   useEffect(() => {
     const poll = (): void => {
-      getInstallProgress()
-        .then((p) => {
-          if (isActiveStage(p)) {
-            setProgress(p);
-          } else {
-            setProgress(undefined);
-          }
+      listJobs()
+        .then((jobs) => {
+          // Latest running modpack install, if any
+          const active = jobs
+            .filter((job) => job.kind === "modpack_install" && !job.finished)
+            .map((job) => job.progress)
+            .filter(isActiveStage);
+          setProgress(active.at(-1));
         })
         .catch(() => {
           // Ignore polling errors
@@ -16,9 +16,24 @@ import type {
   SyncManifest,
   ApplyResult,
   ModInfo,
+  RescanReport,
+  UpdateCheckReport,
+  UpdateResult,
+  StoreGcReport,
+  ReleaseChannel,
+  InstanceCheckReport,
+  MrpackExportOptions,
+  CurseForgeExportOptions,
+  LauncherImportReport,
+  ExportReport,
   ModSource,
   ModVersionInfo,
+  ModDetails,
+  DependencyPlan,
+  ModInstallResult,
   InstallProgress,
+  InstallCheckpoint,
+  JobInfo,
   GameStatus,
   LaunchInfo,
   CrashLog,
@@ -101,18 +116,63 @@ export async function deleteInstance(id: string): Promise<void> {
   return invoke<void>("delete_instance", { id });
 }
 
+export async function setInstanceReleaseChannel(
+  instanceId: string,
+  channel: ReleaseChannel,
+): Promise<void> {
+  return invoke<void>("set_instance_release_channel", { instanceId, channel });
+}
+
+export async function checkInstance(
+  instanceId: string,
+): Promise<InstanceCheckReport> {
+  return invoke<InstanceCheckReport>("check_instance", { instanceId });
+}
+
+export async function exportInstanceMrpack(
+  instanceId: string,
+  options: MrpackExportOptions,
+): Promise<ExportReport> {
+  return invoke<ExportReport>("export_instance_mrpack", {
+    instanceId,
+    options,
+  });
+}
+
+export async function exportInstanceCurseforge(
+  instanceId: string,
+  options: CurseForgeExportOptions,
+): Promise<ExportReport> {
+  return invoke<ExportReport>("export_instance_curseforge", {
+    instanceId,
+    options,
+  });
+}
+
+export async function importLauncherInstances(
+  path: string,
+): Promise<LauncherImportReport> {
+  return invoke<LauncherImportReport>("import_launcher_instances", { path });
+}
+
 // Minecraft commands — mirrors src-tauri/src/commands/minecraft.rs
 
 export async function listMcVersions(): Promise<VersionEntry[]> {
   return invoke<VersionEntry[]>("list_mc_versions");
 }
 
-export async function downloadVersion(versionId: string): Promise<void> {
-  return invoke<void>("download_version", { versionId });
+export async function downloadVersion(versionId: string): Promise<string> {
+  return invoke<string>("download_version", { versionId });
 }
 
-export async function getDownloadProgress(): Promise<DownloadProgress> {
-  return invoke<DownloadProgress>("get_download_progress");
+export async function getDownloadProgress(
+  jobId?: string,
+): Promise<DownloadProgress> {
+  return invoke<DownloadProgress>("get_download_progress", { jobId });
+}
+
+export async function cancelDownload(jobId: string): Promise<void> {
+  return invoke<void>("cancel_download", { jobId });
 }
 
 // Launch commands — mirrors src-tauri/src/commands/launch.rs
@@ -120,10 +180,12 @@ export async function getDownloadProgress(): Promise<DownloadProgress> {
 export async function launchInstance(
   instanceId: string,
   javaPath?: string,
+  force?: boolean,
 ): Promise<LaunchInfo> {
   return invoke<LaunchInfo>("launch_instance", {
     instanceId,
     javaPath: javaPath ?? null,
+    force: force ?? null,
   });
 }
 
@@ -228,8 +290,19 @@ export async function installMod(params: {
   source: ModSource;
   projectId: string;
   versionId: string;
-}): Promise<ModInfo> {
-  return invoke<ModInfo>("install_mod", params);
+  installDependencies?: boolean;
+  jobId?: string;
+}): Promise<ModInstallResult> {
+  return invoke<ModInstallResult>("install_mod", params);
+}
+
+export async function planModInstall(params: {
+  instanceId: string;
+  source: ModSource;
+  projectId: string;
+  versionId: string;
+}): Promise<DependencyPlan> {
+  return invoke<DependencyPlan>("plan_mod_install", params);
 }
 
 export async function installModpack(params: {
@@ -239,12 +312,52 @@ export async function installModpack(params: {
   modpackName?: string;
   modpackIconUrl?: string;
   modpackDescription?: string;
+  jobId?: string;
 }): Promise<MinecraftInstance> {
   return invoke<MinecraftInstance>("install_modpack", params);
 }
 
-export async function getInstallProgress(): Promise<InstallProgress> {
-  return invoke<InstallProgress>("get_install_progress");
+export async function importModpackFile(
+  path: string,
+  jobId?: string,
+): Promise<MinecraftInstance> {
+  return invoke<MinecraftInstance>("import_modpack_file", { path, jobId });
+}
+
+export async function getInstallProgress(
+  jobId?: string,
+): Promise<InstallProgress> {
+  return invoke<InstallProgress>("get_install_progress", { jobId });
+}
+
+export async function cancelInstall(jobId: string): Promise<void> {
+  return invoke<void>("cancel_install", { jobId });
+}
+
+export async function resumeInstall(
+  instanceId: string,
+  skipFailedOptional?: boolean,
+  jobId?: string,
+): Promise<MinecraftInstance> {
+  return invoke<MinecraftInstance>("resume_install", {
+    instanceId,
+    skipFailedOptional,
+    jobId,
+  });
+}
+
+export async function listUnfinishedInstalls(): Promise<InstallCheckpoint[]> {
+  return invoke<InstallCheckpoint[]>("list_unfinished_installs");
+}
+
+export async function discardInstall(instanceId: string): Promise<void> {
+  return invoke<void>("discard_install", { instanceId });
+}
+
+// Job commands — mirrors src-tauri/src/commands/jobs.rs
+
+export async function listJobs(): Promise<JobInfo[]> {
+  return invoke<JobInfo[]>("list_jobs");
 }
 
 export async function getModVersions(params: {
@@ -252,14 +365,74 @@ export async function getModVersions(params: {
   projectId: string;
   gameVersion?: string;
   loader?: string;
+  minChannel?: ReleaseChannel;
 }): Promise<ModVersionInfo[]> {
   return invoke<ModVersionInfo[]>("get_mod_versions", params);
 }
 
+export async function getCompatibleVersions(params: {
+  instanceId: string;
+  source: ModSource;
+  projectId: string;
+}): Promise<ModVersionInfo[]> {
+  return invoke<ModVersionInfo[]>("get_compatible_versions", params);
+}
+
+export async function getModrinthProjects(
+  projectIds: string[],
+): Promise<ModDetails[]> {
+  return invoke<ModDetails[]>("get_modrinth_projects", { projectIds });
+}
+
+export async function getModrinthVersions(
+  versionIds: string[],
+): Promise<ModVersionInfo[]> {
+  return invoke<ModVersionInfo[]>("get_modrinth_versions", { versionIds });
+}
+
 export async function listInstanceMods(instanceId: string): Promise<ModInfo[]> {
   return invoke<ModInfo[]>("list_instance_mods", { instanceId });
 }
 
-export async function removeMod(modId: string): Promise<void> {
-  return invoke<void>("remove_mod", { modId });
+export async function removeMod(modId: string): Promise<ModInfo[]> {
+  return invoke<ModInfo[]>("remove_mod", { modId });
+}
+
+export async function toggleMod(
+  modId: string,
+  enabled: boolean,
+): Promise<ModInfo> {
+  return invoke<ModInfo>("toggle_mod", { modId, enabled });
+}
+
+export async function rescanInstanceMods(
+  instanceId: string,
+): Promise<RescanReport> {
+  return invoke<RescanReport>("rescan_instance_mods", { instanceId });
+}
+
+export async function checkModUpdates(
+  instanceId: string,
+  minChannel?: ReleaseChannel,
+): Promise<UpdateCheckReport> {
+  return invoke<UpdateCheckReport>("check_mod_updates", {
+    instanceId,
+    minChannel: minChannel ?? null,
+  });
+}
+
+export async function updateMods(
+  instanceId: string,
+  modIds: string[],
+  minChannel?: ReleaseChannel,
+): Promise<UpdateResult> {
+  return invoke<UpdateResult>("update_mods", {
+    instanceId,
+    modIds,
+    minChannel: minChannel ?? null,
+  });
+}
+
+export async function gcStore(): Promise<StoreGcReport> {
+  return invoke<StoreGcReport>("gc_store");
 }
@@ -19,6 +19,7 @@ export interface MinecraftInstance {
   last_played_at: string | undefined;
   total_play_time: number;
   is_active: boolean;
+  min_release_channel: ReleaseChannel;
   created_at: string;
   updated_at: string;
 }
@@ -38,6 +39,7 @@ export type DownloadState =
   | "idle"
   | "downloading"
   | "completed"
+  | "cancelled"
   | { failed: { message: string } };
 
 export interface DownloadProgress {
@@ -107,9 +109,132 @@ export interface ModInfo {
   file_name: string;
   file_hash: string | undefined;
   enabled: boolean;
+  file_missing: boolean;
+  auto_installed: boolean;
+  metadata: JarMetadata | null;
   installed_at: string;
 }
 
+export type MetadataFormat = "fabric" | "quilt" | "forge" | "neo_forge";
+
+export type ModEnvironment = "both" | "client" | "server";
+
+export interface DeclaredDependency {
+  mod_id: string;
+  version_range: string | null;
+  dependency_type: DependencyType;
+}
+
+export interface JarMetadata {
+  format: MetadataFormat;
+  mod_id: string;
+  name: string;
+  version: string;
+  authors: string[];
+  minecraft_range: string | null;
+  loader_range: string | null;
+  dependencies: DeclaredDependency[];
+  provides: string[];
+  environment: ModEnvironment;
+}
+
+export interface RescanReport {
+  added: ModInfo[];
+  identified: ModInfo[];
+  missing: ModInfo[];
+  duplicates: string[];
+  scanned_files: number;
+}
+
+export interface MissingDependency {
+  mod_row_id: string;
+  mod_name: string;
+  dependency_id: string;
+  version_range: string | null;
+  found_version: string | null;
+  unverified: boolean;
+}
+
+export interface ModIncompatibility {
+  mod_row_id: string;
+  mod_name: string;
+  other_mod_row_id: string;
+  other_mod_name: string;
+}
+
+export interface DuplicateMod {
+  mod_id: string;
+  mod_row_ids: string[];
+  file_names: string[];
+}
+
+export interface VersionRangeViolation {
+  mod_row_id: string;
+  mod_name: string;
+  declared_range: string;
+  actual_version: string;
+}
+
+export interface LoaderMismatch {
+  mod_row_id: string;
+  mod_name: string;
+  format: MetadataFormat;
+  instance_loader: ModLoader;
+}
+
+export interface InstanceCheckReport {
+  instance_id: string;
+  missing_dependencies: MissingDependency[];
+  incompatibilities: ModIncompatibility[];
+  duplicates: DuplicateMod[];
+  minecraft_version_violations: VersionRangeViolation[];
+  loader_version_violations: VersionRangeViolation[];
+  loader_mismatches: LoaderMismatch[];
+}
+
+// Export — mirrors Rust models/export.rs
+
+export interface MrpackExportOptions {
+  output_path: string;
+  name?: string;
+  version_id: string;
+  summary?: string;
+  include_folders: string[];
+}
+
+export interface CurseForgeExportOptions {
+  output_path: string;
+  name?: string;
+  version: string;
+  author: string;
+  include_folders: string[];
+}
+
+export interface ExportReport {
+  output_path: string;
+  linked_files: number;
+  bundled_mods: string[];
+  warnings: string[];
+}
+
+// Launcher import — mirrors Rust models/launcher_import.rs
+
+export interface SkippedImport {
+  name: string;
+  reason: string;
+}
+
+export interface ImportNote {
+  name: string;
+  message: string;
+}
+
+export interface LauncherImportReport {
+  imported: MinecraftInstance[];
+  skipped: SkippedImport[];
+  notes: ImportNote[];
+}
+
 export interface SyncSession {
   id: string;
   instance_id: string;
@@ -154,6 +279,7 @@ export interface SyncModEntry {
   source: string;
   source_project_id: string | undefined;
   source_version_id: string | undefined;
+  enabled: boolean;
 }
 
 // Sync protocol — mirrors services/sync_protocol
@@ -251,6 +377,7 @@ export type InstallStage =
   | { type: "copying_overrides" }
   | { type: "registering_mods" }
   | { type: "completed" }
+  | { type: "cancelled" }
   | { type: "failed"; message: string };
 
 export interface InstallProgress {
@@ -261,6 +388,48 @@ export interface InstallProgress {
   modpack_icon_url: string | undefined;
 }
 
+export type CheckpointStage =
+  | "pack_ready"
+  | "minecraft_ready"
+  | "loader_ready"
+  | "mods_ready"
+  | "overrides_copied";
+
+export interface FailedModDownload {
+  name: string;
+  path: string | undefined;
+  url: string | undefined;
+  required: boolean;
+  reason: string;
+}
+
+export interface InstallCheckpoint {
+  instance_id: string;
+  instance_path: string;
+  archive_path: string;
+  modpack_name: string | undefined;
+  modpack_icon_url: string | undefined;
+  modpack_description: string | undefined;
+  stage: CheckpointStage;
+  failed_mods: FailedModDownload[];
+  error: string | undefined;
+  updated_at: string;
+}
+
+// Jobs — mirrors services/jobs.rs
+
+export type JobKind = "mod_install" | "modpack_install" | "minecraft_download";
+
+export interface JobInfo {
+  id: string;
+  kind: JobKind;
+  progress: InstallProgress;
+  downloads: DownloadProgress;
+  cancelled: boolean;
+  finished: boolean;
+  started_at: string;
+}
+
 // Mod versions — mirrors Rust models/mod_platform.rs
 
 export interface ModVersionInfo {
@@ -274,6 +443,62 @@ export interface ModVersionInfo {
   dependencies: ModDependency[];
   date_published: string;
   source: ModSource;
+  channel: ReleaseChannel;
+}
+
+export type ReleaseChannel = "release" | "beta" | "alpha";
+
+export interface VersionIncompatibility {
+  version_id: string;
+  version_name: string;
+  minecraft_version: string;
+  loader: ModLoader;
+  game_versions: string[];
+  loaders: string[];
+  game_version_supported: boolean;
+  loader_supported: boolean;
+}
+
+// Error thrown by install_mod when the version does not fit the instance
+export interface IncompatibleVersionError {
+  message: string;
+  incompatible_version: VersionIncompatibility;
+}
+
+// Error thrown by launch_instance when the mod check finds blocking problems
+export interface ModProblemsError {
+  message: string;
+  mod_problems: InstanceCheckReport;
+}
+
+export interface ModUpdate {
+  mod_id: string;
+  mod_name: string;
+  current_version: string;
+  current_version_id: string | null;
+  latest: ModVersionInfo;
+}
+
+export interface UpdateCheckReport {
+  updates: ModUpdate[];
+  up_to_date: number;
+  skipped: ModInfo[];
+}
+
+export interface UpdateFailure {
+  mod_id: string;
+  mod_name: string;
+  error: string;
+}
+
+export interface UpdateResult {
+  updated: ModInfo[];
+  failed: UpdateFailure[];
+}
+
+export interface StoreGcReport {
+  removed_files: number;
+  removed_bytes: number;
 }
 
 export interface ModVersionFile {
@@ -293,19 +518,74 @@ export type DependencyType =
 export interface ModDependency {
   project_id: string;
   dependency_type: DependencyType;
+  version_id: string | undefined;
+  file_name: string | undefined;
+}
+
+// Dependency plan — mirrors Rust models/dependency_plan.rs
+
+export interface DependencyPlan {
+  instance_id: string;
+  install: PlannedMod[];
+  skip: SkippedDependency[];
+  conflicts: DependencyConflict[];
+  unresolved: UnresolvedDependency[];
+}
+
+export interface PlannedMod {
+  version: ModVersionInfo;
+  slug: string | undefined;
+  required_by: string | undefined;
+}
+
+export interface SkippedDependency {
+  project_id: string;
+  source: ModSource;
+  required_by: string;
+  installed_mod_id: string;
+  installed_name: string;
+  installed_enabled: boolean;
+}
+
+export interface DependencyConflict {
+  project_id: string;
+  mod_name: string;
+  other_project_id: string;
+  other_name: string;
+  installed_mod_id: string | undefined;
+}
+
+export interface UnresolvedDependency {
+  project_id: string;
+  source: ModSource;
+  required_by: string;
+  reason: string;
+}
+
+export interface ModInstallResult {
+  installed: ModInfo;
+  dependencies: ModInfo[];
+  unresolved: UnresolvedDependency[];
 }
 
 // Mod Platform — mirrors Rust models/mod_platform.rs
 
 export type SearchSort = "relevance" | "downloads" | "updated" | "newest";
 
-export type ContentType = "mod" | "modpack";
+export type ContentType =
+  | "mod"
+  | "modpack"
+  | "resource_pack"
+  | "shader"
+  | "data_pack"
+  | "plugin";
 
 export interface SearchFilters {
   query: string;
-  game_version: string | undefined;
-  loader: string | undefined;
-  category: string | undefined;
+  game_versions: string[];
+  loaders: string[];
+  categories: string[];
+  environment?: ModEnvironment;
   sort: SearchSort;
   offset: number;
   limit: number;
@@ -333,3 +613,22 @@ export interface ModSearchResult {
   date_updated: string;
   date_created: string;
 }
+
+export interface ModDetails {
+  id: string;
+  slug: string;
+  name: string;
+  description: string;
+  body: string;
+  author: string;
+  downloads: number;
+  icon_url: string | undefined;
+  source: ModSource;
+  categories: string[];
+  game_versions: string[];
+  loaders: string[];
+  date_updated: string;
+  date_created: string;
+  source_url: string | undefined;
+  issues_url: string | undefined;
+}
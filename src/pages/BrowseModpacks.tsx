@@ -284,9 +284,9 @@ export function BrowseModpacks(): ReactNode {
       try {
         const response = await searchModpacks({
           query: debouncedQuery,
-          game_version: versionFilter !== "" ? versionFilter : undefined,
-          loader: loaderFilter !== "" ? loaderFilter : undefined,
-          category: undefined,
+          game_versions: versionFilter !== "" ? [versionFilter] : [],
+          loaders: loaderFilter !== "" ? [loaderFilter] : [],
+          categories: [],
           sort: sortBy,
           offset: searchOffset,
           limit: PAGE_SIZE,
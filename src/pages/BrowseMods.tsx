@@ -280,9 +280,9 @@ export function BrowseMods(): ReactNode {
       try {
         const response = await searchMods({
           query: debouncedQuery,
-          game_version: versionFilter !== "" ? versionFilter : undefined,
-          loader: loaderFilter !== "" ? loaderFilter : undefined,
-          category: undefined,
+          game_versions: versionFilter !== "" ? [versionFilter] : [],
+          loaders: loaderFilter !== "" ? [loaderFilter] : [],
+          categories: [],
           sort: sortBy,
           offset: searchOffset,
           limit: PAGE_SIZE,
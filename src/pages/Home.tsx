@@ -106,6 +106,8 @@ function shortStageLabel(stage: InstallStage): string {
       return "Registering…";
     case "completed":
       return "Done!";
+    case "cancelled":
+      return "Cancelled";
     case "failed":
       return "Failed";
   }
@@ -672,9 +674,11 @@ export function Home(): ReactNode {
   const {
     status: gameStatus,
     launch,
+    launchAnyway,
     isRunning,
     isPreparing,
     launchError,
+    blockedInstanceId,
     isDownloadingBeforeLaunch,
     downloadProgress,
     activeInstanceId,
@@ -904,11 +908,23 @@ export function Home(): ReactNode {
               />
             </div>
             <span
-              className="text-sm"
+              className="flex-1 text-sm"
               style={{ color: "var(--color-accent-red)" }}
             >
               {launchError}
             </span>
+            {blockedInstanceId !== undefined && (
+              <Button
+                size="sm"
+                variant="secondary"
+                disabled={actionsLocked}
+                onClick={() => {
+                  void launchAnyway();
+                }}
+              >
+                Lancer quand même
+              </Button>
+            )}
           </div>
         </Card>
       )}
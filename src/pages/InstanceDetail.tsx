@@ -92,6 +92,8 @@ function stageLabel(stage: InstallStage): string {
       return "Registering mods…";
     case "completed":
       return "Installation complete!";
+    case "cancelled":
+      return "Installation cancelled";
     case "failed":
       return `Failed: ${stage.message}`;
   }
@@ -430,10 +432,12 @@ export function InstanceDetail(): ReactNode {
   const {
     status: gameStatus,
     launch,
+    launchAnyway,
     kill,
     isRunning,
     isPreparing,
     launchError,
+    blockedInstanceId,
     downloadProgress,
     isDownloadingBeforeLaunch,
     activeInstanceId,
@@ -711,11 +715,23 @@ export function InstanceDetail(): ReactNode {
         >
           <AlertCircle size={14} style={{ color: "var(--color-accent-red)" }} />
           <span
-            className="text-xs"
+            className="flex-1 text-xs"
             style={{ color: "var(--color-accent-red)" }}
           >
             {launchError}
           </span>
+          {blockedInstanceId === id && (
+            <Button
+              size="sm"
+              variant="secondary"
+              disabled={isPreparing || isRunning || isDownloadingBeforeLaunch}
+              onClick={() => {
+                void launchAnyway();
+              }}
+            >
+              Lancer quand même
+            </Button>
+          )}
         </div>
       )}
 